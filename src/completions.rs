@@ -0,0 +1,129 @@
+// Shell completion scripts for the `morris` command line, printed by
+// `morris --completions <shell>` and meant to be sourced from the shell profile.
+pub struct CompletionGenerator {
+    flags: Vec<(&'static str, &'static str)>,
+}
+
+impl CompletionGenerator {
+    pub fn new() -> Self {
+        Self {
+            flags: vec![
+                ("-e", "Execute an intent and exit"),
+                ("--eval", "Execute an intent and exit"),
+                ("--var", "Set a variable (name=value)"),
+                ("--strict", "Stop at the first failed intent"),
+                ("--dry-run", "Parse the script without executing"),
+                ("--profile", "Time each intent"),
+                ("--sandbox", "Refuse shell commands and HTTP requests"),
+                ("--allow-http", "Permit outgoing HTTP requests"),
+                ("--color", "Force colored output"),
+                ("--no-color", "Disable colored output"),
+                ("--init", "Create a new project directory"),
+                ("--check", "Validate a project directory"),
+                ("--completions", "Print a shell completion script"),
+                ("--help", "Show usage"),
+            ],
+        }
+    }
+
+    pub fn generate(&self, shell: &str) -> Result<String, String> {
+        match shell {
+            "bash" => Ok(self.bash()),
+            "zsh" => Ok(self.zsh()),
+            "fish" => Ok(self.fish()),
+            other => Err(format!("Unsupported shell '{}'. Use bash, zsh or fish", other)),
+        }
+    }
+
+    fn flag_names(&self) -> String {
+        self.flags.iter().map(|(flag, _)| *flag).collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn bash(&self) -> String {
+        format!(r#"# morris bash completion - add to ~/.bashrc:
+#   source <(morris --completions bash)
+_morris() {{
+    local cur prev
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    case "$prev" in
+        --completions)
+            COMPREPLY=( $(compgen -W "bash zsh fish" -- "$cur") )
+            return ;;
+        -e|--eval|--var|--init)
+            return ;;
+        --check)
+            COMPREPLY=( $(compgen -d -- "$cur") )
+            return ;;
+    esac
+
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=( $(compgen -W "{flags}" -- "$cur") )
+    else
+        COMPREPLY=( $(compgen -f -X '!*.msh' -- "$cur") $(compgen -d -- "$cur") )
+    fi
+}}
+complete -o filenames -F _morris morris
+"#, flags = self.flag_names())
+    }
+
+    pub fn zsh(&self) -> String {
+        let mut script = String::from("#compdef morris\n# morris zsh completion - add to ~/.zshrc:\n#   source <(morris --completions zsh)\n_morris() {\n    _arguments \\\n");
+        for (flag, description) in &self.flags {
+            let spec = match *flag {
+                "-e" | "--eval" => format!("'*{}[{}]:intent:'", flag, description),
+                "--var" => format!("'*{}[{}]:name=value:'", flag, description),
+                "--init" => format!("'{}[{}]:project name:'", flag, description),
+                "--check" => format!("'{}[{}]:project dir:_files -/'", flag, description),
+                "--completions" => format!("'{}[{}]:shell:(bash zsh fish)'", flag, description),
+                _ => format!("'{}[{}]'", flag, description),
+            };
+            script.push_str(&format!("        {} \\\n", spec));
+        }
+        script.push_str("        '*:script:_files -g \"*.msh\"'\n}\ncompdef _morris morris\n");
+        script
+    }
+
+    pub fn fish(&self) -> String {
+        let mut script = String::from("# morris fish completion - save as ~/.config/fish/completions/morris.fish\n");
+        script.push_str("complete -c morris -f -a '(__fish_complete_suffix .msh)'\n");
+        for (flag, description) in &self.flags {
+            let option = match flag.strip_prefix("--") {
+                Some(long) => format!("-l {}", long),
+                None => format!("-s {}", flag.trim_start_matches('-')),
+            };
+            let argument = match *flag {
+                "-e" | "--eval" | "--var" | "--init" => " -r",
+                "--check" => " -r -a '(__fish_complete_directories)'",
+                "--completions" => " -x -a 'bash zsh fish'",
+                _ => "",
+            };
+            script.push_str(&format!("complete -c morris {}{} -d '{}'\n", option, argument, description));
+        }
+        script
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_each_shell() {
+        let generator = CompletionGenerator::new();
+        let bash = generator.generate("bash").unwrap();
+        assert!(bash.contains("complete -o filenames -F _morris morris"));
+        assert!(bash.contains("--dry-run --profile"));
+
+        let zsh = generator.generate("zsh").unwrap();
+        assert!(zsh.starts_with("#compdef morris"));
+        assert!(zsh.contains("'--completions[Print a shell completion script]:shell:(bash zsh fish)'"));
+
+        let fish = generator.generate("fish").unwrap();
+        assert!(fish.contains("complete -c morris -s e -r -d 'Execute an intent and exit'"));
+        assert!(fish.contains("complete -c morris -l strict -d 'Stop at the first failed intent'"));
+
+        assert!(generator.generate("powershell").is_err());
+    }
+}
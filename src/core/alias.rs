@@ -0,0 +1,132 @@
+// File: src/core/alias.rs
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+// Command shortcuts kept in ~/.morris_aliases.json as { "name": "expansion" }.
+// Expansions may chain several intents with `&&`
+pub struct AliasManager {
+    path: PathBuf,
+    aliases: BTreeMap<String, String>,
+}
+
+impl AliasManager {
+    pub fn new() -> Self {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        let path = home.join(".morris_aliases.json");
+        // A missing or unreadable file just means no aliases yet
+        let aliases = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, aliases }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn aliases(&self) -> &BTreeMap<String, String> {
+        &self.aliases
+    }
+
+    pub fn define(&mut self, name: &str, expansion: &str) -> Result<(), String> {
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+            return Err(format!("Invalid alias name '{}': use letters, digits, '_' or '-'", name));
+        }
+        if crate::core::intent::is_verb_keyword(name) {
+            return Err(format!("Alias name '{}' is a built-in intent; choose another name", name));
+        }
+        if expansion.trim().is_empty() {
+            return Err(format!("Alias '{}' needs a non-empty expansion", name));
+        }
+        self.aliases.insert(name.to_string(), expansion.trim().to_string());
+        self.save()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), String> {
+        if self.aliases.remove(name).is_none() {
+            return Err(format!("No alias named '{}'", name));
+        }
+        self.save()
+    }
+
+    // Expand a line whose first word is an alias into the intents it stands for.
+    // Anything after the alias name is appended to the last intent. Expansion is
+    // a single pass, so aliases cannot recurse
+    pub fn expand(&self, input: &str) -> Option<Vec<String>> {
+        let input = input.trim();
+        let (name, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+        let expansion = self.aliases.get(name)?;
+        // A hand-edited aliases file could still name a verb; the verb wins
+        if crate::core::intent::is_verb_keyword(name) {
+            return None;
+        }
+
+        let mut intents: Vec<String> = expansion.split("&&")
+            .map(|part| part.trim().to_string())
+            .filter(|part| !part.is_empty())
+            .collect();
+        if let Some(last) = intents.last_mut() {
+            if !rest.trim().is_empty() {
+                last.push(' ');
+                last.push_str(rest.trim());
+            }
+        }
+        Some(intents)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(&self.aliases)
+            .map_err(|e| format!("Failed to encode aliases: {}", e))?;
+        fs::write(&self.path, content)
+            .map_err(|e| format!("Cannot write aliases file '{}': {}", self.path.display(), e))
+    }
+}
+
+impl Default for AliasManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(tag: &str) -> AliasManager {
+        let path = std::env::temp_dir().join(format!("morris_test_aliases_{}_{}.json", tag, std::process::id()));
+        AliasManager { path, aliases: BTreeMap::new() }
+    }
+
+    #[test]
+    fn test_define_validates_names() {
+        let mut aliases = manager("define");
+        assert!(aliases.define("bad name", "set a = 1").is_err());
+        assert_eq!(aliases.define("set", "writeout(1)").unwrap_err(), "Alias name 'set' is a built-in intent; choose another name");
+        assert_eq!(aliases.define("exit", "writeout(1)").unwrap_err(), "Alias name 'exit' is a built-in intent; choose another name");
+        assert_eq!(aliases.define("go", " ").unwrap_err(), "Alias 'go' needs a non-empty expansion");
+
+        aliases.define("go", "set a = 1").unwrap();
+        let saved = fs::read_to_string(aliases.path()).unwrap();
+        assert!(saved.contains("\"go\": \"set a = 1\""));
+        aliases.remove("go").unwrap();
+        assert!(aliases.remove("go").is_err());
+        fs::remove_file(aliases.path()).ok();
+    }
+
+    #[test]
+    fn test_expand_appends_arguments_to_last_intent() {
+        let mut aliases = manager("expand");
+        aliases.aliases.insert("ship".to_string(), "set env = \"prod\" && writeout".to_string());
+        assert_eq!(
+            aliases.expand("ship {env}").unwrap(),
+            vec!["set env = \"prod\"".to_string(), "writeout {env}".to_string()]
+        );
+        assert_eq!(aliases.expand("unknown"), None);
+
+        // A verb name in a hand-edited file never replaces the verb
+        aliases.aliases.insert("set".to_string(), "writeout(1)".to_string());
+        assert_eq!(aliases.expand("set a = 2"), None);
+    }
+}
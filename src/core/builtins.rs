@@ -28,7 +28,7 @@ pub fn len(value: &Value) -> Result<Value, String> {
         Value::List(items) => Ok(Value::Int(items.len() as i64)),
         Value::Dict(map) => Ok(Value::Int(map.len() as i64)),
         Value::Json(json_str) => Ok(Value::Int(json_str.len() as i64)),
-        
+        Value::Null => Ok(Value::Int(0)),
     }
 }
 
@@ -96,7 +96,7 @@ fn convert_json_value(json_val: &serde_json::Value) -> Value {
     }
 }
 
-fn convert_to_json_value(value: &Value) -> serde_json::Value {
+pub fn convert_to_json_value(value: &Value) -> serde_json::Value {
     match value {
         Value::Str(s) => serde_json::Value::String(s.clone()),
         Value::Int(i) => serde_json::Value::Number(serde_json::Number::from(*i)),
@@ -120,6 +120,150 @@ fn convert_to_json_value(value: &Value) -> serde_json::Value {
             serde_json::from_str(json_str)
                 .unwrap_or(serde_json::Value::String(json_str.clone()))
         },
+        Value::Null => serde_json::Value::Null,
+    }
+}
+
+// `---` separated documents (as in Kubernetes manifests) come back as a list of documents
+pub fn parse_yaml(yaml_str: &str) -> Result<Value, String> {
+    use serde::Deserialize;
+
+    let mut documents = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(yaml_str) {
+        let yaml = serde_yaml::Value::deserialize(document)
+            .map_err(|e| format!("Invalid YAML: {}", e))?;
+        documents.push(convert_yaml_value(&yaml));
+    }
+
+    match documents.len() {
+        0 => Ok(Value::Null),
+        1 => Ok(documents.remove(0)),
+        _ => Ok(Value::List(documents)),
+    }
+}
+
+pub fn to_yaml(value: &Value) -> Result<String, String> {
+    serde_yaml::to_string(&convert_to_json_value(value))
+        .map_err(|e| format!("Cannot serialize to YAML: {}", e))
+}
+
+fn convert_yaml_value(yaml: &serde_yaml::Value) -> Value {
+    match yaml {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(b) => Value::Bool(*b),
+        serde_yaml::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Int(i),
+            None => Value::Float(n.as_f64().unwrap_or(0.0)),
+        },
+        serde_yaml::Value::String(s) => Value::Str(s.clone()),
+        serde_yaml::Value::Sequence(items) => Value::List(items.iter().map(convert_yaml_value).collect()),
+        // Dict keys are strings, so other scalar keys (`1: one`, `true: yes`) are written out
+        serde_yaml::Value::Mapping(map) => Value::Dict(map.iter()
+            .map(|(key, value)| {
+                let key = match key {
+                    serde_yaml::Value::String(s) => s.clone(),
+                    other => convert_yaml_value(other).to_string(),
+                };
+                (key, convert_yaml_value(value))
+            })
+            .collect()),
+        // Tags such as !Ref are dropped, keeping the tagged value
+        serde_yaml::Value::Tagged(tagged) => convert_yaml_value(&tagged.value),
+    }
+}
+
+// With headers each row becomes a dict keyed by the first row, otherwise a list of fields.
+// Fields that look like numbers or booleans are typed; everything else stays a string
+pub fn parse_csv(csv_str: &str, headers: bool) -> Result<Value, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(headers)
+        .trim(csv::Trim::All)
+        .from_reader(csv_str.as_bytes());
+
+    let names: Vec<String> = if headers {
+        reader.headers()
+            .map_err(|e| format!("Invalid CSV header: {}", e))?
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+        let fields = record.iter().map(csv_field_value);
+        rows.push(if headers {
+            Value::Dict(names.iter().cloned().zip(fields).collect())
+        } else {
+            Value::List(fields.collect())
+        });
+    }
+    Ok(Value::List(rows))
+}
+
+// A list of dicts gets a header row of every key (sorted, since dicts are unordered);
+// a list of lists is written as plain rows
+pub fn to_csv(value: &Value) -> Result<String, String> {
+    let rows = match value {
+        Value::List(rows) => rows,
+        other => return Err(format!("to_csv() requires a list of dicts or lists, got {}", other.type_name())),
+    };
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let dict_rows = rows.iter().all(|row| matches!(row, Value::Dict(_)));
+    if dict_rows && !rows.is_empty() {
+        let mut names: Vec<&String> = rows.iter()
+            .filter_map(|row| match row {
+                Value::Dict(map) => Some(map.keys()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        names.sort();
+        names.dedup();
+
+        writer.write_record(&names).map_err(|e| format!("Cannot write CSV: {}", e))?;
+        for row in rows {
+            if let Value::Dict(map) = row {
+                let fields = names.iter().map(|name| map.get(*name).map(csv_field_text).unwrap_or_default());
+                writer.write_record(fields).map_err(|e| format!("Cannot write CSV: {}", e))?;
+            }
+        }
+    } else {
+        for row in rows {
+            let fields: Vec<String> = match row {
+                Value::List(items) => items.iter().map(csv_field_text).collect(),
+                other => return Err(format!("to_csv() rows must all be dicts or all be lists, found {}", other.type_name())),
+            };
+            writer.write_record(&fields).map_err(|e| format!("Cannot write CSV: {}", e))?;
+        }
+    }
+
+    let bytes = writer.into_inner().map_err(|e| format!("Cannot write CSV: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("Cannot write CSV: {}", e))
+}
+
+fn csv_field_value(field: &str) -> Value {
+    if let Ok(i) = field.parse::<i64>() {
+        Value::Int(i)
+    } else if let Ok(f) = field.parse::<f64>() {
+        Value::Float(f)
+    } else {
+        match field {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => Value::Str(field.to_string()),
+        }
+    }
+}
+
+fn csv_field_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::List(_) | Value::Dict(_) => to_json(value).unwrap_or_default(),
+        other => other.to_string(),
     }
 }
 
@@ -313,15 +457,7 @@ pub fn sort(list_value: &Value) -> Result<Value, String> {
             
             // Simple sorting for homogeneous lists
             // This is basic - you might want more sophisticated sorting
-            sorted_items.sort_by(|a, b| {
-                match (a, b) {
-                    (Value::Int(a), Value::Int(b)) => a.cmp(b),
-                    (Value::Float(a), Value::Float(b)) => a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal),
-                    (Value::Str(a), Value::Str(b)) => a.cmp(b),
-                    // Mixed types - convert to string for comparison
-                    _ => a.to_string().cmp(&b.to_string()),
-                }
-            });
+            sorted_items.sort_by(sort_order);
             
             Ok(Value::List(sorted_items))
         }
@@ -329,6 +465,16 @@ pub fn sort(list_value: &Value) -> Result<Value, String> {
     }
 }
 
+fn sort_order(a: &Value, b: &Value) -> std::cmp::Ordering {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => a.cmp(b),
+        (Value::Float(a), Value::Float(b)) => a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal),
+        (Value::Str(a), Value::Str(b)) => a.cmp(b),
+        // Mixed types - convert to string for comparison
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
 pub fn split(string_value: &Value, delimiter: &Value) -> Result<Value, String> {
     match (string_value, delimiter) {
         (Value::Str(s), Value::Str(delim)) => {
@@ -735,4 +881,1753 @@ pub fn merge(dict1_value: &Value, dict2_value: &Value) -> Result<Value, String>
         _ => Err(format!("merge() requires two dictionaries, got {}/{}", 
                         dict1_value.type_name(), dict2_value.type_name())),
     }
-}
\ No newline at end of file
+}
+
+// Synthetic data generation for `generate "schema" count N into var`
+#[derive(Default)]
+pub struct DataGenerator {
+    rng: rand::rngs::ThreadRng,
+}
+
+const GENERATOR_WORDS: &[&str] = &[
+    "amber", "basalt", "cinder", "delta", "ember", "flint", "granite", "harbor",
+    "iron", "juniper", "kettle", "lantern", "marble", "nickel", "onyx", "pewter",
+    "quartz", "rivet", "slate", "tinder", "umber", "vellum", "willow", "zephyr",
+];
+
+impl DataGenerator {
+    pub fn new() -> Self {
+        Self { rng: rand::thread_rng() }
+    }
+
+    pub fn generate_row(&mut self, schema: &crate::core::schema::Schema) -> Result<Value, String> {
+        let mut row = HashMap::new();
+        for field in &schema.fields {
+            row.insert(field.name.clone(), self.generate_field(field)?);
+        }
+        Ok(Value::Dict(row))
+    }
+
+    fn generate_field(&mut self, field: &crate::core::schema::SchemaField) -> Result<Value, String> {
+        use crate::core::schema::FieldType;
+        use rand::Rng;
+
+        Ok(match field.field_type {
+            FieldType::String | FieldType::Any => {
+                let word = GENERATOR_WORDS[self.rng.gen_range(0..GENERATOR_WORDS.len())];
+                Value::Str(word.to_string())
+            }
+            FieldType::Int => {
+                let (min, max) = field_range(field, 0.0, 100.0)?;
+                Value::Int(self.rng.gen_range(min as i64..=max as i64))
+            }
+            FieldType::Float => {
+                let (min, max) = field_range(field, 0.0, 1.0)?;
+                if min == max {
+                    Value::Float(min)
+                } else {
+                    Value::Float(self.rng.gen_range(min..max))
+                }
+            }
+            FieldType::Bool => Value::Bool(self.rng.gen_bool(0.5)),
+            FieldType::Timestamp => {
+                // Range is in days relative to now, default: last 30 days
+                let (min_days, max_days) = field_range(field, -30.0, 0.0)?;
+                let offset_secs = if min_days == max_days {
+                    (min_days * 86400.0) as i64
+                } else {
+                    (self.rng.gen_range(min_days..max_days) * 86400.0) as i64
+                };
+                let ts = chrono::Duration::try_seconds(offset_secs)
+                    .and_then(|offset| Utc::now().checked_add_signed(offset))
+                    .ok_or_else(|| format!("Field '{}': timestamp range is too far from now", field.name))?;
+                Value::Str(ts.to_rfc3339())
+            }
+            FieldType::List => {
+                let len = self.rng.gen_range(0..=3);
+                Value::List((0..len).map(|_| Value::Int(self.rng.gen_range(0..100))).collect())
+            }
+            FieldType::Dict => Value::Dict(HashMap::new()),
+        })
+    }
+}
+
+// The field's min/max, or the defaults. gen_range panics on NaN, infinite or
+// reversed bounds, so those are rejected here instead
+fn field_range(field: &crate::core::schema::SchemaField, default_min: f64, default_max: f64) -> Result<(f64, f64), String> {
+    let min = field.min.unwrap_or(default_min);
+    let max = field.max.unwrap_or(default_max);
+    if !min.is_finite() || !max.is_finite() || !(max - min).is_finite() {
+        return Err(format!("Field '{}': range {}..{} must be finite", field.name, min, max));
+    }
+    if min > max {
+        return Err(format!("Field '{}': min {} is greater than max {}", field.name, min, max));
+    }
+    Ok((min, max))
+}
+
+// Upper bound on generated rows
+const GENERATE_LIMIT: usize = 100_000;
+
+pub fn generate_data(schema: &crate::core::schema::Schema, count: usize) -> Result<Value, String> {
+    if count > GENERATE_LIMIT {
+        return Err(format!("generate count {} exceeds the limit of {}", count, GENERATE_LIMIT));
+    }
+    let mut generator = DataGenerator::new();
+    let rows = (0..count).map(|_| generator.generate_row(schema)).collect::<Result<_, _>>()?;
+    Ok(Value::List(rows))
+}
+
+// Field-level diff of two dicts. Each key maps to a dict with a "status" of
+// "=", "+", "-" or "~"; nested dicts carry a "changes" diff, lists carry
+// "added"/"removed" elements.
+pub fn diff_dicts(a: &Value, b: &Value) -> Result<Value, String> {
+    match (a, b) {
+        (Value::Dict(map_a), Value::Dict(map_b)) => {
+            let mut keys: Vec<&String> = map_a.keys().chain(map_b.keys()).collect();
+            keys.sort();
+            keys.dedup();
+
+            let mut diff = HashMap::new();
+            for key in keys {
+                let mut entry = HashMap::new();
+                match (map_a.get(key), map_b.get(key)) {
+                    (Some(old), Some(new)) if old == new => {
+                        entry.insert("status".to_string(), Value::Str("=".to_string()));
+                    }
+                    (Some(old @ Value::Dict(_)), Some(new @ Value::Dict(_))) => {
+                        entry.insert("status".to_string(), Value::Str("~".to_string()));
+                        entry.insert("changes".to_string(), diff_dicts(old, new)?);
+                    }
+                    (Some(Value::List(old_items)), Some(Value::List(new_items))) => {
+                        let added: Vec<Value> = new_items.iter().filter(|v| !old_items.contains(v)).cloned().collect();
+                        let removed: Vec<Value> = old_items.iter().filter(|v| !new_items.contains(v)).cloned().collect();
+                        entry.insert("status".to_string(), Value::Str("~".to_string()));
+                        entry.insert("added".to_string(), Value::List(added));
+                        entry.insert("removed".to_string(), Value::List(removed));
+                    }
+                    (Some(old), Some(new)) => {
+                        entry.insert("status".to_string(), Value::Str("~".to_string()));
+                        entry.insert("old".to_string(), old.clone());
+                        entry.insert("new".to_string(), new.clone());
+                    }
+                    (Some(old), None) => {
+                        entry.insert("status".to_string(), Value::Str("-".to_string()));
+                        entry.insert("old".to_string(), old.clone());
+                    }
+                    (None, Some(new)) => {
+                        entry.insert("status".to_string(), Value::Str("+".to_string()));
+                        entry.insert("new".to_string(), new.clone());
+                    }
+                    (None, None) => continue,
+                }
+                diff.insert(key.clone(), Value::Dict(entry));
+            }
+            Ok(Value::Dict(diff))
+        }
+        _ => Err(format!("diff requires two dictionaries, got {}/{}",
+                        a.type_name(), b.type_name())),
+    }
+}
+
+// Render a diff produced by diff_dicts() as indented +/-/~/= lines
+pub fn format_dict_diff(diff: &Value, indent: usize) -> String {
+    let mut output = String::new();
+    let pad = " ".repeat(indent);
+
+    if let Value::Dict(entries) = diff {
+        let mut keys: Vec<&String> = entries.keys().collect();
+        keys.sort();
+
+        for key in keys {
+            let entry = match &entries[key] {
+                Value::Dict(entry) => entry,
+                _ => continue,
+            };
+            let status = match entry.get("status") {
+                Some(Value::Str(s)) => s.as_str(),
+                _ => "?",
+            };
+            let field = |name: &str| entry.get(name).map(|v| v.display()).unwrap_or_default();
+
+            match status {
+                "=" => output.push_str(&format!("{}= {}\n", pad, key)),
+                "+" => output.push_str(&format!("{}+ {}: {}\n", pad, key, field("new"))),
+                "-" => output.push_str(&format!("{}- {}: {}\n", pad, key, field("old"))),
+                _ => {
+                    if let Some(changes) = entry.get("changes") {
+                        output.push_str(&format!("{}~ {}:\n", pad, key));
+                        output.push_str(&format_dict_diff(changes, indent + 2));
+                    } else if entry.contains_key("added") {
+                        output.push_str(&format!("{}~ {}: added {}, removed {}\n",
+                            pad, key, field("added"), field("removed")));
+                    } else {
+                        output.push_str(&format!("{}~ {}: {} → {}\n", pad, key, field("old"), field("new")));
+                    }
+                }
+            }
+        }
+    }
+
+    output
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MergeStrategy {
+    LastWins,
+    FirstWins,
+    ErrorOnConflict,
+    Append,
+}
+
+impl MergeStrategy {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "last_wins" => Ok(MergeStrategy::LastWins),
+            "first_wins" => Ok(MergeStrategy::FirstWins),
+            "error_on_conflict" => Ok(MergeStrategy::ErrorOnConflict),
+            "append" => Ok(MergeStrategy::Append),
+            _ => Err(format!("Unknown merge strategy '{}' (use last_wins, first_wins, error_on_conflict, append)", s)),
+        }
+    }
+}
+
+// Recursive merge - unlike merge(), nested dicts are merged key by key
+pub fn merge_deep(a: &Value, b: &Value, strategy: MergeStrategy) -> Result<Value, String> {
+    merge_deep_at(a, b, strategy, "")
+}
+
+fn merge_deep_at(a: &Value, b: &Value, strategy: MergeStrategy, path: &str) -> Result<Value, String> {
+    match (a, b) {
+        (Value::Dict(map_a), Value::Dict(map_b)) => {
+            let mut merged = map_a.clone();
+            for (key, value_b) in map_b {
+                let key_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                let value = match map_a.get(key) {
+                    Some(value_a) => merge_deep_at(value_a, value_b, strategy, &key_path)?,
+                    None => value_b.clone(),
+                };
+                merged.insert(key.clone(), value);
+            }
+            Ok(Value::Dict(merged))
+        }
+        _ if path.is_empty() => Err(format!("merge requires two dictionaries, got {}/{}",
+                                    a.type_name(), b.type_name())),
+        _ if a == b => Ok(a.clone()),
+        (Value::List(items_a), Value::List(items_b)) if strategy == MergeStrategy::Append => {
+            let mut items = items_a.clone();
+            items.extend(items_b.iter().cloned());
+            Ok(Value::List(items))
+        }
+        _ => match strategy {
+            MergeStrategy::LastWins | MergeStrategy::Append => Ok(b.clone()),
+            MergeStrategy::FirstWins => Ok(a.clone()),
+            MergeStrategy::ErrorOnConflict => Err(format!("Merge conflict at '{}': {} vs {}",
+                                                  path, a.display(), b.display())),
+        },
+    }
+}
+
+// Treat JSON nulls (parsed as "null") and missing fields as null
+fn is_null_like(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => true,
+        Some(Value::Str(s)) => s == "null",
+        Some(_) => false,
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+    let rank = p * (sorted.len() - 1) as f64;
+    let low = rank.floor() as usize;
+    let high = rank.ceil() as usize;
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+}
+
+// One-pass statistics over a list (or one field of a list of dicts)
+pub fn summarize(list_value: &Value, field: Option<&str>) -> Result<Value, String> {
+    let items = match list_value {
+        Value::List(items) => items,
+        _ => return Err(format!("summarize() requires list, got {}", list_value.type_name())),
+    };
+
+    let mut null_count = 0;
+    let mut values: Vec<&Value> = Vec::new();
+    for item in items {
+        let value = match field {
+            Some(key) => match item {
+                Value::Dict(map) => map.get(key),
+                _ => return Err(format!("summarize field '{}' requires list of dicts, found {}", key, item.type_name())),
+            },
+            None => Some(item),
+        };
+        if is_null_like(value) {
+            null_count += 1;
+        } else if let Some(value) = value {
+            values.push(value);
+        }
+    }
+
+    let mut unique: Vec<String> = values.iter().map(|v| v.display()).collect();
+    unique.sort();
+    unique.dedup();
+
+    let mut stats = HashMap::new();
+    stats.insert("count".to_string(), Value::Int(items.len() as i64));
+    stats.insert("null_count".to_string(), Value::Int(null_count as i64));
+    stats.insert("unique_count".to_string(), Value::Int(unique.len() as i64));
+
+    let numbers: Option<Vec<f64>> = values.iter()
+        .map(|v| match v {
+            Value::Int(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        })
+        .collect();
+
+    match numbers {
+        Some(mut numbers) if !numbers.is_empty() => {
+            numbers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let n = numbers.len() as f64;
+            let mean = numbers.iter().sum::<f64>() / n;
+            let variance = numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+            stats.insert("min".to_string(), Value::Float(numbers[0]));
+            stats.insert("max".to_string(), Value::Float(numbers[numbers.len() - 1]));
+            stats.insert("mean".to_string(), Value::Float(mean));
+            stats.insert("median".to_string(), Value::Float(percentile(&numbers, 0.5)));
+            stats.insert("p25".to_string(), Value::Float(percentile(&numbers, 0.25)));
+            stats.insert("p75".to_string(), Value::Float(percentile(&numbers, 0.75)));
+            stats.insert("p99".to_string(), Value::Float(percentile(&numbers, 0.99)));
+            stats.insert("std_dev".to_string(), Value::Float(variance.sqrt()));
+        }
+        _ => {
+            let strings: Vec<&String> = values.iter()
+                .filter_map(|v| match v {
+                    Value::Str(s) => Some(s),
+                    _ => None,
+                })
+                .collect();
+
+            if !strings.is_empty() {
+                let mut counts: HashMap<&String, usize> = HashMap::new();
+                for s in &strings {
+                    *counts.entry(*s).or_insert(0) += 1;
+                }
+                // Ties go to the alphabetically first value so output is stable
+                let most_common = counts.iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                    .map(|(s, _)| (*s).clone())
+                    .unwrap_or_default();
+                let lengths: Vec<usize> = strings.iter().map(|s| s.chars().count()).collect();
+
+                stats.insert("most_common".to_string(), Value::Str(most_common));
+                stats.insert("min_length".to_string(), Value::Int(*lengths.iter().min().unwrap_or(&0) as i64));
+                stats.insert("max_length".to_string(), Value::Int(*lengths.iter().max().unwrap_or(&0) as i64));
+            }
+        }
+    }
+
+    Ok(Value::Dict(stats))
+}
+
+// Long → wide: one row per distinct row_key value, one column per distinct col_key value
+pub fn pivot(list_value: &Value, row_key: &str, col_key: &str, val_key: &str) -> Result<Value, String> {
+    let items = match list_value {
+        Value::List(items) => items,
+        _ => return Err(format!("pivot() requires list, got {}", list_value.type_name())),
+    };
+
+    let mut row_order: Vec<Value> = Vec::new();
+    let mut columns: Vec<String> = Vec::new();
+    // Keyed by row position, so rows "1" and 1 stay apart
+    let mut cells: HashMap<(usize, String), Value> = HashMap::new();
+
+    for item in items {
+        let map = match item {
+            Value::Dict(map) => map,
+            _ => return Err(format!("pivot() requires list of dicts, found {}", item.type_name())),
+        };
+        let row = map.get(row_key)
+            .ok_or_else(|| format!("pivot(): row missing key '{}'", row_key))?;
+        // Column names are plain text: "1" and 1 both become column 1, never "\"1\""
+        let col = match map.get(col_key) {
+            Some(Value::Str(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => return Err(format!("pivot(): row missing key '{}'", col_key)),
+        };
+        // The row key is a column of its own in the output, so it cannot double as a pivoted one
+        if col == row_key {
+            return Err(format!("pivot(): column value '{}' collides with the row key '{}'", col, row_key));
+        }
+        let val = map.get(val_key).cloned().unwrap_or(Value::Null);
+
+        let row_index = match row_order.iter().position(|existing| existing == row) {
+            Some(index) => index,
+            None => {
+                row_order.push(row.clone());
+                row_order.len() - 1
+            }
+        };
+        if !columns.contains(&col) {
+            columns.push(col.clone());
+        }
+        cells.insert((row_index, col), val);
+    }
+
+    let rows = row_order.into_iter()
+        .enumerate()
+        .map(|(row_index, row)| {
+            let mut out = HashMap::new();
+            for col in &columns {
+                let cell = cells.get(&(row_index, col.clone())).cloned().unwrap_or(Value::Null);
+                out.insert(col.clone(), cell);
+            }
+            out.insert(row_key.to_string(), row);
+            Value::Dict(out)
+        })
+        .collect();
+
+    Ok(Value::List(rows))
+}
+
+// Wide → long: each non-row column becomes its own {row_col, "column", val_col} record
+pub fn unpivot(value: &Value, row_col: &str, val_col: &str) -> Result<Value, String> {
+    let rows: Vec<&Value> = match value {
+        Value::List(items) => items.iter().collect(),
+        Value::Dict(_) => vec![value],
+        _ => return Err(format!("unpivot() requires dict or list of dicts, got {}", value.type_name())),
+    };
+
+    let mut result = Vec::new();
+    for row in rows {
+        let map = match row {
+            Value::Dict(map) => map,
+            _ => return Err(format!("unpivot() requires list of dicts, found {}", row.type_name())),
+        };
+        let row_value = map.get(row_col)
+            .ok_or_else(|| format!("unpivot(): row missing key '{}'", row_col))?;
+
+        let mut keys: Vec<&String> = map.keys().filter(|k| k.as_str() != row_col).collect();
+        keys.sort();
+
+        for key in keys {
+            if map[key] == Value::Null {
+                continue;
+            }
+            let mut out = HashMap::new();
+            out.insert(row_col.to_string(), row_value.clone());
+            out.insert("column".to_string(), Value::Str(key.clone()));
+            out.insert(val_col.to_string(), map[key].clone());
+            result.push(Value::Dict(out));
+        }
+    }
+
+    Ok(Value::List(result))
+}
+
+fn join_rows<'a>(list_value: &'a Value, side: &str) -> Result<Vec<&'a HashMap<String, Value>>, String> {
+    match list_value {
+        Value::List(items) => items.iter()
+            .map(|item| match item {
+                Value::Dict(map) => Ok(map),
+                _ => Err(format!("join requires list of dicts, {} list has {}", side, item.type_name())),
+            })
+            .collect(),
+        _ => Err(format!("join requires list, {} side is {}", side, list_value.type_name())),
+    }
+}
+
+fn join_lists(a: &Value, b: &Value, key: &str, keep_unmatched: bool) -> Result<Value, String> {
+    let rows_a = join_rows(a, "left")?;
+    let rows_b = join_rows(b, "right")?;
+
+    let fields_a: std::collections::HashSet<&String> = rows_a.iter().flat_map(|m| m.keys()).collect();
+    let mut fields_b: Vec<&String> = rows_b.iter().flat_map(|m| m.keys()).filter(|k| k.as_str() != key).collect();
+    fields_b.sort();
+    fields_b.dedup();
+
+    // Same non-key field on both sides → field_a / field_b
+    let conflicts: Vec<&String> = fields_b.iter().filter(|k| fields_a.contains(*k)).copied().collect();
+    let rename = |field: &String, suffix: &str| {
+        if conflicts.contains(&field) { format!("{}_{}", field, suffix) } else { field.clone() }
+    };
+
+    let mut result = Vec::new();
+    for row_a in &rows_a {
+        let key_value = match row_a.get(key) {
+            Some(v) => v,
+            None if keep_unmatched => &Value::Null,
+            None => continue,
+        };
+
+        let mut left = HashMap::new();
+        for (field, value) in row_a.iter() {
+            let name = if field == key { field.clone() } else { rename(field, "a") };
+            left.insert(name, value.clone());
+        }
+
+        let matches: Vec<&&HashMap<String, Value>> = rows_b.iter()
+            .filter(|row_b| *key_value != Value::Null && row_b.get(key) == Some(key_value))
+            .collect();
+
+        if matches.is_empty() {
+            if keep_unmatched {
+                let mut out = left.clone();
+                for field in &fields_b {
+                    out.insert(rename(field, "b"), Value::Null);
+                }
+                result.push(Value::Dict(out));
+            }
+            continue;
+        }
+
+        for row_b in matches {
+            let mut out = left.clone();
+            for (field, value) in row_b.iter() {
+                if field != key {
+                    out.insert(rename(field, "b"), value.clone());
+                }
+            }
+            result.push(Value::Dict(out));
+        }
+    }
+
+    Ok(Value::List(result))
+}
+
+pub fn inner_join(a: &Value, b: &Value, key: &str) -> Result<Value, String> {
+    join_lists(a, b, key, false)
+}
+
+pub fn left_join(a: &Value, b: &Value, key: &str) -> Result<Value, String> {
+    join_lists(a, b, key, true)
+}
+
+// Trailing window aggregate; positions before the first full window are null
+pub fn sliding_window(list_value: &Value, size: usize, func: &str) -> Result<Value, String> {
+    let items = match list_value {
+        Value::List(items) => items,
+        _ => return Err(format!("sliding_window() requires list, got {}", list_value.type_name())),
+    };
+    if size == 0 {
+        return Err("sliding_window() size must be at least 1".to_string());
+    }
+    if !matches!(func, "sum" | "avg" | "max" | "min") {
+        return Err(format!("Unknown window aggregate '{}' (use sum, avg, max, min)", func));
+    }
+
+    for item in items {
+        if !matches!(item, Value::Int(_) | Value::Float(_)) {
+            return Err(format!("sliding_window() requires numeric list, found {}", item.type_name()));
+        }
+    }
+
+    let mut result = Vec::with_capacity(items.len());
+    for end in 0..items.len() {
+        if end + 1 < size {
+            result.push(Value::Null);
+            continue;
+        }
+
+        let window = &items[end + 1 - size..=end];
+        let all_ints = window.iter().all(|v| matches!(v, Value::Int(_)));
+        let numbers: Vec<f64> = window.iter()
+            .map(|v| match v {
+                Value::Int(i) => *i as f64,
+                Value::Float(f) => *f,
+                _ => 0.0,
+            })
+            .collect();
+
+        let value = match func {
+            "avg" => Value::Float(numbers.iter().sum::<f64>() / size as f64),
+            "sum" => Value::Float(numbers.iter().sum::<f64>()),
+            "max" => Value::Float(numbers.iter().cloned().fold(f64::MIN, f64::max)),
+            _ => Value::Float(numbers.iter().cloned().fold(f64::MAX, f64::min)),
+        };
+        result.push(match value {
+            Value::Float(f) if all_ints && func != "avg" => Value::Int(f as i64),
+            other => other,
+        });
+    }
+
+    Ok(Value::List(result))
+}
+
+fn interpolation_input(list_value: &Value, name: &str) -> Result<Vec<Option<f64>>, String> {
+    match list_value {
+        Value::List(items) => items.iter()
+            .map(|item| match item {
+                Value::Int(i) => Ok(Some(*i as f64)),
+                Value::Float(f) => Ok(Some(*f)),
+                other if is_null_like(Some(other)) => Ok(None),
+                other => Err(format!("{}() requires numbers or nulls, found {}", name, other.type_name())),
+            })
+            .collect(),
+        _ => Err(format!("{}() requires list, got {}", name, list_value.type_name())),
+    }
+}
+
+// Keep the original element where there was one, so ints stay ints
+fn interpolation_output(list_value: &Value, filled: Vec<Option<f64>>) -> Value {
+    let items = match list_value {
+        Value::List(items) => items,
+        _ => return Value::List(Vec::new()),
+    };
+    Value::List(items.iter().zip(filled)
+        .map(|(original, filled)| match filled {
+            _ if !is_null_like(Some(original)) => original.clone(),
+            Some(f) => Value::Float(f),
+            None => Value::Null,
+        })
+        .collect())
+}
+
+// Nulls between two numbers are filled on the straight line between them;
+// leading and trailing nulls have no neighbour on one side and stay null
+pub fn interpolate_linear(list_value: &Value) -> Result<Value, String> {
+    let mut values = interpolation_input(list_value, "interpolate_linear")?;
+
+    let mut last_known: Option<usize> = None;
+    for i in 0..values.len() {
+        if values[i].is_none() {
+            continue;
+        }
+        if let Some(start) = last_known {
+            if i > start + 1 {
+                let (a, b) = (values[start].unwrap_or(0.0), values[i].unwrap_or(0.0));
+                let span = (i - start) as f64;
+                for (k, slot) in values.iter_mut().enumerate().take(i).skip(start + 1) {
+                    *slot = Some(a + (b - a) * (k - start) as f64 / span);
+                }
+            }
+        }
+        last_known = Some(i);
+    }
+
+    Ok(interpolation_output(list_value, values))
+}
+
+pub fn interpolate_forward_fill(list_value: &Value) -> Result<Value, String> {
+    let mut values = interpolation_input(list_value, "interpolate_forward_fill")?;
+    let mut last = None;
+    for slot in values.iter_mut() {
+        match slot {
+            Some(v) => last = Some(*v),
+            None => *slot = last,
+        }
+    }
+    Ok(interpolation_output(list_value, values))
+}
+
+pub fn interpolate_backward_fill(list_value: &Value) -> Result<Value, String> {
+    let mut values = interpolation_input(list_value, "interpolate_backward_fill")?;
+    let mut next = None;
+    for slot in values.iter_mut().rev() {
+        match slot {
+            Some(v) => next = Some(*v),
+            None => *slot = next,
+        }
+    }
+    Ok(interpolation_output(list_value, values))
+}
+
+fn numeric_list(list_value: &Value, name: &str) -> Result<Vec<f64>, String> {
+    match list_value {
+        Value::List(items) if items.is_empty() => Err(format!("{}() requires a non-empty list", name)),
+        Value::List(items) => items.iter()
+            .map(|item| match item {
+                Value::Int(i) => Ok(*i as f64),
+                Value::Float(f) => Ok(*f),
+                other => Err(format!("{}() requires numeric list, found {}", name, other.type_name())),
+            })
+            .collect(),
+        _ => Err(format!("{}() requires list, got {}", name, list_value.type_name())),
+    }
+}
+
+pub fn normalize_minmax(list_value: &Value) -> Result<Value, String> {
+    let numbers = numeric_list(list_value, "normalize_minmax")?;
+    let min = numbers.iter().cloned().fold(f64::MAX, f64::min);
+    let max = numbers.iter().cloned().fold(f64::MIN, f64::max);
+    if max == min {
+        return Err("normalize_minmax() range is zero (all values equal)".to_string());
+    }
+    Ok(Value::List(numbers.iter().map(|x| Value::Float((x - min) / (max - min))).collect()))
+}
+
+pub fn normalize_zscore(list_value: &Value) -> Result<Value, String> {
+    let numbers = numeric_list(list_value, "normalize_zscore")?;
+    let n = numbers.len() as f64;
+    let mean = numbers.iter().sum::<f64>() / n;
+    let std_dev = (numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if std_dev == 0.0 {
+        return Err("normalize_zscore() standard deviation is zero".to_string());
+    }
+    Ok(Value::List(numbers.iter().map(|x| Value::Float((x - mean) / std_dev)).collect()))
+}
+
+pub fn normalize_max(list_value: &Value) -> Result<Value, String> {
+    let numbers = numeric_list(list_value, "normalize_max")?;
+    let max = numbers.iter().cloned().fold(f64::MIN, f64::max);
+    if max == 0.0 {
+        return Err("normalize_max() maximum is zero".to_string());
+    }
+    Ok(Value::List(numbers.iter().map(|x| Value::Float(x / max)).collect()))
+}
+
+fn categories(list_value: &Value, name: &str) -> Result<(Vec<String>, Vec<String>), String> {
+    let items = match list_value {
+        Value::List(items) => items,
+        _ => return Err(format!("{}() requires list, got {}", name, list_value.type_name())),
+    };
+    let labels: Vec<String> = items.iter()
+        .map(|item| match item {
+            Value::Str(s) => Ok(s.clone()),
+            Value::Int(_) | Value::Bool(_) => Ok(item.to_string()),
+            other => Err(format!("{}() requires categorical values, found {}", name, other.type_name())),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut unique = labels.clone();
+    unique.sort();
+    unique.dedup();
+    Ok((labels, unique))
+}
+
+pub fn one_hot_encode(list_value: &Value) -> Result<Value, String> {
+    let (labels, unique) = categories(list_value, "one_hot_encode")?;
+    Ok(Value::List(labels.iter()
+        .map(|label| {
+            Value::Dict(unique.iter()
+                .map(|category| (category.clone(), Value::Bool(category == label)))
+                .collect())
+        })
+        .collect()))
+}
+
+// Returns (codes, mapping) where mapping is category → code, categories sorted
+pub fn label_encode(list_value: &Value) -> Result<(Value, Value), String> {
+    let (labels, unique) = categories(list_value, "label_encode")?;
+    let codes = labels.iter()
+        .map(|label| Value::Int(unique.iter().position(|c| c == label).unwrap_or(0) as i64))
+        .collect();
+    let mapping = unique.iter()
+        .enumerate()
+        .map(|(i, category)| (category.clone(), Value::Int(i as i64)))
+        .collect();
+    Ok((Value::List(codes), Value::Dict(mapping)))
+}
+
+// First value that is neither null nor the "<?>" pending-transaction placeholder
+pub fn coalesce(values: &[Value]) -> Value {
+    values.iter()
+        .find(|v| !is_null_like(Some(v)) && !matches!(v, Value::Str(s) if s == "<?>"))
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+// Stays Int while every element is Int; the first Float switches to Float
+pub fn sum(val: &Value) -> Result<Value, String> {
+    let items = match val {
+        Value::List(items) => items,
+        _ => return Err(format!("sum() requires list, got {}", val.type_name())),
+    };
+
+    let mut total = Value::Int(0);
+    for item in items {
+        total = match (&total, item) {
+            (Value::Int(a), Value::Int(b)) => Value::Int(a + b),
+            (Value::Int(a), Value::Float(b)) => Value::Float(*a as f64 + b),
+            (Value::Float(a), Value::Int(b)) => Value::Float(a + *b as f64),
+            (Value::Float(a), Value::Float(b)) => Value::Float(a + b),
+            (_, other) => return Err(format!("sum() requires numeric list, found {}", other.type_name())),
+        };
+    }
+    Ok(total)
+}
+
+// Numbers compare as f64 so Int and Float mix; strings compare lexicographically
+fn compare_ordered(a: &Value, b: &Value, name: &str) -> Result<std::cmp::Ordering, String> {
+    let as_number = |v: &Value| match v {
+        Value::Int(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    };
+    match (a, b) {
+        (Value::Str(x), Value::Str(y)) => Ok(x.cmp(y)),
+        _ => match (as_number(a), as_number(b)) {
+            (Some(x), Some(y)) => Ok(x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal)),
+            _ => Err(format!("{}() cannot compare {} and {}", name, a.type_name(), b.type_name())),
+        },
+    }
+}
+
+fn extreme(val: &Value, name: &str, keep: std::cmp::Ordering) -> Result<Value, String> {
+    let items = match val {
+        Value::List(items) if items.is_empty() => return Err(format!("{}() requires a non-empty list", name)),
+        Value::List(items) => items,
+        _ => return Err(format!("{}() requires list, got {}", name, val.type_name())),
+    };
+
+    let mut best = &items[0];
+    for item in &items[1..] {
+        if compare_ordered(item, best, name)? == keep {
+            best = item;
+        }
+    }
+    Ok(best.clone())
+}
+
+pub fn min(val: &Value) -> Result<Value, String> {
+    extreme(val, "min", std::cmp::Ordering::Less)
+}
+
+pub fn max(val: &Value) -> Result<Value, String> {
+    extreme(val, "max", std::cmp::Ordering::Greater)
+}
+
+pub fn abs(val: &Value) -> Result<Value, String> {
+    match val {
+        Value::Int(i) => i.checked_abs()
+            .map(Value::Int)
+            .ok_or_else(|| format!("abs() overflow for {}", i)),
+        Value::Float(f) => Ok(Value::Float(f.abs())),
+        _ => Err(format!("abs() requires int or float, got {}", val.type_name())),
+    }
+}
+
+// floor/ceil/round always hand back an Int, like Python
+fn to_int_with(val: &Value, name: &str, op: fn(f64) -> f64) -> Result<Value, String> {
+    match val {
+        Value::Int(i) => Ok(Value::Int(*i)),
+        Value::Float(f) => {
+            let result = op(*f);
+            if !result.is_finite() || result < i64::MIN as f64 || result > i64::MAX as f64 {
+                return Err(format!("{}() result {} does not fit in an int", name, f));
+            }
+            Ok(Value::Int(result as i64))
+        }
+        _ => Err(format!("{}() requires int or float, got {}", name, val.type_name())),
+    }
+}
+
+pub fn floor(val: &Value) -> Result<Value, String> {
+    to_int_with(val, "floor", f64::floor)
+}
+
+pub fn ceil(val: &Value) -> Result<Value, String> {
+    to_int_with(val, "ceil", f64::ceil)
+}
+
+pub fn round(val: &Value) -> Result<Value, String> {
+    to_int_with(val, "round", f64::round)
+}
+
+pub fn sqrt(val: &Value) -> Result<Value, String> {
+    let x = match val {
+        Value::Int(i) => *i as f64,
+        Value::Float(f) => *f,
+        _ => return Err(format!("sqrt() requires int or float, got {}", val.type_name())),
+    };
+    if x < 0.0 {
+        return Err("sqrt of negative number".to_string());
+    }
+    Ok(Value::Float(x.sqrt()))
+}
+
+// Always a Float, even for two ints: pow(2, 10) is 1024.0
+pub fn pow(base: &Value, exp: &Value) -> Result<Value, String> {
+    let b = match base {
+        Value::Int(i) => *i as f64,
+        Value::Float(f) => *f,
+        _ => return Err(format!("pow() requires numeric base, got {}", base.type_name())),
+    };
+    match exp {
+        Value::Int(e) => {
+            let e = i32::try_from(*e).map_err(|_| format!("pow() exponent {} is out of range", e))?;
+            Ok(Value::Float(b.powi(e)))
+        }
+        Value::Float(e) => Ok(Value::Float(b.powf(*e))),
+        _ => Err(format!("pow() requires numeric exponent, got {}", exp.type_name())),
+    }
+}
+
+// Upper bound on range() output so a typo can't exhaust memory
+const RANGE_LIMIT: usize = 100_000;
+
+// Python-style range: exclusive stop, step may be negative
+pub fn range(start: &Value, stop: &Value, step: Option<&Value>) -> Result<Value, String> {
+    let as_int = |v: &Value, what: &str| match v {
+        Value::Int(i) => Ok(*i),
+        _ => Err(format!("range() {} must be int, got {}", what, v.type_name())),
+    };
+    let start = as_int(start, "start")?;
+    let stop = as_int(stop, "stop")?;
+    let step = match step {
+        Some(step) => as_int(step, "step")?,
+        None => 1,
+    };
+    if step == 0 {
+        return Err("range() step cannot be zero".to_string());
+    }
+
+    let mut items = Vec::new();
+    let mut current = start;
+    while (step > 0 && current < stop) || (step < 0 && current > stop) {
+        if items.len() >= RANGE_LIMIT {
+            return Err(format!("range() would produce more than {} elements", RANGE_LIMIT));
+        }
+        items.push(Value::Int(current));
+        current = match current.checked_add(step) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    Ok(Value::List(items))
+}
+
+pub fn enumerate(val: &Value) -> Result<Value, String> {
+    match val {
+        Value::List(items) => Ok(Value::List(items.iter()
+            .enumerate()
+            .map(|(i, item)| Value::List(vec![Value::Int(i as i64), item.clone()]))
+            .collect())),
+        _ => Err(format!("enumerate() requires list, got {}", val.type_name())),
+    }
+}
+
+// false, 0, 0.0, "", [], {} and null are falsy; everything else is truthy
+pub fn is_truthy(val: &Value) -> bool {
+    match val {
+        Value::Bool(b) => *b,
+        Value::Int(i) => *i != 0,
+        Value::Float(f) => *f != 0.0,
+        Value::Str(s) | Value::Json(s) => !s.is_empty(),
+        Value::List(items) => !items.is_empty(),
+        Value::Dict(map) => !map.is_empty(),
+        Value::Null => false,
+    }
+}
+
+pub fn any(val: &Value) -> Result<Value, String> {
+    match val {
+        Value::List(items) => Ok(Value::Bool(items.iter().any(is_truthy))),
+        _ => Err(format!("any() requires list, got {}", val.type_name())),
+    }
+}
+
+pub fn all(val: &Value) -> Result<Value, String> {
+    match val {
+        Value::List(items) => Ok(Value::Bool(items.iter().all(is_truthy))),
+        _ => Err(format!("all() requires list, got {}", val.type_name())),
+    }
+}
+
+pub fn first(list_value: &Value) -> Result<Value, String> {
+    match list_value {
+        Value::List(items) => items.first()
+            .cloned()
+            .ok_or_else(|| "first() of empty list".to_string()),
+        _ => Err(format!("first() requires list, got {}", list_value.type_name())),
+    }
+}
+
+pub fn last(list_value: &Value) -> Result<Value, String> {
+    match list_value {
+        Value::List(items) => items.last()
+            .cloned()
+            .ok_or_else(|| "last() of empty list".to_string()),
+        _ => Err(format!("last() requires list, got {}", list_value.type_name())),
+    }
+}
+
+// Zero-based; negative indices count from the end, so nth(list, -1) is the last element
+pub fn nth(list_value: &Value, index_value: &Value) -> Result<Value, String> {
+    let items = match list_value {
+        Value::List(items) => items,
+        _ => return Err(format!("nth() requires list, got {}", list_value.type_name())),
+    };
+    let index = match index_value {
+        Value::Int(i) => *i,
+        _ => return Err("Index must be integer".to_string()),
+    };
+
+    let len = items.len() as i64;
+    let resolved = if index < 0 { len + index } else { index };
+    if resolved < 0 || resolved >= len {
+        return Err(format!("Index {} out of bounds for list of length {}", index, len));
+    }
+    Ok(items[resolved as usize].clone())
+}
+
+// depth None flattens all the way down; Some(1) only lifts one level
+pub fn flatten(val: &Value, depth: Option<usize>) -> Result<Value, String> {
+    fn flatten_into(items: &[Value], depth: Option<usize>, out: &mut Vec<Value>) {
+        for item in items {
+            match item {
+                Value::List(inner) if depth != Some(0) => {
+                    flatten_into(inner, depth.map(|d| d - 1), out);
+                }
+                other => out.push(other.clone()),
+            }
+        }
+    }
+
+    match val {
+        Value::List(items) => {
+            let mut out = Vec::new();
+            flatten_into(items, depth, &mut out);
+            Ok(Value::List(out))
+        }
+        _ => Err(format!("flatten() requires list, got {}", val.type_name())),
+    }
+}
+
+// Keeps first appearances in order. Uses Value equality, so dicts match on every
+// key/value pair and NaN never equals anything (each NaN is kept)
+pub fn unique(val: &Value) -> Result<Value, String> {
+    match val {
+        Value::List(items) => {
+            let mut kept: Vec<Value> = Vec::new();
+            for item in items {
+                if !kept.contains(item) {
+                    kept.push(item.clone());
+                }
+            }
+            Ok(Value::List(kept))
+        }
+        _ => Err(format!("unique() requires list, got {}", val.type_name())),
+    }
+}
+
+// Sort by a dict field ("age"), a nested-list position (1) or the element itself (".")
+pub fn sort_by(list: &Value, key: &Value) -> Result<Value, String> {
+    let items = match list {
+        Value::List(items) => items,
+        _ => return Err(format!("sort_by() requires list, got {}", list.type_name())),
+    };
+
+    let key_of = |item: &Value| -> Result<Value, String> {
+        match (key, item) {
+            (Value::Str(k), _) if k == "." => Ok(item.clone()),
+            (Value::Str(k), Value::Dict(map)) => Ok(map.get(k).cloned().unwrap_or(Value::Null)),
+            (Value::Int(i), Value::List(inner)) => Ok(usize::try_from(*i).ok()
+                .and_then(|i| inner.get(i))
+                .cloned()
+                .unwrap_or(Value::Null)),
+            (Value::Str(_), other) => Err(format!("sort_by() field key needs dict elements, found {}", other.type_name())),
+            (Value::Int(_), other) => Err(format!("sort_by() index key needs list elements, found {}", other.type_name())),
+            _ => Err(format!("sort_by() key must be a field name or index, got {}", key.type_name())),
+        }
+    };
+
+    let mut keyed = items.iter()
+        .map(|item| Ok((key_of(item)?, item.clone())))
+        .collect::<Result<Vec<_>, String>>()?;
+    keyed.sort_by(|(a, _), (b, _)| sort_order(a, b));
+    Ok(Value::List(keyed.into_iter().map(|(_, item)| item).collect()))
+}
+
+// Dict elements group on the named field ("<missing>" when absent); scalars group on themselves
+pub fn group_by(list: &Value, key: &Value) -> Result<Value, String> {
+    let items = match list {
+        Value::List(items) => items,
+        _ => return Err(format!("group_by() requires list, got {}", list.type_name())),
+    };
+    let field = match key {
+        Value::Str(field) => field,
+        _ => return Err(format!("group_by() key must be a string, got {}", key.type_name())),
+    };
+
+    let mut groups: HashMap<String, Value> = HashMap::new();
+    for item in items {
+        let group_value = match item {
+            Value::Dict(map) => map.get(field),
+            other => Some(other),
+        };
+        let group_key = match group_value {
+            Some(Value::Str(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => "<missing>".to_string(),
+        };
+        if let Value::List(members) = groups.entry(group_key).or_insert_with(|| Value::List(Vec::new())) {
+            members.push(item.clone());
+        }
+    }
+    Ok(Value::Dict(groups))
+}
+
+// Pairs (or triples) elements position by position, stopping at the shortest list
+pub fn zip(lists: &[Value]) -> Result<Value, String> {
+    let columns = lists.iter()
+        .map(|list| match list {
+            Value::List(items) => Ok(items),
+            other => Err(format!("zip() requires lists, got {}", other.type_name())),
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let shortest = columns.iter().map(|items| items.len()).min().unwrap_or(0);
+    Ok(Value::List((0..shortest)
+        .map(|i| Value::List(columns.iter().map(|items| items[i].clone()).collect()))
+        .collect()))
+}
+
+// -1 rather than an error when the item is absent, like most shell tools
+pub fn index_of(list: &Value, item: &Value) -> Result<Value, String> {
+    match list {
+        Value::List(items) => Ok(Value::Int(items.iter()
+            .position(|candidate| candidate == item)
+            .map_or(-1, |i| i as i64))),
+        _ => Err(format!("index_of() requires list, got {}", list.type_name())),
+    }
+}
+
+pub fn reverse(val: &Value) -> Result<Value, String> {
+    match val {
+        Value::List(items) => Ok(Value::List(items.iter().rev().cloned().collect())),
+        Value::Str(s) => Ok(Value::Str(s.chars().rev().collect())),
+        _ => Err(format!("reverse() requires list or string, got {}", val.type_name())),
+    }
+}
+
+// The last chunk may be shorter than size
+pub fn chunk(val: &Value, size: &Value) -> Result<Value, String> {
+    let size = match size {
+        Value::Int(n) if *n > 0 => *n as usize,
+        Value::Int(n) => return Err(format!("chunk() size must be positive, got {}", n)),
+        _ => return Err(format!("chunk() size must be int, got {}", size.type_name())),
+    };
+    match val {
+        Value::List(items) => Ok(Value::List(items.chunks(size)
+            .map(|part| Value::List(part.to_vec()))
+            .collect())),
+        _ => Err(format!("chunk() requires list, got {}", val.type_name())),
+    }
+}
+
+fn pad(name: &str, val: &Value, width: &Value, fill: Option<&Value>, left: bool) -> Result<Value, String> {
+    let s = match val {
+        Value::Str(s) => s.clone(),
+        Value::Int(_) | Value::Float(_) | Value::Bool(_) => val.to_string(),
+        _ => return Err(format!("{}() requires string, got {}", name, val.type_name())),
+    };
+    let width = match width {
+        Value::Int(w) if *w >= 0 => *w as usize,
+        _ => return Err(format!("{}() width must be a non-negative int", name)),
+    };
+    let fill = match fill {
+        None => ' ',
+        Some(Value::Str(f)) if f.chars().count() == 1 => f.chars().next().unwrap_or(' '),
+        Some(other) => return Err(format!("{}() pad character must be a single-character string, got {}", name, other.display())),
+    };
+
+    let current = s.chars().count();
+    if current >= width {
+        return Ok(Value::Str(s));
+    }
+    let padding: String = std::iter::repeat_n(fill, width - current).collect();
+    Ok(Value::Str(if left { padding + &s } else { s + &padding }))
+}
+
+pub fn pad_left(val: &Value, width: &Value, fill: Option<&Value>) -> Result<Value, String> {
+    pad("pad_left", val, width, fill, true)
+}
+
+pub fn pad_right(val: &Value, width: &Value, fill: Option<&Value>) -> Result<Value, String> {
+    pad("pad_right", val, width, fill, false)
+}
+
+pub fn repeat(val: &Value, n: &Value) -> Result<Value, String> {
+    let s = match val {
+        Value::Str(s) => s,
+        _ => return Err(format!("repeat() requires string, got {}", val.type_name())),
+    };
+    let times = match n {
+        Value::Int(n) if *n >= 0 => *n as usize,
+        _ => return Err(format!("repeat() count must be a non-negative int, got {}", n.display())),
+    };
+    if s.len().checked_mul(times).is_none() {
+        return Err("repeat() result is too large".to_string());
+    }
+    Ok(Value::Str(s.repeat(times)))
+}
+
+// Floats truncate toward zero: to_int(3.9) is 3
+pub fn to_int(val: &Value) -> Result<Value, String> {
+    let truncate = |f: f64| {
+        if f.is_finite() && f >= i64::MIN as f64 && f <= i64::MAX as f64 {
+            Ok(Value::Int(f.trunc() as i64))
+        } else {
+            Err(format!("to_int() cannot convert {} to int", f))
+        }
+    };
+    match val {
+        Value::Int(i) => Ok(Value::Int(*i)),
+        Value::Float(f) => truncate(*f),
+        Value::Bool(b) => Ok(Value::Int(*b as i64)),
+        Value::Str(s) => {
+            let s = s.trim();
+            match s.parse::<i64>() {
+                Ok(i) => Ok(Value::Int(i)),
+                Err(_) => match s.parse::<f64>() {
+                    Ok(f) => truncate(f),
+                    Err(_) => Err(format!("to_int() cannot convert \"{}\" to int", s)),
+                },
+            }
+        }
+        _ => Err(format!("to_int() cannot convert {} to int", val.type_name())),
+    }
+}
+
+pub fn to_float(val: &Value) -> Result<Value, String> {
+    match val {
+        Value::Int(i) => Ok(Value::Float(*i as f64)),
+        Value::Float(f) => Ok(Value::Float(*f)),
+        Value::Bool(b) => Ok(Value::Float(if *b { 1.0 } else { 0.0 })),
+        Value::Str(s) => s.trim().parse::<f64>()
+            .map(Value::Float)
+            .map_err(|_| format!("to_float() cannot convert \"{}\" to float", s)),
+        _ => Err(format!("to_float() cannot convert {} to float", val.type_name())),
+    }
+}
+
+pub fn to_bool(val: &Value) -> Result<Value, String> {
+    Ok(Value::Bool(is_truthy(val)))
+}
+
+pub fn type_of(val: &Value) -> Result<Value, String> {
+    Ok(Value::Str(val.type_name().to_string()))
+}
+
+// Takes the variable name as a string: is_defined("x"), not is_defined(x)
+pub fn is_defined(name: &Value, env: &crate::core::env::Env) -> Result<Value, String> {
+    match name {
+        Value::Str(name) => Ok(Value::Bool(env.get_value(name).is_some())),
+        _ => Err(format!("is_defined() requires the variable name as a string, got {}", name.type_name())),
+    }
+}
+
+pub fn uuid() -> Value {
+    Value::Str(uuid::Uuid::new_v4().to_string())
+}
+
+// Hex digest of the value's string form; algorithm names are case-insensitive
+pub fn hash(val: &Value, algo: &Value) -> Result<Value, String> {
+    use sha2::Digest;
+
+    let input = match val {
+        Value::Str(s) => s.clone(),
+        Value::Int(_) | Value::Float(_) | Value::Bool(_) => val.to_string(),
+        _ => return Err(format!("hash() requires a scalar value, got {}", val.type_name())),
+    };
+    let algo = match algo {
+        Value::Str(a) => a.to_lowercase(),
+        _ => return Err(format!("hash() algorithm must be a string, got {}", algo.type_name())),
+    };
+
+    let digest = match algo.as_str() {
+        "sha256" => format!("{:x}", sha2::Sha256::digest(input.as_bytes())),
+        "sha1" => format!("{:x}", sha1::Sha1::digest(input.as_bytes())),
+        "md5" => format!("{:x}", md5::Md5::digest(input.as_bytes())),
+        other => return Err(format!("hash() unsupported algorithm '{}' (use sha256, sha1 or md5)", other)),
+    };
+    Ok(Value::Str(digest))
+}
+
+pub fn encode64(val: &Value) -> Result<Value, String> {
+    use base64::Engine;
+    match val {
+        Value::Str(s) => Ok(Value::Str(base64::engine::general_purpose::STANDARD.encode(s.as_bytes()))),
+        _ => Err(format!("encode64() requires string, got {}", val.type_name())),
+    }
+}
+
+pub fn decode64(val: &Value) -> Result<Value, String> {
+    use base64::Engine;
+    let encoded = match val {
+        Value::Str(s) => s.trim(),
+        _ => return Err(format!("decode64() requires string, got {}", val.type_name())),
+    };
+    let bytes = base64::engine::general_purpose::STANDARD.decode(encoded)
+        .map_err(|e| format!("decode64() invalid base64: {}", e))?;
+    String::from_utf8(bytes)
+        .map(Value::Str)
+        .map_err(|_| "decode64() result is not valid UTF-8 text".to_string())
+}
+
+pub fn timestamp() -> Value {
+    Value::Int(Utc::now().timestamp())
+}
+
+// Date-only formats are taken as midnight; everything is UTC
+pub fn parse_date(date: &Value, format: &Value) -> Result<Value, String> {
+    let (date, format) = match (date, format) {
+        (Value::Str(d), Value::Str(f)) => (d, f),
+        _ => return Err("parse_date() requires a date string and a format string".to_string()),
+    };
+    let parsed = chrono::NaiveDateTime::parse_from_str(date, format)
+        .or_else(|_| chrono::NaiveDate::parse_from_str(date, format)
+            .map(|day| day.and_hms_opt(0, 0, 0).unwrap_or_default()))
+        .map_err(|e| format!("parse_date() cannot parse \"{}\" with \"{}\": {}", date, format, e))?;
+    Ok(Value::Int(parsed.and_utc().timestamp()))
+}
+
+pub fn format_date(timestamp: &Value, format: &Value) -> Result<Value, String> {
+    use std::fmt::Write;
+
+    let (seconds, format) = match (timestamp, format) {
+        (Value::Int(t), Value::Str(f)) => (*t, f),
+        _ => return Err("format_date() requires an int timestamp and a format string".to_string()),
+    };
+    let datetime = chrono::DateTime::from_timestamp(seconds, 0)
+        .ok_or_else(|| format!("format_date() timestamp {} is out of range", seconds))?;
+
+    let mut out = String::new();
+    write!(out, "{}", datetime.format(format))
+        .map_err(|_| format!("format_date() invalid format \"{}\"", format))?;
+    Ok(Value::Str(out))
+}
+
+// Unset variables read as "" (or the given default) instead of failing the script
+pub fn env_var(name: &Value, default: Option<&Value>) -> Result<Value, String> {
+    let name = match name {
+        Value::Str(name) => name,
+        _ => return Err(format!("env_var() requires the variable name as a string, got {}", name.type_name())),
+    };
+    match std::env::var(name) {
+        Ok(value) => Ok(Value::Str(value)),
+        Err(_) => Ok(default.cloned().unwrap_or_else(|| Value::Str(String::new()))),
+    }
+}
+
+// Runs through `sh -c`; stdout comes back with its trailing newline removed
+pub fn exec_shell(command: &Value) -> Result<Value, String> {
+    let command = match command {
+        Value::Str(command) => command,
+        _ => return Err(format!("exec_shell() requires a command string, got {}", command.type_name())),
+    };
+    crate::core::safety_guard::SafetyGuard::new()?.validate_shell(command)?;
+
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .map_err(|e| format!("exec_shell() could not start sh: {}", e))?;
+
+    if !output.status.success() {
+        let code = output.status.code().map_or("signal".to_string(), |c| c.to_string());
+        return Err(format!("exec_shell() exited with {}: {}", code, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(Value::Str(stdout.strip_suffix('\n').unwrap_or(&stdout).to_string()))
+}
+
+const HTTP_DEFAULT_TIMEOUT_SECS: f64 = 30.0;
+
+// Both need --allow-http; the optional last argument is a timeout in seconds
+pub fn http_get(url: &Value, timeout: Option<&Value>) -> Result<Value, String> {
+    let url = http_url("http_get", url)?;
+    let agent = http_agent("http_get", timeout)?;
+    crate::core::safety_guard::SafetyGuard::new()?.validate_http("GET", url)?;
+    http_response("http_get", url, agent.get(url).call())
+}
+
+// Strings are sent as-is (as JSON when they look like an object or array);
+// lists and dicts are serialized to JSON
+pub fn http_post(url: &Value, body: &Value, timeout: Option<&Value>) -> Result<Value, String> {
+    let url = http_url("http_post", url)?;
+    let agent = http_agent("http_post", timeout)?;
+    let (body, content_type) = match body {
+        Value::Str(s) if s.trim_start().starts_with(['{', '[']) => (s.clone(), "application/json"),
+        Value::Str(s) => (s.clone(), "text/plain; charset=utf-8"),
+        Value::Json(s) => (s.clone(), "application/json"),
+        other => (to_json(other)?, "application/json"),
+    };
+    crate::core::safety_guard::SafetyGuard::new()?.validate_http("POST", url)?;
+    http_response("http_post", url, agent.post(url).set("Content-Type", content_type).send_string(&body))
+}
+
+fn http_url<'a>(name: &str, url: &'a Value) -> Result<&'a str, String> {
+    match url {
+        Value::Str(url) => Ok(url),
+        _ => Err(format!("{}() requires a URL string, got {}", name, url.type_name())),
+    }
+}
+
+fn http_agent(name: &str, timeout: Option<&Value>) -> Result<ureq::Agent, String> {
+    let seconds = match timeout {
+        None => HTTP_DEFAULT_TIMEOUT_SECS,
+        Some(Value::Int(i)) if *i > 0 => *i as f64,
+        Some(Value::Float(f)) if *f > 0.0 => *f,
+        Some(other) => return Err(format!("{}() timeout must be a positive number of seconds, got {}", name, other.display())),
+    };
+    Ok(ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs_f64(seconds))
+        .build())
+}
+
+fn http_response(name: &str, url: &str, result: Result<ureq::Response, ureq::Error>) -> Result<Value, String> {
+    match result {
+        Ok(response) => response.into_string()
+            .map(Value::Str)
+            .map_err(|e| format!("{}() could not read the response from '{}': {}", name, url, e)),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(format!("{}() got HTTP {} from '{}': {}", name, code, url, body.trim()))
+        }
+        Err(e) => Err(format!("{}() failed for '{}': {}", name, url, e)),
+    }
+}
+
+fn string_pieces<'a, I>(name: &str, val: &'a Value, split: impl FnOnce(&'a str) -> I) -> Result<Value, String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    match val {
+        Value::Str(s) => Ok(Value::List(split(s).map(|piece| Value::Str(piece.to_string())).collect())),
+        _ => Err(format!("{}() requires string, got {}", name, val.type_name())),
+    }
+}
+
+pub fn lines(val: &Value) -> Result<Value, String> {
+    string_pieces("lines", val, str::lines)
+}
+
+pub fn words(val: &Value) -> Result<Value, String> {
+    string_pieces("words", val, str::split_whitespace)
+}
+
+// Grapheme clusters, so "é" written as e + combining accent stays one character
+pub fn chars(val: &Value) -> Result<Value, String> {
+    use unicode_segmentation::UnicodeSegmentation;
+    string_pieces("chars", val, |s| s.graphemes(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::Schema;
+
+    #[test]
+    fn test_generate_data() {
+        let schema = Schema::parse("point", "{ x: int(1..3), y: float(2..2) }").unwrap();
+        let rows = generate_data(&schema, 20).unwrap();
+        let items = match rows {
+            Value::List(items) => items,
+            other => panic!("generate_data returned {:?}", other),
+        };
+        assert_eq!(items.len(), 20);
+        for row in &items {
+            if let Value::Dict(map) = row {
+                assert!(matches!(map.get("x"), Some(Value::Int(x)) if (1..=3).contains(x)));
+                assert_eq!(map.get("y"), Some(&Value::Float(2.0)));
+            }
+        }
+
+        let err = generate_data(&schema, GENERATE_LIMIT + 1).unwrap_err();
+        assert_eq!(err, "generate count 100001 exceeds the limit of 100000");
+    }
+
+    #[test]
+    fn test_generate_rejects_bad_ranges() {
+        let reversed = Schema::parse("bad", "{ x: int(5..1) }").unwrap();
+        assert_eq!(generate_data(&reversed, 1).unwrap_err(), "Field 'x': min 5 is greater than max 1");
+
+        let huge = Schema::parse("bad", "{ t: timestamp(0..1e300) }").unwrap();
+        assert!(generate_data(&huge, 1).is_err());
+
+        let infinite = Schema::parse("bad", "{ f: float(0..inf) }").unwrap();
+        assert!(generate_data(&infinite, 1).unwrap_err().ends_with("must be finite"));
+    }
+
+    fn dict(pairs: &[(&str, Value)]) -> Value {
+        Value::Dict(pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect())
+    }
+
+    fn status(diff: &Value, key: &str) -> String {
+        match diff {
+            Value::Dict(map) => match map.get(key) {
+                Some(Value::Dict(entry)) => entry.get("status").map(|s| s.to_string()).unwrap_or_default(),
+                _ => String::new(),
+            },
+            _ => String::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_dicts() {
+        let a = dict(&[
+            ("same", Value::Int(1)),
+            ("changed", Value::Int(2)),
+            ("gone", Value::Bool(true)),
+            ("nested", dict(&[("x", Value::Int(1))])),
+        ]);
+        let b = dict(&[
+            ("same", Value::Int(1)),
+            ("changed", Value::Int(3)),
+            ("added", Value::Str("new".to_string())),
+            ("nested", dict(&[("x", Value::Int(2))])),
+        ]);
+        let diff = diff_dicts(&a, &b).unwrap();
+        assert_eq!(status(&diff, "same"), "=");
+        assert_eq!(status(&diff, "changed"), "~");
+        assert_eq!(status(&diff, "gone"), "-");
+        assert_eq!(status(&diff, "added"), "+");
+        assert_eq!(status(&diff, "nested"), "~");
+
+        let text = format_dict_diff(&diff, 0);
+        assert!(text.contains("~ changed: 2 → 3"));
+        assert!(text.contains("~ nested:\n  ~ x: 1 → 2"));
+        assert!(diff_dicts(&a, &Value::Int(1)).is_err());
+    }
+
+    #[test]
+    fn test_merge_deep_strategies() {
+        let a = dict(&[
+            ("db", dict(&[("host", Value::Str("a".to_string())), ("port", Value::Int(1))])),
+            ("tags", Value::List(vec![Value::Int(1)])),
+        ]);
+        let b = dict(&[
+            ("db", dict(&[("host", Value::Str("b".to_string()))])),
+            ("tags", Value::List(vec![Value::Int(2)])),
+        ]);
+
+        let last = merge_deep(&a, &b, MergeStrategy::LastWins).unwrap();
+        let expected_db = dict(&[("host", Value::Str("b".to_string())), ("port", Value::Int(1))]);
+        assert_eq!(last, dict(&[("db", expected_db), ("tags", Value::List(vec![Value::Int(2)]))]));
+
+        let first = merge_deep(&a, &b, MergeStrategy::FirstWins).unwrap();
+        assert_eq!(first, a);
+
+        let appended = merge_deep(&a, &b, MergeStrategy::Append).unwrap();
+        assert!(matches!(appended, Value::Dict(map) if map["tags"] == Value::List(vec![Value::Int(1), Value::Int(2)])));
+
+        let host_a = dict(&[("db", dict(&[("host", Value::Str("a".to_string()))]))]);
+        let host_b = dict(&[("db", dict(&[("host", Value::Str("b".to_string()))]))]);
+        let err = merge_deep(&host_a, &host_b, MergeStrategy::ErrorOnConflict).unwrap_err();
+        assert!(err.starts_with("Merge conflict at 'db.host'"), "{}", err);
+        assert!(MergeStrategy::parse("newest").is_err());
+    }
+
+    #[test]
+    fn test_summarize() {
+        let numbers = Value::List((1..=5).map(Value::Int).collect());
+        let stats = match summarize(&numbers, None).unwrap() {
+            Value::Dict(stats) => stats,
+            other => panic!("summarize returned {:?}", other),
+        };
+        assert_eq!(stats["count"], Value::Int(5));
+        assert_eq!(stats["min"], Value::Float(1.0));
+        assert_eq!(stats["max"], Value::Float(5.0));
+        assert_eq!(stats["mean"], Value::Float(3.0));
+        assert_eq!(stats["median"], Value::Float(3.0));
+        assert_eq!(stats["p25"], Value::Float(2.0));
+
+        let rows = Value::List(vec![
+            dict(&[("city", Value::Str("oslo".to_string()))]),
+            dict(&[("city", Value::Str("rome".to_string()))]),
+            dict(&[("city", Value::Str("oslo".to_string()))]),
+        ]);
+        let stats = match summarize(&rows, Some("city")).unwrap() {
+            Value::Dict(stats) => stats,
+            other => panic!("summarize returned {:?}", other),
+        };
+        assert_eq!(stats["unique_count"], Value::Int(2));
+        assert_eq!(stats["most_common"], Value::Str("oslo".to_string()));
+        assert!(summarize(&Value::Int(1), None).is_err());
+    }
+
+    #[test]
+    fn test_pivot_keys() {
+        let rows = Value::List(vec![
+            dict(&[("r", Value::Int(1)), ("c", Value::Str("1".to_string())), ("v", Value::Int(10))]),
+            dict(&[("r", Value::Str("1".to_string())), ("c", Value::Int(2)), ("v", Value::Int(20))]),
+        ]);
+        let items = match pivot(&rows, "r", "c", "v").unwrap() {
+            Value::List(items) => items,
+            other => panic!("pivot returned {:?}", other),
+        };
+        // Rows 1 and "1" stay apart; column names are plain text
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0], dict(&[("r", Value::Int(1)), ("1", Value::Int(10)), ("2", Value::Null)]));
+        assert_eq!(items[1], dict(&[("r", Value::Str("1".to_string())), ("1", Value::Null), ("2", Value::Int(20))]));
+    }
+
+    #[test]
+    fn test_pivot_rejects_row_key_column() {
+        let rows = Value::List(vec![
+            dict(&[("day", Value::Str("mon".to_string())), ("metric", Value::Str("day".to_string())), ("v", Value::Int(1))]),
+        ]);
+        assert_eq!(
+            pivot(&rows, "day", "metric", "v").unwrap_err(),
+            "pivot(): column value 'day' collides with the row key 'day'"
+        );
+    }
+
+    #[test]
+    fn test_unpivot_skips_nulls() {
+        let wide = dict(&[("day", Value::Str("mon".to_string())), ("a", Value::Int(1)), ("b", Value::Null)]);
+        let long = unpivot(&wide, "day", "value").unwrap();
+        assert_eq!(long, Value::List(vec![dict(&[
+            ("day", Value::Str("mon".to_string())),
+            ("column", Value::Str("a".to_string())),
+            ("value", Value::Int(1)),
+        ])]));
+    }
+
+    #[test]
+    fn test_joins() {
+        let users = Value::List(vec![
+            dict(&[("id", Value::Int(1)), ("name", Value::Str("ann".to_string()))]),
+            dict(&[("id", Value::Int(2)), ("name", Value::Str("bob".to_string()))]),
+        ]);
+        let orders = Value::List(vec![
+            dict(&[("id", Value::Int(1)), ("name", Value::Str("lamp".to_string()))]),
+        ]);
+
+        // The shared non-key field is split into name_a / name_b
+        let inner = inner_join(&users, &orders, "id").unwrap();
+        assert_eq!(inner, Value::List(vec![dict(&[
+            ("id", Value::Int(1)),
+            ("name_a", Value::Str("ann".to_string())),
+            ("name_b", Value::Str("lamp".to_string())),
+        ])]));
+
+        let left = match left_join(&users, &orders, "id").unwrap() {
+            Value::List(rows) => rows,
+            other => panic!("left_join returned {:?}", other),
+        };
+        assert_eq!(left.len(), 2);
+        assert_eq!(left[1], dict(&[
+            ("id", Value::Int(2)),
+            ("name_a", Value::Str("bob".to_string())),
+            ("name_b", Value::Null),
+        ]));
+        assert!(inner_join(&users, &Value::Int(1), "id").is_err());
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let list = Value::List(vec![Value::Int(1), Value::Int(2), Value::Int(3), Value::Int(4)]);
+        assert_eq!(
+            sliding_window(&list, 2, "sum").unwrap(),
+            Value::List(vec![Value::Null, Value::Int(3), Value::Int(5), Value::Int(7)])
+        );
+        assert_eq!(
+            sliding_window(&list, 3, "avg").unwrap(),
+            Value::List(vec![Value::Null, Value::Null, Value::Float(2.0), Value::Float(3.0)])
+        );
+        assert_eq!(sliding_window(&list, 0, "sum").unwrap_err(), "sliding_window() size must be at least 1");
+        assert!(sliding_window(&list, 2, "median").is_err());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let list = Value::List(vec![Value::Null, Value::Int(0), Value::Null, Value::Int(4), Value::Null]);
+        assert_eq!(
+            interpolate_linear(&list).unwrap(),
+            Value::List(vec![Value::Null, Value::Int(0), Value::Float(2.0), Value::Int(4), Value::Null])
+        );
+        assert_eq!(
+            interpolate_forward_fill(&list).unwrap(),
+            Value::List(vec![Value::Null, Value::Int(0), Value::Float(0.0), Value::Int(4), Value::Float(4.0)])
+        );
+        assert_eq!(
+            interpolate_backward_fill(&list).unwrap(),
+            Value::List(vec![Value::Float(0.0), Value::Int(0), Value::Float(4.0), Value::Int(4), Value::Null])
+        );
+        assert!(interpolate_linear(&Value::List(vec![Value::Bool(true)])).is_err());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let list = Value::List(vec![Value::Int(2), Value::Int(4), Value::Int(6)]);
+        assert_eq!(
+            normalize_minmax(&list).unwrap(),
+            Value::List(vec![Value::Float(0.0), Value::Float(0.5), Value::Float(1.0)])
+        );
+        assert_eq!(
+            normalize_max(&list).unwrap(),
+            Value::List(vec![Value::Float(2.0 / 6.0), Value::Float(4.0 / 6.0), Value::Float(1.0)])
+        );
+        match normalize_zscore(&list).unwrap() {
+            Value::List(items) => assert_eq!(items[1], Value::Float(0.0)),
+            other => panic!("normalize_zscore returned {:?}", other),
+        }
+
+        let flat = Value::List(vec![Value::Int(3), Value::Int(3)]);
+        assert_eq!(normalize_minmax(&flat).unwrap_err(), "normalize_minmax() range is zero (all values equal)");
+        assert_eq!(normalize_zscore(&flat).unwrap_err(), "normalize_zscore() standard deviation is zero");
+        assert!(normalize_minmax(&Value::List(vec![])).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let colors = Value::List(vec![
+            Value::Str("red".to_string()),
+            Value::Str("blue".to_string()),
+            Value::Str("red".to_string()),
+        ]);
+        match one_hot_encode(&colors).unwrap() {
+            Value::List(rows) => {
+                assert_eq!(rows.len(), 3);
+                assert_eq!(rows[1], dict(&[("blue", Value::Bool(true)), ("red", Value::Bool(false))]));
+            }
+            other => panic!("one_hot_encode returned {:?}", other),
+        }
+
+        let (codes, mapping) = label_encode(&colors).unwrap();
+        assert_eq!(codes, Value::List(vec![Value::Int(1), Value::Int(0), Value::Int(1)]));
+        assert_eq!(mapping, dict(&[("blue", Value::Int(0)), ("red", Value::Int(1))]));
+        assert!(label_encode(&Value::List(vec![Value::Float(1.5)])).is_err());
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let values = [Value::Null, Value::Str("<?>".to_string()), Value::Int(0), Value::Int(5)];
+        assert_eq!(coalesce(&values), Value::Int(0));
+        assert_eq!(coalesce(&[Value::Null]), Value::Null);
+    }
+
+    #[test]
+    fn test_range_limit() {
+        let ok = range(&Value::Int(0), &Value::Int(RANGE_LIMIT as i64), None).unwrap();
+        assert!(matches!(ok, Value::List(items) if items.len() == RANGE_LIMIT));
+        let err = range(&Value::Int(0), &Value::Int(RANGE_LIMIT as i64 + 1), None).unwrap_err();
+        assert_eq!(err, "range() would produce more than 100000 elements");
+        assert!(range(&Value::Int(0), &Value::Int(5), Some(&Value::Int(0))).is_err());
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let value = dict(&[
+            ("name", Value::Str("morris".to_string())),
+            ("port", Value::Int(8080)),
+            ("ratio", Value::Float(0.5)),
+            ("tags", Value::List(vec![Value::Str("a".to_string()), Value::Bool(true)])),
+            ("empty", Value::Null),
+        ]);
+        assert_eq!(parse_yaml(&to_yaml(&value).unwrap()).unwrap(), value);
+        assert!(parse_yaml("key: [unclosed").unwrap_err().starts_with("Invalid YAML:"));
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let rows = Value::List(vec![
+            dict(&[("id", Value::Int(1)), ("name", Value::Str("a, b".to_string())), ("ok", Value::Bool(true))]),
+            dict(&[("id", Value::Int(2)), ("name", Value::Str("c".to_string())), ("ok", Value::Bool(false))]),
+        ]);
+        let text = to_csv(&rows).unwrap();
+        assert!(text.starts_with("id,name,ok\n"));
+        assert_eq!(parse_csv(&text, true).unwrap(), rows);
+
+        let plain = Value::List(vec![Value::List(vec![Value::Int(1), Value::Float(2.5)])]);
+        assert_eq!(parse_csv(&to_csv(&plain).unwrap(), false).unwrap(), plain);
+        assert!(to_csv(&Value::Int(1)).is_err());
+    }
+}
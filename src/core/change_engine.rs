@@ -1,5 +1,5 @@
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 use serde::{Serialize, Deserialize};
@@ -40,6 +40,19 @@ pub struct ChangeEngine {
     // Session state
     pub current_session: Option<SessionInfo>,
     pub recent_sessions: Vec<SessionInfo>,
+    
+    // One entry per forged transaction, oldest first
+    #[serde(default)]
+    pub forge_records: Vec<ForgeRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ForgeRecord {
+    pub transaction_id: String,
+    pub name: Option<String>,
+    pub forged_at: i64,  // Store as timestamp
+    pub metadata: BTreeMap<String, String>,
+    pub changes: BTreeMap<String, String>,  // variable -> value after forging
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -181,6 +194,7 @@ impl ChangeEngineManager {
             annotations: HashMap::new(),
             current_session: None,
             recent_sessions: Vec::new(),
+            forge_records: Vec::new(),
         };
         
         Self {
@@ -354,6 +368,21 @@ impl ChangeEngineManager {
         }
     }
     
+    pub fn record_forge(&mut self, record: ForgeRecord) {
+        self.engine.forge_records.push(record);
+        if self.auto_save {
+            self.save().ok();
+        }
+    }
+    
+    // Forges that changed the variable, newest first
+    pub fn history_for(&self, variable: &str) -> Vec<&ForgeRecord> {
+        self.engine.forge_records.iter()
+            .rev()
+            .filter(|record| record.changes.contains_key(variable))
+            .collect()
+    }
+    
     pub fn capture_env_state(&mut self, env: &Env) {
         // Capture all variables from environment
         for (name, value) in env.list() {
@@ -432,6 +461,7 @@ impl ChangeEngineManager {
             intent_definitions: self.engine.intent_definitions.len(),
             propagation_rules: self.engine.propagation_rules.len(),
             hooks: self.engine.hooks.len(),
+            forged_transactions: self.engine.forge_records.len(),
             sessions: self.engine.recent_sessions.len() + 
                      if self.engine.current_session.is_some() { 1 } else { 0 },
             last_modified: self.engine.last_modified,
@@ -462,6 +492,7 @@ pub struct EngineStats {
     pub intent_definitions: usize,
     pub propagation_rules: usize,
     pub hooks: usize,
+    pub forged_transactions: usize,
     #[allow(dead_code)]
     pub sessions: usize,
     #[allow(dead_code)]
@@ -16,6 +16,6 @@ pub fn derive(value: &Value) -> Value {
             }
         }
         Value::Int(_) | Value::Bool(_) | Value::Float(_) => value.clone(),
-        Value::List(_) | Value::Dict(_) | Value::Json(_) => value.clone(),
+        Value::List(_) | Value::Dict(_) | Value::Json(_) | Value::Null => value.clone(),
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 // File: src/core/env.rs
-use std::collections::{HashMap, HashSet};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use chrono::Utc;
 use uuid::Uuid;
 
@@ -8,6 +9,9 @@ use crate::core::expr::{Expr, extract_variables};
 use crate::core::propagation::{PropagationEngine, PropagationStrategy};
 use crate::core::transaction::TransactionEngine;
 use crate::core::types::SimpleType;
+use crate::core::schema::Schema;
+use crate::core::script_log::ScriptLog;
+use crate::core::intent::Intent;
 
 //use crate::core::transaction::TransactionPreview;
 
@@ -20,6 +24,17 @@ pub struct Env {
     propagation_engine: PropagationEngine,
     use_new_engine: bool,
     transaction_engine: TransactionEngine,
+    schemas: HashMap<String, Schema>,
+    script_log: ScriptLog,
+    on_error_notification: Option<(String, String)>,
+    defined_intents: HashMap<String, Intent>,
+    // Lazy variables evaluated by a read since the last settle_lazy
+    lazy_reads: RefCell<HashSet<String>>,
+    // `trace on`: print each propagation step as it happens
+    trace_enabled: bool,
+    // `watch`: variables on the live panel, and those propagation touched since the last redraw
+    watched: Vec<String>,
+    watch_changes: HashSet<String>,
 }
 
 impl Env {
@@ -32,6 +47,14 @@ impl Env {
             propagation_engine: PropagationEngine::new(),
             use_new_engine: false,
             transaction_engine: TransactionEngine::new(),
+            schemas: HashMap::new(),
+            script_log: ScriptLog::new(),
+            on_error_notification: None,
+            defined_intents: HashMap::new(),
+            lazy_reads: RefCell::new(HashSet::new()),
+            trace_enabled: false,
+            watched: Vec::new(),
+            watch_changes: HashSet::new(),
         }
     }
     
@@ -122,6 +145,12 @@ impl Env {
             return Err(format!("Forging failed: {}", failures.join(", ")));
         }
 
+        for name in gilded_names(&transaction.metadata) {
+            if let Some(var) = self.variables.get_mut(&name) {
+                var.is_gilded = true;
+            }
+        }
+
         transaction.state = crate::core::transaction::TransactionState::Forged;
         self.transaction_engine.record_transaction(transaction);
         Ok(applied)
@@ -156,6 +185,145 @@ impl Env {
         Ok(())
     }
     
+    // Drop work that forge would do for nothing: assignments overwritten later in the
+    // transaction, and direct sets that leave a variable at its pre-transaction value.
+    // Returns a summary line per elimination followed by the remaining change set.
+    pub fn polish_transaction(&mut self) -> Result<Vec<String>, String> {
+        let transaction = self.transaction_engine.get_active_transaction_mut()
+            .map_err(|e| format!("Polishing error: {}", e))?;
+        
+        let mut summary = Vec::new();
+        let superseded = std::mem::take(&mut transaction.superseded);
+        if superseded > 0 {
+            summary.push(format!("Collapsed {} overwritten assignment(s)", superseded));
+        }
+        
+        let mut cancelled: Vec<String> = transaction.changes.iter()
+            .filter(|(name, change)| {
+                change.expression.is_none() && transaction.snapshot.get(*name) == Some(&change.new_value)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        cancelled.sort();
+        
+        let mut restored = Vec::new();
+        for name in &cancelled {
+            transaction.changes.remove(name);
+            if let Some(original) = transaction.snapshot.get(name) {
+                restored.push((name.clone(), original.clone()));
+            }
+            summary.push(format!("Removed {}: set back to its original value", name));
+        }
+        transaction.modified_at = Utc::now();
+        
+        let mut remaining: Vec<String> = transaction.changes.values()
+            .map(|change| match &change.raw_expression {
+                Some(expr) => format!("{} = {}", change.variable, expr),
+                None => format!("{} = {}", change.variable, change.new_value.display()),
+            })
+            .collect();
+        remaining.sort();
+        
+        // The variables hold placeholders while crafting; put the originals back
+        for (name, original) in restored {
+            if let Some(var) = self.variables.get_mut(&name) {
+                var.value = original;
+            }
+        }
+        
+        summary.push(format!("Eliminated {} change(s), {} remaining", superseded + cancelled.len(), remaining.len()));
+        summary.extend(remaining.into_iter().map(|line| format!("  {}", line)));
+        Ok(summary)
+    }
+    
+    // Merge two recorded transactions into the active one (crafting a new one if needed).
+    // Returns the variables brought in and any conflicts; later changes always win.
+    pub fn alloy(&mut self, id1: &str, id2: &str) -> Result<(Vec<String>, Vec<String>), String> {
+        let (merged, mut conflicts) = self.transaction_engine.merge_transactions(id1, id2)
+            .map_err(|e| format!("Alloy error: {}", e))?;
+        
+        if !self.has_active_transaction() {
+            self.craft(merged.name.as_deref())?;
+        }
+        
+        let mut names: Vec<&String> = merged.changes.keys().collect();
+        names.sort();
+        
+        for name in &names {
+            let change = &merged.changes[*name];
+            if let Ok(active) = self.transaction_engine.inspect() {
+                if let Some(existing) = active.changes.get(*name) {
+                    if existing.new_value != change.new_value || existing.raw_expression != change.raw_expression {
+                        conflicts.push(format!("{}: crafted change replaced by alloy", name));
+                    }
+                }
+            }
+            
+            // Go through the normal setters so placeholders and dependencies are tracked
+            match &change.expression {
+                Some(expr) => self.set_computed_with_type(name, change.new_value.clone(), expr, None),
+                None => self.set_direct_with_type(name, change.new_value.clone(), None),
+            }
+        }
+        
+        if let Ok(active) = self.transaction_engine.get_active_transaction_mut() {
+            for (key, value) in &merged.metadata {
+                active.metadata.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+        
+        Ok((names.into_iter().cloned().collect(), conflicts))
+    }
+    
+    // Gilding marks the variables themselves and, while crafting, the transaction too,
+    // so the mark survives the placeholder variables that crafting creates
+    pub fn gild(&mut self, names: &[String]) -> Result<(), String> {
+        let active = self.has_active_transaction();
+        for name in names {
+            let staged = active && self.transaction_engine.inspect()
+                .is_ok_and(|transaction| transaction.changes.contains_key(name));
+            if !self.variables.contains_key(name) && !staged {
+                return Err(format!("Cannot gild '{}': variable not found", name));
+            }
+        }
+        
+        for name in names {
+            if let Some(var) = self.variables.get_mut(name) {
+                var.is_gilded = true;
+            }
+        }
+        
+        if let Ok(transaction) = self.transaction_engine.get_active_transaction_mut() {
+            let mut gilded = gilded_names(&transaction.metadata);
+            for name in names {
+                if !gilded.contains(name) {
+                    gilded.push(name.clone());
+                }
+            }
+            transaction.add_metadata("gilded", Value::List(gilded.into_iter().map(Value::Str).collect()));
+        }
+        
+        Ok(())
+    }
+    
+    pub fn is_gilded(&self, name: &str) -> bool {
+        self.variables.get(name).is_some_and(|var| var.is_gilded)
+            || self.transaction_engine.inspect()
+                .is_ok_and(|transaction| gilded_names(&transaction.metadata).iter().any(|n| n == name))
+    }
+    
+    pub fn engrave(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let transaction = self.transaction_engine.get_active_transaction_mut()
+            .map_err(|e| format!("Engraving error: {}", e))?;
+        transaction.add_metadata(key, Value::Str(value.to_string()));
+        Ok(())
+    }
+    
+    // The most recently finished (forged, smelted or quenched) transaction
+    pub fn last_transaction(&self) -> Option<&crate::core::transaction::Transaction> {
+        self.transaction_engine.get_transaction_history(1).into_iter().next()
+    }
+    
     fn restore_from_snapshot(&mut self, snapshot: &HashMap<String, Value>) {
         for (var_name, original_value) in snapshot {
             let _ = self.update_value(var_name, original_value.clone());
@@ -206,6 +374,15 @@ impl Env {
                     }
                 }
                 
+                if !transaction.metadata.is_empty() {
+                    let mut keys: Vec<&String> = transaction.metadata.keys().collect();
+                    keys.sort();
+                    output.push_str("  Engraved:\n");
+                    for key in keys {
+                        output.push_str(&format!("    {} = {}\n", key, transaction.metadata[key].display()));
+                    }
+                }
+                
                 Ok(output)
             }
             Err(e) => Err(format!("Inspection error: {:?}", e)),
@@ -442,8 +619,8 @@ impl Env {
             let name = transaction.name.as_deref().unwrap_or("unnamed");
             let time = transaction.created_at.format("%H:%M:%S");
             
-            output.push(format!("{} {} @ {} ({} changes)", 
-                state_str, name, time, transaction.change_count()));
+            output.push(format!("{} {} {} @ {} ({} changes)", 
+                state_str, &transaction.id.to_string()[..8], name, time, transaction.change_count()));
         }
         
         output
@@ -627,6 +804,326 @@ impl Env {
         }
     }
     
+    // Readonly is declared at creation time and, unlike freeze, cannot be undone
+    pub fn mark_readonly(&mut self, name: &str) -> Result<(), String> {
+        match self.variables.get_mut(name) {
+            Some(var) => {
+                var.is_readonly = true;
+                Ok(())
+            }
+            None => Err(format!("Variable '{}' not found", name)),
+        }
+    }
+    
+    // A final variable takes exactly one real (non-null) assignment
+    pub fn mark_final(&mut self, name: &str) -> Result<(), String> {
+        match self.variables.get_mut(name) {
+            Some(var) => {
+                var.is_final = true;
+                Ok(())
+            }
+            None => Err(format!("Variable '{}' not found", name)),
+        }
+    }
+    
+    // Lazy variables keep their expression and a null value until something reads them
+    pub fn set_lazy(&mut self, name: &str, expr: &Expr) {
+        self.set_computed_with_type(name, Value::Null, expr, None);
+        if let Some(var) = self.variables.get_mut(name) {
+            var.is_lazy = true;
+            var.lazy_expr = Some(expr.clone());
+        }
+    }
+    
+    // Reads go through evaluate(), which only borrows the env, so they are
+    // recorded here and cached by settle_lazy once the intent has finished
+    pub fn note_lazy_read(&self, name: &str) {
+        self.lazy_reads.borrow_mut().insert(name.to_string());
+    }
+    
+    pub fn settle_lazy(&mut self) {
+        let names: Vec<String> = self.lazy_reads.borrow_mut().drain().collect();
+        for name in names {
+            let expr = match self.variables.get(&name).and_then(|var| var.lazy_expr.clone()) {
+                Some(expr) => expr,
+                None => continue,
+            };
+            if let Ok(value) = crate::core::expr::evaluate(&expr, self) {
+                if let Some(var) = self.variables.get_mut(&name) {
+                    var.value = value;
+                    var.lazy_expr = None;
+                    var.last_updated = Utc::now();
+                    var.update_count += 1;
+                }
+            }
+        }
+        self.lazy_reads.borrow_mut().clear();
+    }
+    
+    pub fn set_trace(&mut self, enabled: bool) {
+        self.trace_enabled = enabled;
+    }
+    
+    pub fn trace_enabled(&self) -> bool {
+        self.trace_enabled
+    }
+    
+    pub fn set_watched(&mut self, names: Vec<String>) {
+        self.watched = names;
+        self.watch_changes.clear();
+    }
+    
+    // Post-propagation hook: propagate_from_all reports every variable it touched
+    pub fn after_propagation(&mut self, touched: &[String]) {
+        if self.watched.is_empty() {
+            return;
+        }
+        for name in touched {
+            if self.watched.contains(name) {
+                self.watch_changes.insert(name.clone());
+            }
+        }
+    }
+    
+    pub fn take_watch_changes(&mut self) -> HashSet<String> {
+        std::mem::take(&mut self.watch_changes)
+    }
+    
+    pub fn is_final(&self, name: &str) -> bool {
+        self.variables.get(name).is_some_and(|var| var.is_final)
+    }
+    
+    pub fn check_writable(&self, name: &str) -> Result<(), String> {
+        if let Some(var) = self.variables.get(name) {
+            if var.is_readonly {
+                return Err(format!("Cannot modify readonly variable '{}'", name));
+            }
+            if var.is_final && var.value != Value::Null {
+                return Err(format!("Cannot reassign final variable '{}'", name));
+            }
+        }
+        Ok(())
+    }
+    
+    // Remove a variable and every graph edge touching it. Dependents are
+    // re-evaluated where possible and fall back to null otherwise
+    pub fn unset(&mut self, name: &str) -> Result<(), String> {
+        let var = self.variables.get(name)
+            .ok_or_else(|| format!("Variable '{}' not found", name))?;
+        if var.is_readonly {
+            return Err(format!("Cannot unset readonly variable '{}'", name));
+        }
+        if var.is_constant {
+            return Err(format!("Cannot unset frozen variable '{}'", name));
+        }
+        if self.has_active_transaction() {
+            return Err(format!("Cannot unset '{}' while crafting; forge or smelt first", name));
+        }
+        
+        let dependents = self.get_dependents(name);
+        self.remove_dependencies(name);
+        self.variables.remove(name);
+        self.dependents.remove(name);
+        for dependencies in self.dependencies.values_mut() {
+            dependencies.remove(name);
+        }
+        
+        if self.use_new_engine {
+            self.propagation_engine.graph_mut().remove_variable(name).ok();
+        }
+        
+        let mut queue = dependents;
+        let mut visited = HashSet::new();
+        while let Some(dependent) = queue.pop() {
+            if !visited.insert(dependent.clone()) {
+                continue;
+            }
+            let value = self.expressions.get(&dependent)
+                .and_then(|expr| crate::core::expr::evaluate(expr, self).ok())
+                .unwrap_or(Value::Null);
+            if self.update_value(&dependent, value).is_ok() {
+                queue.extend(self.get_dependents(&dependent));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    // Move a variable to a new name, carrying its graph edges with it and
+    // rewriting dependent expressions so propagation keeps working
+    pub fn rename(&mut self, old: &str, new: &str, force: bool) -> Result<(), String> {
+        if !self.variables.contains_key(old) {
+            return Err(format!("Variable '{}' not found", old));
+        }
+        if old == new {
+            return Ok(());
+        }
+        if self.has_active_transaction() {
+            return Err(format!("Cannot rename '{}' while crafting; forge or smelt first", old));
+        }
+        if self.variables.contains_key(new) {
+            if !force {
+                return Err(format!("Variable '{}' already exists (use --force to overwrite)", new));
+            }
+            self.check_writable(new)?;
+            if self.variables.get(new).is_some_and(|var| var.is_constant) {
+                return Err(format!("Cannot overwrite frozen variable '{}'", new));
+            }
+            self.remove_dependencies(new);
+            self.variables.remove(new);
+        }
+        
+        if let Some(var) = self.variables.remove(old) {
+            self.variables.insert(new.to_string(), var);
+        }
+        if let Some(expr) = self.expressions.remove(old) {
+            self.expressions.insert(new.to_string(), expr);
+        }
+        if let Some(deps) = self.dependencies.remove(old) {
+            self.dependencies.insert(new.to_string(), deps);
+        }
+        
+        // Anything that depended on the overwritten variable now depends on the renamed one
+        let mut dependents = self.dependents.remove(old).unwrap_or_default();
+        dependents.extend(self.dependents.remove(new).unwrap_or_default());
+        dependents.remove(new);
+        
+        for edges in self.dependencies.values_mut().chain(self.dependents.values_mut()) {
+            if edges.remove(old) {
+                edges.insert(new.to_string());
+            }
+        }
+        
+        for dependent in &dependents {
+            if let Some(deps) = self.dependencies.get_mut(dependent) {
+                deps.insert(new.to_string());
+            }
+            let source = match self.expressions.get(dependent) {
+                Some(expr) => crate::core::expr::rename_identifier(&expr.to_string(), old, new),
+                None => continue,
+            };
+            let parsed = crate::core::expr::parse_expression(&source).ok();
+            if let Some(var) = self.variables.get_mut(dependent) {
+                if var.lazy_expr.is_some() {
+                    var.lazy_expr = parsed.clone();
+                }
+                var.expression = Some(source);
+            }
+            if let Some(expr) = parsed {
+                self.expressions.insert(dependent.clone(), expr);
+            }
+        }
+        if !dependents.is_empty() {
+            self.dependents.insert(new.to_string(), dependents);
+        }
+        
+        if force {
+            crate::core::propagate::propagate_from(self, new)?;
+        }
+        Ok(())
+    }
+    
+    // Exchange two values in one step. Both become direct variables holding the
+    // other's value, and their dependents are propagated in a single pass
+    pub fn swap(&mut self, a: &str, b: &str) -> Result<Vec<String>, String> {
+        for name in [a, b] {
+            let var = self.variables.get(name)
+                .ok_or_else(|| format!("Variable '{}' not found", name))?;
+            if var.is_constant {
+                return Err(format!("Cannot swap frozen variable '{}'", name));
+            }
+            self.check_writable(name)?;
+        }
+        if a == b {
+            return Ok(Vec::new());
+        }
+        
+        // Evaluated rather than read so pending lazy variables yield their real value
+        let value_a = crate::core::expr::evaluate(&Expr::Variable(a.to_string()), self)?;
+        let value_b = crate::core::expr::evaluate(&Expr::Variable(b.to_string()), self)?;
+        let type_a = self.variables.get(a).and_then(|var| var.declared_type.clone());
+        let type_b = self.variables.get(b).and_then(|var| var.declared_type.clone());
+        
+        // Declared types travel with their values, so an int and a string swap cleanly
+        self.set_direct_with_type(a, value_b, type_b);
+        self.set_direct_with_type(b, value_a, type_a);
+        
+        if self.has_active_transaction() {
+            return Ok(Vec::new());
+        }
+        crate::core::propagate::propagate_from_all(self, &[a, b])
+    }
+    
+    // Full variable records for `snapshot`, sorted so snapshot files diff cleanly
+    pub fn snapshot_variables(&self) -> BTreeMap<String, Variable> {
+        self.variables.iter()
+            .map(|(name, var)| (name.clone(), var.clone()))
+            .collect()
+    }
+    
+    // `restore`: with replace the current variables are dropped first, otherwise
+    // the snapshot is merged in and frozen or readonly names keep their current
+    // value. Returns (restored, skipped)
+    pub fn restore_variables(&mut self, variables: BTreeMap<String, Variable>, replace: bool) -> Result<(Vec<String>, Vec<String>), String> {
+        if self.has_active_transaction() {
+            return Err("Cannot restore a snapshot during an active transaction".to_string());
+        }
+        
+        // Parse every expression up front so a bad snapshot leaves the env untouched
+        let mut restored = Vec::new();
+        let mut skipped = Vec::new();
+        for (name, var) in variables {
+            if !replace && self.variables.get(&name).is_some_and(|current| current.is_constant || current.is_readonly) {
+                skipped.push(name);
+                continue;
+            }
+            let expr = match &var.expression {
+                Some(expr_str) => Some(crate::core::expr::parse_expression(expr_str)
+                    .map_err(|e| format!("Cannot restore '{}': {}", name, e))?),
+                None => None,
+            };
+            restored.push((name, var, expr));
+        }
+        
+        if replace {
+            self.variables.clear();
+            self.expressions.clear();
+            self.dependents.clear();
+            self.dependencies.clear();
+        }
+        
+        let mut names = Vec::new();
+        for (name, mut var, expr) in restored {
+            self.remove_dependencies(&name);
+            if let Some(expr) = expr {
+                let deps: HashSet<String> = extract_variables(&expr).into_iter()
+                    .filter(|dep| dep != &name)
+                    .collect();
+                for dep in &deps {
+                    self.dependents.entry(dep.clone())
+                        .or_default()
+                        .insert(name.clone());
+                }
+                self.dependencies.insert(name.clone(), deps);
+                if var.is_lazy && matches!(var.value, Value::Null) {
+                    var.lazy_expr = Some(expr.clone());
+                }
+                self.expressions.insert(name.clone(), expr);
+            }
+            self.variables.insert(name.clone(), var);
+            names.push(name);
+        }
+        
+        if self.use_new_engine {
+            self.migrate_to_new_engine()?;
+        }
+        if !replace {
+            let changed: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+            crate::core::propagate::propagate_from_all(self, &changed)?;
+        }
+        Ok((names, skipped))
+    }
+    
     pub fn get_value(&self, name: &str) -> Option<&Value> {
         self.variables.get(name).map(|v| &v.value)
     }
@@ -665,6 +1162,45 @@ impl Env {
     pub fn get_expression(&self, name: &str) -> Option<&Expr> {
         self.expressions.get(name)
     }
+
+    // ==================== SCHEMAS ====================
+
+    pub fn define_schema(&mut self, schema: Schema) {
+        self.schemas.insert(schema.name.clone(), schema);
+    }
+
+    pub fn get_schema(&self, name: &str) -> Option<&Schema> {
+        self.schemas.get(name)
+    }
+
+    // ==================== DEFINED INTENTS ====================
+
+    pub fn defined_intents(&self) -> &HashMap<String, Intent> {
+        &self.defined_intents
+    }
+
+    pub fn defined_intents_mut(&mut self) -> &mut HashMap<String, Intent> {
+        &mut self.defined_intents
+    }
+
+    // ==================== SCRIPT LOG ====================
+
+    pub fn script_log(&self) -> &ScriptLog {
+        &self.script_log
+    }
+
+    pub fn script_log_mut(&mut self) -> &mut ScriptLog {
+        &mut self.script_log
+    }
+
+    // (title, message) sent when a script finishes with errors
+    pub fn set_on_error_notification(&mut self, title: &str, message: &str) {
+        self.on_error_notification = Some((title.to_string(), message.to_string()));
+    }
+
+    pub fn on_error_notification(&self) -> Option<&(String, String)> {
+        self.on_error_notification.as_ref()
+    }
     
     pub fn propagate_from_enhanced(&mut self, changed_var: &str) -> Result<Vec<String>, String> {
         if !self.use_new_engine {
@@ -1071,6 +1607,8 @@ impl Env {
     }
 
     pub fn update_value_without_propagation_check(&mut self, name: &str, value: Value) -> Result<(), String> {
+        self.check_writable(name)?;
+        
         if let Some(var) = self.variables.get_mut(name) {
             if var.is_constant {
                 return Err(format!("Variable '{}' is frozen", name));
@@ -1089,3 +1627,90 @@ impl Env {
 
 }
 
+
+fn gilded_names(metadata: &HashMap<String, Value>) -> Vec<String> {
+    match metadata.get("gilded") {
+        Some(Value::List(items)) => items.iter().map(|item| item.to_string()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readonly() {
+        let mut env = Env::new();
+        env.set_direct("r", Value::Int(1));
+        env.mark_readonly("r").unwrap();
+        assert_eq!(env.check_writable("r").unwrap_err(), "Cannot modify readonly variable 'r'");
+        assert!(env.check_writable("other").is_ok());
+        assert!(env.mark_readonly("missing").is_err());
+    }
+
+    #[test]
+    fn test_final_allows_one_assignment() {
+        let mut env = Env::new();
+        env.set_direct("f", Value::Null);
+        env.mark_final("f").unwrap();
+        assert!(env.check_writable("f").is_ok());
+
+        env.set_direct("g", Value::Int(2));
+        env.mark_final("g").unwrap();
+        assert!(env.is_final("g"));
+        assert_eq!(env.check_writable("g").unwrap_err(), "Cannot reassign final variable 'g'");
+    }
+
+    #[test]
+    fn test_lazy_evaluates_on_read() {
+        let mut env = Env::new();
+        env.set_direct("x", Value::Int(2));
+        env.set_lazy("y", &crate::core::expr::parse_expression("x * 3").unwrap());
+        assert_eq!(env.get_value("y"), Some(&Value::Null));
+        let read = crate::core::expr::parse_expression("y + 1").unwrap();
+        assert_eq!(crate::core::expr::evaluate(&read, &env).unwrap(), Value::Int(7));
+        env.settle_lazy();
+        assert_eq!(env.get_value("y"), Some(&Value::Int(6)));
+    }
+
+    #[test]
+    fn test_unset() {
+        let mut env = Env::new();
+        env.set_direct("c", Value::Int(3));
+        env.unset("c").unwrap();
+        assert_eq!(env.get_value("c"), None);
+        assert_eq!(env.unset("c").unwrap_err(), "Variable 'c' not found");
+
+        env.set_direct("r", Value::Int(1));
+        env.mark_readonly("r").unwrap();
+        assert_eq!(env.unset("r").unwrap_err(), "Cannot unset readonly variable 'r'");
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut env = Env::new();
+        env.set_direct("a", Value::Int(1));
+        env.set_direct("b", Value::Int(2));
+        env.rename("a", "n", false).unwrap();
+        assert_eq!(env.get_value("a"), None);
+        assert_eq!(env.get_value("n"), Some(&Value::Int(1)));
+        assert_eq!(env.rename("n", "b", false).unwrap_err(), "Variable 'b' already exists (use --force to overwrite)");
+        env.rename("n", "b", true).unwrap();
+        assert_eq!(env.get_value("b"), Some(&Value::Int(1)));
+        assert!(env.rename("missing", "x", false).is_err());
+    }
+
+    #[test]
+    fn test_swap_moves_declared_types() {
+        let mut env = Env::new();
+        env.set_direct("n", Value::Int(1));
+        env.set_direct_with_type("b", Value::Str("two".to_string()), Some(SimpleType::String));
+        env.swap("n", "b").unwrap();
+        assert_eq!(env.get_value("n"), Some(&Value::Str("two".to_string())));
+        assert_eq!(env.get_value("b"), Some(&Value::Int(1)));
+        assert_eq!(env.get_variable("n").and_then(|var| var.declared_type.clone()), Some(SimpleType::String));
+        assert_eq!(env.get_variable("b").and_then(|var| var.declared_type.clone()), None);
+        assert_eq!(env.swap("n", "missing").unwrap_err(), "Variable 'missing' not found");
+    }
+}
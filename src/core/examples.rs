@@ -0,0 +1,113 @@
+// File: src/core/examples.rs
+
+// Runnable examples shown by `examples <verb>` and executed by `examples run <n>`.
+// Examples are numbered 1..N across all verbs so `run` never needs the verb again.
+pub struct MorrisExample {
+    pub verb: &'static str,
+    pub title: &'static str,
+    pub code: &'static str,
+    pub explanation: &'static str,
+}
+
+pub const EXAMPLES: &[MorrisExample] = &[
+    MorrisExample {
+        verb: "set",
+        title: "Reactive totals",
+        code: "set price = 20\nset quantity = 3\nset total = price * quantity",
+        explanation: "total is recalculated whenever price or quantity changes.",
+    },
+    MorrisExample {
+        verb: "set",
+        title: "Conditional value",
+        code: "set score = 84\nset grade = \"A\" when score >= 90 | \"B\" when score >= 80 | \"C\"",
+        explanation: "The first branch whose condition holds wins; the last value is the fallback.",
+    },
+    MorrisExample {
+        verb: "set",
+        title: "Typed input",
+        code: "set retries:int = 3\nset next_try = retries + 1",
+        explanation: "The :int hint declares the variable's type; it labels the value and does not convert a quoted string.",
+    },
+    MorrisExample {
+        verb: "writeout",
+        title: "Interpolated report line",
+        code: "set revenue = 1200\nset cost = 800\nset margin = (revenue - cost) / revenue * 100\nwriteout(Margin: {margin}%)",
+        explanation: "Variables inside {braces} are replaced with their current value.",
+    },
+    MorrisExample {
+        verb: "craft",
+        title: "Staged change",
+        code: "set rate = 1\ncraft \"rate bump\"\nset rate = 2\ninspect\nforge",
+        explanation: "Changes between craft and forge are staged and applied together.",
+    },
+    MorrisExample {
+        verb: "forge",
+        title: "Apply a transaction",
+        code: "craft \"batch\"\nset a = 1\nset b = 2\nforge",
+        explanation: "forge commits every staged change at once.",
+    },
+    MorrisExample {
+        verb: "switch",
+        title: "Branch on a value",
+        code: "set env = \"prod\"\nswitch env { case \"prod\" then set replicas = 3; default set replicas = 1 }",
+        explanation: "Only the first matching case runs.",
+    },
+    MorrisExample {
+        verb: "try",
+        title: "Recover from a missing file",
+        code: "try { load \"missing.msh\" } catch /Cannot read/ { writeout(skipped: {_error}) }",
+        explanation: "The error text is available as _error inside catch.",
+    },
+    MorrisExample {
+        verb: "log",
+        title: "Structured log entry",
+        code: "set version = \"1.4\"\nlog info \"deployed {version}\" fields { env=\"staging\" }",
+        explanation: "Appends a JSON line to ~/.morris_script.log.",
+    },
+    MorrisExample {
+        verb: "schema",
+        title: "Generate and validate rows",
+        code: "schema \"user\" { name: string, age: int(18..90) }\ngenerate \"user\" count 3 into users\nassert-schema users against \"user\"",
+        explanation: "Generated rows always satisfy the schema they came from.",
+    },
+    MorrisExample {
+        verb: "merge",
+        title: "Deep merge of two dicts",
+        code: "set defaults = {\"port\": 80, \"debug\": false}\nset overrides = {\"debug\": true}\nmerge defaults and overrides into config",
+        explanation: "With the default last_wins strategy, overrides take precedence.",
+    },
+];
+
+// (global number, example) pairs for one verb
+pub fn examples_for(verb: &str) -> Vec<(usize, &'static MorrisExample)> {
+    EXAMPLES.iter()
+        .enumerate()
+        .filter(|(_, example)| example.verb == verb)
+        .map(|(i, example)| (i + 1, example))
+        .collect()
+}
+
+pub fn example_by_number(n: usize) -> Option<&'static MorrisExample> {
+    n.checked_sub(1).and_then(|i| EXAMPLES.get(i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_parse_and_number_globally() {
+        for example in EXAMPLES {
+            for line in example.code.lines() {
+                assert!(crate::core::intent::parse_to_intent(line).is_ok(), "example '{}' line '{}'", example.title, line);
+            }
+        }
+
+        let sets = examples_for("set");
+        assert_eq!(sets[0].0, 1);
+        assert!(sets.iter().all(|(_, example)| example.verb == "set"));
+        assert_eq!(example_by_number(sets[1].0).map(|e| e.title), Some(sets[1].1.title));
+        assert!(example_by_number(0).is_none());
+        assert!(example_by_number(EXAMPLES.len() + 1).is_none());
+    }
+}
@@ -20,6 +20,16 @@ pub enum Expr {
     Subtract(Box<Expr>, Box<Expr>),
     Multiply(Box<Expr>, Box<Expr>),
     Divide(Box<Expr>, Box<Expr>),
+    IntDivide(Box<Expr>, Box<Expr>),
+    Modulo(Box<Expr>, Box<Expr>),
+    Power(Box<Expr>, Box<Expr>),
+    // Bitwise operators (integers only)
+    BitAnd(Box<Expr>, Box<Expr>),
+    BitOr(Box<Expr>, Box<Expr>),
+    BitXor(Box<Expr>, Box<Expr>),
+    BitNot(Box<Expr>),
+    Shl(Box<Expr>, Box<Expr>),
+    Shr(Box<Expr>, Box<Expr>),
     FunctionCall(String, Vec<Expr>),
     Conditional(Vec<ConditionalBranch>),
     // NEW: Comparison operators
@@ -48,6 +58,7 @@ impl std::fmt::Display for Expr {
             Expr::Literal(Value::Int(i)) => write!(f, "{}", i),
             Expr::Literal(Value::Float(fl)) => write!(f, "{}", fl),
             Expr::Literal(Value::Bool(b)) => write!(f, "{}", b),
+            Expr::Literal(Value::Null) => write!(f, "null"),
             Expr::Literal(Value::List(items)) => {
                 write!(f, "[")?;
                 for (i, item) in items.iter().enumerate() {
@@ -69,6 +80,15 @@ impl std::fmt::Display for Expr {
             Expr::Subtract(left, right) => write!(f, "({} - {})", left, right),
             Expr::Multiply(left, right) => write!(f, "({} * {})", left, right),
             Expr::Divide(left, right) => write!(f, "({} / {})", left, right),
+            Expr::IntDivide(left, right) => write!(f, "({} // {})", left, right),
+            Expr::Modulo(left, right) => write!(f, "({} % {})", left, right),
+            Expr::Power(left, right) => write!(f, "({} ** {})", left, right),
+            Expr::BitAnd(left, right) => write!(f, "({} & {})", left, right),
+            Expr::BitOr(left, right) => write!(f, "({} | {})", left, right),
+            Expr::BitXor(left, right) => write!(f, "({} ^ {})", left, right),
+            Expr::BitNot(expr) => write!(f, "(~{})", expr),
+            Expr::Shl(left, right) => write!(f, "({} << {})", left, right),
+            Expr::Shr(left, right) => write!(f, "({} >> {})", left, right),
             Expr::GreaterThan(left, right) => write!(f, "({} > {})", left, right),
             Expr::GreaterThanOrEqual(left, right) => write!(f, "({} >= {})", left, right),
             Expr::LessThan(left, right) => write!(f, "({} < {})", left, right),
@@ -214,9 +234,13 @@ fn parse_token(token: &str) -> Expr {
     if token == "false" {
         return Expr::Literal(Value::Bool(false));
     }
+    if token == "null" {
+        return Expr::Literal(Value::Null);
+    }
     
     // Check for method calls: obj.method() or obj.method(arg1, arg2)
-    if token.contains('.') && token.contains('(') && token.ends_with(')') {
+    // (only a dot before the first paren counts - `sum([1, 2.5])` is a plain call)
+    if token.contains('(') && token.ends_with(')') && token[..token.find('(').unwrap()].contains('.') {
         return parse_method_call(token);
     }
     
@@ -244,11 +268,12 @@ fn parse_token(token: &str) -> Expr {
                     }
                     current_arg.push(ch);
                 }
-                '(' if !in_quotes => {
+                // List and dict literals keep their commas: sum([1, 2, 3])
+                '(' | '[' | '{' if !in_quotes => {
                     paren_depth += 1;
                     current_arg.push(ch);
                 }
-                ')' if !in_quotes => {
+                ')' | ']' | '}' if !in_quotes => {
                     paren_depth -= 1;
                     current_arg.push(ch);
                 }
@@ -277,8 +302,9 @@ fn parse_token(token: &str) -> Expr {
         if content.is_empty() {
             return Expr::List(Vec::new());
         }
-        // Very simple parser for now - just split by comma
-        let items: Vec<Expr> = content.split(',')
+        // Nested lists and dicts keep their own commas
+        let items: Vec<Expr> = split_arguments_respecting_nesting(content)
+            .iter()
             .map(|item| parse_token(item.trim()))
             .collect();
         return Expr::List(items);
@@ -406,11 +432,11 @@ fn split_arguments_respecting_nesting(s: &str) -> Vec<String> {
                 paren_depth -= 1;
                 current.push(ch);
             }
-            '[' if !in_quotes => {
+            '[' | '{' if !in_quotes => {
                 bracket_depth += 1;
                 current.push(ch);
             }
-            ']' if !in_quotes => {
+            ']' | '}' if !in_quotes => {
                 bracket_depth -= 1;
                 current.push(ch);
             }
@@ -443,8 +469,9 @@ fn is_conditional_expression(s: &str) -> bool {
             ')' if !in_quotes => paren_depth -= 1,
             '{' if !in_quotes => in_braces += 1,
             '}' if !in_quotes => in_braces -= 1,
+            // A bare `|` is bitwise or; conditionals always have at least one `when`
             '|' if !in_quotes && paren_depth == 0 && in_braces == 0 => {
-                return true;
+                return find_when_keyword_advanced(s).is_some();
             }
             _ => {}
         }
@@ -611,7 +638,7 @@ fn parse_conditional_expression(s: &str) -> Result<Expr, String> {
     Ok(Expr::Conditional(branches))
 }
 
-fn parse_condition_expression(s: &str) -> Result<Expr, String> {
+pub fn parse_condition_expression(s: &str) -> Result<Expr, String> {
     let s = s.trim();
     
     // Handle "not" operator
@@ -701,7 +728,12 @@ fn parse_condition_expression(s: &str) -> Result<Expr, String> {
             let open_parens = before.chars().filter(|&c| c == '(').count();
             let close_parens = before.chars().filter(|&c| c == ')').count();
         
-            if open_parens == close_parens {
+            // `<<` and `>>` are shifts, not comparisons
+            let is_shift = (op == "<" || op == ">")
+                && (before.ends_with(op) || after.starts_with(op));
+            let in_string = before.matches('"').count() % 2 == 1;
+
+            if open_parens == close_parens && !is_shift && !in_string {
                 // At top level
                 let left = before.trim();
                 let right = after.trim();
@@ -796,44 +828,74 @@ fn parse_operator_expression(s: &str) -> Result<Expr, String> {
     let mut paren_depth = 0;
     let mut best_pos = None;
     let mut best_op = None;
-    let mut best_precedence = 3;
+    let mut best_precedence = 8;
+    let mut skip_next = false;
 
-    // Handle negative numbers
-    if s.starts_with('-') {
-        let rest = &s[1..].trim();
-        if !rest.is_empty() {
-            // Check if it's a number
-            if let Ok(n) = rest.parse::<i64>() {
-                return Ok(Expr::Literal(Value::Int(-n)));
-            }
-            // Or it could be a parenthesized expression
-            // For simplicity, we'll parse it as 0 - expression
-            let expr = parse_operator_expression(rest)?;
-            return Ok(Expr::Subtract(Box::new(Expr::Literal(Value::Int(0))), Box::new(expr)));
-        }
-    }
-    
+    let mut in_quotes = false;
     for (i, ch) in s.char_indices() {
-        match ch {
-            '(' => paren_depth += 1,
-            ')' => paren_depth -= 1,
-            '+' | '-' if paren_depth == 0 => {
-                best_pos = Some(i);
-                best_op = Some(ch);
-                best_precedence = 1;
-            }
-            '*' | '/' if paren_depth == 0 && best_precedence > 1 => {
-                best_pos = Some(i);
-                best_op = Some(ch);
-                best_precedence = 2;
+        if skip_next {
+            skip_next = false;
+            continue;
+        }
+        if ch == '"' {
+            in_quotes = !in_quotes;
+            continue;
+        }
+        if in_quotes {
+            continue;
+        }
+        // Lower precedence binds looser: | ^ & shifts, then + -, then * / // %, then **
+        let (op, precedence) = match ch {
+            '(' => {
+                paren_depth += 1;
+                continue;
             }
-            _ => {}
+            ')' => {
+                paren_depth -= 1;
+                continue;
+            }
+            _ if paren_depth != 0 => continue,
+            // A sign with nothing or another operator before it is unary: -5 + y, x * -1, 2 ** -1
+            '+' | '-' if s[..i].trim_end().is_empty()
+                || s[..i].trim_end().ends_with(['+', '-', '*', '/', '%', '&', '|', '^', '<', '>', '~']) => continue,
+            '|' => ("|", 1),
+            '^' => ("^", 2),
+            '&' => ("&", 3),
+            '<' if s[i..].starts_with("<<") => {
+                skip_next = true;
+                ("<<", 4)
+            }
+            '>' if s[i..].starts_with(">>") => {
+                skip_next = true;
+                (">>", 4)
+            }
+            '+' => ("+", 5),
+            '-' => ("-", 5),
+            // Two-character operators must be checked before `*` and `/`
+            '*' if s[i..].starts_with("**") => {
+                skip_next = true;
+                ("**", 7)
+            }
+            '/' if s[i..].starts_with("//") => {
+                skip_next = true;
+                ("//", 6)
+            }
+            '*' => ("*", 6),
+            '/' => ("/", 6),
+            '%' => ("%", 6),
+            _ => continue,
+        };
+        // Ties go to the rightmost operator so chains group left, except `**` which groups right
+        if precedence < best_precedence || (precedence == best_precedence && op != "**") {
+            best_pos = Some(i);
+            best_op = Some(op);
+            best_precedence = precedence;
         }
     }
     
     if let (Some(pos), Some(op)) = (best_pos, best_op) {
         let left = s[..pos].trim();
-        let right = s[pos+1..].trim();
+        let right = s[pos + op.len()..].trim();
         
         if left.is_empty() || right.is_empty() {
             return Err(format!("Incomplete expression around '{}'", op));
@@ -843,12 +905,38 @@ fn parse_operator_expression(s: &str) -> Result<Expr, String> {
         let right_expr = parse_operator_expression(right)?;
         
         match op {
-            '+' => Ok(Expr::Add(Box::new(left_expr), Box::new(right_expr))),
-            '-' => Ok(Expr::Subtract(Box::new(left_expr), Box::new(right_expr))),
-            '*' => Ok(Expr::Multiply(Box::new(left_expr), Box::new(right_expr))),
-            '/' => Ok(Expr::Divide(Box::new(left_expr), Box::new(right_expr))),
+            "+" => Ok(Expr::Add(Box::new(left_expr), Box::new(right_expr))),
+            "-" => Ok(Expr::Subtract(Box::new(left_expr), Box::new(right_expr))),
+            "*" => Ok(Expr::Multiply(Box::new(left_expr), Box::new(right_expr))),
+            "/" => Ok(Expr::Divide(Box::new(left_expr), Box::new(right_expr))),
+            "//" => Ok(Expr::IntDivide(Box::new(left_expr), Box::new(right_expr))),
+            "%" => Ok(Expr::Modulo(Box::new(left_expr), Box::new(right_expr))),
+            "**" => Ok(Expr::Power(Box::new(left_expr), Box::new(right_expr))),
+            "&" => Ok(Expr::BitAnd(Box::new(left_expr), Box::new(right_expr))),
+            "|" => Ok(Expr::BitOr(Box::new(left_expr), Box::new(right_expr))),
+            "^" => Ok(Expr::BitXor(Box::new(left_expr), Box::new(right_expr))),
+            "<<" => Ok(Expr::Shl(Box::new(left_expr), Box::new(right_expr))),
+            ">>" => Ok(Expr::Shr(Box::new(left_expr), Box::new(right_expr))),
             _ => unreachable!(),
         }
+    } else if let Some(rest) = s.strip_prefix('-') {
+        // Unary minus: negative literals fold, anything else becomes 0 - expression
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return Err("Missing operand after '-'".to_string());
+        }
+        match parse_operator_expression(rest)? {
+            Expr::Literal(Value::Int(n)) => Ok(Expr::Literal(Value::Int(-n))),
+            Expr::Literal(Value::Float(f)) => Ok(Expr::Literal(Value::Float(-f))),
+            expr => Ok(Expr::Subtract(Box::new(Expr::Literal(Value::Int(0))), Box::new(expr))),
+        }
+    } else if let Some(rest) = s.strip_prefix('~') {
+        // Unary bitwise complement, like `not` for booleans
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return Err("Missing operand after '~'".to_string());
+        }
+        Ok(Expr::BitNot(Box::new(parse_operator_expression(rest)?)))
     } else {
         if s.starts_with('(') && s.ends_with(')') {
             let mut depth = 0;
@@ -887,7 +975,8 @@ fn parse_expr_with_precedence(s: &str) -> Result<Expr, String> {
     if is_conditional_expression(s) {
         parse_conditional_expression(s)
     } else {
-        parse_operator_expression(s)
+        // Comparisons and and/or/not are valid outside `when` too: set same = a == b
+        parse_condition_expression(s)
     }
 }
 
@@ -915,6 +1004,10 @@ pub fn evaluate(expr: &Expr, env: &Env) -> Result<Value, String> {
         }
         Expr::Literal(value) => Ok(value.clone()),
         Expr::Variable(name) => {
+            if let Some(lazy_expr) = env.get_variable(name).and_then(|var| var.lazy_expr.as_ref()) {
+                env.note_lazy_read(name);
+                return evaluate(lazy_expr, env);
+            }
             env.get_value(name)
                 .cloned()
                 .ok_or_else(|| format!("Variable not found: {}", name))
@@ -992,6 +1085,124 @@ pub fn evaluate(expr: &Expr, env: &Env) -> Result<Value, String> {
                 _ => Err(format!("Cannot divide {} by {} - must be int or float", left_val.type_name(), right_val.type_name())),
             }
         }
+        Expr::IntDivide(left, right) => {
+            let left_val = evaluate(left, env)?;
+            let right_val = evaluate(right, env)?;
+            let (a, b) = match (&left_val, &right_val) {
+                (Value::Int(a), Value::Int(b)) => {
+                    if *b == 0 {
+                        return Err("Division by zero".to_string());
+                    }
+                    // Round toward negative infinity like the float case, not toward zero
+                    let quotient = a / b;
+                    let floored = if a % b != 0 && ((*a < 0) != (*b < 0)) { quotient - 1 } else { quotient };
+                    return Ok(Value::Int(floored));
+                }
+                (Value::Float(a), Value::Float(b)) => (*a, *b),
+                (Value::Int(a), Value::Float(b)) => (*a as f64, *b),
+                (Value::Float(a), Value::Int(b)) => (*a, *b as f64),
+                _ => return Err(format!("Cannot divide {} by {} - must be int or float", left_val.type_name(), right_val.type_name())),
+            };
+            if b == 0.0 {
+                Err("Division by zero".to_string())
+            } else {
+                Ok(Value::Int((a / b).floor() as i64))
+            }
+        }
+        Expr::Modulo(left, right) => {
+            let left_val = evaluate(left, env)?;
+            let right_val = evaluate(right, env)?;
+            match (&left_val, &right_val) {
+                (Value::Int(a), Value::Int(b)) => {
+                    if *b == 0 {
+                        Err("Modulo by zero".to_string())
+                    } else {
+                        Ok(Value::Int(a % b))
+                    }
+                }
+                (Value::Float(a), Value::Float(b)) => {
+                    if *b == 0.0 {
+                        Err("Modulo by zero".to_string())
+                    } else {
+                        Ok(Value::Float(a % b))
+                    }
+                }
+                (Value::Int(a), Value::Float(b)) => {
+                    if *b == 0.0 {
+                        Err("Modulo by zero".to_string())
+                    } else {
+                        Ok(Value::Float((*a as f64) % *b))
+                    }
+                }
+                (Value::Float(a), Value::Int(b)) => {
+                    if *b == 0 {
+                        Err("Modulo by zero".to_string())
+                    } else {
+                        Ok(Value::Float(*a % (*b as f64)))
+                    }
+                }
+                _ => Err(format!("Cannot take {} modulo {} - must be int or float", left_val.type_name(), right_val.type_name())),
+            }
+        }
+        Expr::Power(left, right) => {
+            let left_val = evaluate(left, env)?;
+            let right_val = evaluate(right, env)?;
+            match (&left_val, &right_val) {
+                (Value::Int(a), Value::Int(b)) => {
+                    let exponent = i32::try_from(*b)
+                        .map_err(|_| format!("Exponent {} is out of range", b))?;
+                    Ok(Value::Float((*a as f64).powi(exponent)))
+                }
+                (Value::Float(a), Value::Float(b)) => Ok(Value::Float(a.powf(*b))),
+                (Value::Int(a), Value::Float(b)) => Ok(Value::Float((*a as f64).powf(*b))),
+                (Value::Float(a), Value::Int(b)) => {
+                    let exponent = i32::try_from(*b)
+                        .map_err(|_| format!("Exponent {} is out of range", b))?;
+                    Ok(Value::Float(a.powi(exponent)))
+                }
+                _ => Err(format!("Cannot raise {} to {} - must be int or float", left_val.type_name(), right_val.type_name())),
+            }
+        }
+        Expr::BitAnd(left, right) => {
+            let (a, b) = int_operands(evaluate(left, env)?, evaluate(right, env)?, "&")?;
+            Ok(Value::Int(a & b))
+        }
+        Expr::BitOr(left, right) => {
+            let (a, b) = int_operands(evaluate(left, env)?, evaluate(right, env)?, "|")?;
+            Ok(Value::Int(a | b))
+        }
+        Expr::BitXor(left, right) => {
+            let (a, b) = int_operands(evaluate(left, env)?, evaluate(right, env)?, "^")?;
+            Ok(Value::Int(a ^ b))
+        }
+        Expr::BitNot(expr) => match evaluate(expr, env)? {
+            Value::Int(a) => Ok(Value::Int(!a)),
+            other => Err(format!("Cannot apply ~ to {} - must be int", other.type_name())),
+        },
+        Expr::Shl(left, right) | Expr::Shr(left, right) => {
+            let op = if matches!(expr, Expr::Shl(..)) { "<<" } else { ">>" };
+            let (a, b) = int_operands(evaluate(left, env)?, evaluate(right, env)?, op)?;
+            let shift = u32::try_from(b)
+                .ok()
+                .filter(|shift| *shift < 64)
+                .ok_or_else(|| format!("Shift amount {} must be between 0 and 63", b))?;
+            if op == "<<" {
+                Ok(Value::Int(a << shift))
+            } else {
+                Ok(Value::Int(a >> shift))
+            }
+        }
+        Expr::FunctionCall(name, args) if name == "coalesce" => {
+            // Undefined variables count as null here instead of failing the whole call
+            let mut values = Vec::new();
+            for arg in args {
+                match arg {
+                    Expr::Variable(var) if env.get_value(var).is_none() => values.push(Value::Null),
+                    _ => values.push(evaluate(arg, env)?),
+                }
+            }
+            Ok(builtins::coalesce(&values))
+        }
         Expr::FunctionCall(name, args) => {
             let evaluated_args: Result<Vec<Value>, String> = 
                 args.iter().map(|arg| evaluate(arg, env)).collect();
@@ -1007,6 +1218,14 @@ pub fn evaluate(expr: &Expr, env: &Env) -> Result<Value, String> {
                     builtins::count(value, pattern)
                 }
                 "now" if args_values.is_empty() => Ok(builtins::now()),
+                "uuid" if args_values.is_empty() => Ok(builtins::uuid()),
+                "timestamp" if args_values.is_empty() => Ok(builtins::timestamp()),
+                "parse_date" if args_values.len() == 2 => {
+                    builtins::parse_date(&args_values[0], &args_values[1])
+                }
+                "format_date" if args_values.len() == 2 => {
+                    builtins::format_date(&args_values[0], &args_values[1])
+                }
                 "len" if args_values.len() == 1 => {
                     builtins::len(&args_values[0])
                 }
@@ -1101,6 +1320,42 @@ pub fn evaluate(expr: &Expr, env: &Env) -> Result<Value, String> {
                 "sort" if args_values.len() == 2 => {
                     builtins::sort_with_direction(&args_values[0], &args_values[1])
                 }
+                "sort_by" if args_values.len() == 2 => {
+                    builtins::sort_by(&args_values[0], &args_values[1])
+                }
+                "group_by" if args_values.len() == 2 => {
+                    builtins::group_by(&args_values[0], &args_values[1])
+                }
+                "zip" if args_values.len() == 2 || args_values.len() == 3 => {
+                    builtins::zip(&args_values)
+                }
+                "index_of" if args_values.len() == 2 => {
+                    builtins::index_of(&args_values[0], &args_values[1])
+                }
+                "reverse" if args_values.len() == 1 => {
+                    builtins::reverse(&args_values[0])
+                }
+                "chunk" if args_values.len() == 2 => {
+                    builtins::chunk(&args_values[0], &args_values[1])
+                }
+                "lines" if args_values.len() == 1 => {
+                    builtins::lines(&args_values[0])
+                }
+                "words" if args_values.len() == 1 => {
+                    builtins::words(&args_values[0])
+                }
+                "chars" if args_values.len() == 1 => {
+                    builtins::chars(&args_values[0])
+                }
+                "repeat" if args_values.len() == 2 => {
+                    builtins::repeat(&args_values[0], &args_values[1])
+                }
+                "pad_left" if args_values.len() == 2 || args_values.len() == 3 => {
+                    builtins::pad_left(&args_values[0], &args_values[1], args_values.get(2))
+                }
+                "pad_right" if args_values.len() == 2 || args_values.len() == 3 => {
+                    builtins::pad_right(&args_values[0], &args_values[1], args_values.get(2))
+                }
                 
                 // Index-based string functions
                 "char_at" if args_values.len() == 2 => {
@@ -1115,6 +1370,143 @@ pub fn evaluate(expr: &Expr, env: &Env) -> Result<Value, String> {
                 "replace_at" if args_values.len() == 4 => {
                     builtins::replace_at(&args_values[0], &args_values[1], &args_values[2], &args_values[3])
                 }
+
+                // Numeric folds
+                "sum" if args_values.len() == 1 => {
+                    builtins::sum(&args_values[0])
+                }
+                "min" if args_values.len() == 1 => {
+                    builtins::min(&args_values[0])
+                }
+                "max" if args_values.len() == 1 => {
+                    builtins::max(&args_values[0])
+                }
+                "min" if args_values.len() == 2 => {
+                    builtins::min(&Value::List(args_values.clone()))
+                }
+                "max" if args_values.len() == 2 => {
+                    builtins::max(&Value::List(args_values.clone()))
+                }
+
+                // Rounding and sign
+                "abs" if args_values.len() == 1 => {
+                    builtins::abs(&args_values[0])
+                }
+                "floor" if args_values.len() == 1 => {
+                    builtins::floor(&args_values[0])
+                }
+                "ceil" if args_values.len() == 1 => {
+                    builtins::ceil(&args_values[0])
+                }
+                "round" if args_values.len() == 1 => {
+                    builtins::round(&args_values[0])
+                }
+                "sqrt" if args_values.len() == 1 => {
+                    builtins::sqrt(&args_values[0])
+                }
+                "pow" if args_values.len() == 2 => {
+                    builtins::pow(&args_values[0], &args_values[1])
+                }
+
+                // Conversions
+                "to_int" if args_values.len() == 1 => {
+                    builtins::to_int(&args_values[0])
+                }
+                "to_float" if args_values.len() == 1 => {
+                    builtins::to_float(&args_values[0])
+                }
+                "to_bool" if args_values.len() == 1 => {
+                    builtins::to_bool(&args_values[0])
+                }
+                "type_of" if args_values.len() == 1 => {
+                    builtins::type_of(&args_values[0])
+                }
+                "hash" if args_values.len() == 2 => {
+                    builtins::hash(&args_values[0], &args_values[1])
+                }
+                "encode64" if args_values.len() == 1 => {
+                    builtins::encode64(&args_values[0])
+                }
+                "decode64" if args_values.len() == 1 => {
+                    builtins::decode64(&args_values[0])
+                }
+                "is_defined" if args_values.len() == 1 => {
+                    builtins::is_defined(&args_values[0], env)
+                }
+                "env_var" if args_values.len() == 1 || args_values.len() == 2 => {
+                    builtins::env_var(&args_values[0], args_values.get(1))
+                }
+                "exec_shell" if args_values.len() == 1 => {
+                    builtins::exec_shell(&args_values[0])
+                }
+                "parse_yaml" if args_values.len() == 1 => match &args_values[0] {
+                    Value::Str(yaml_str) => builtins::parse_yaml(yaml_str),
+                    other => Err(format!("parse_yaml() requires string, got {}", other.type_name())),
+                },
+                "to_yaml" if args_values.len() == 1 => {
+                    builtins::to_yaml(&args_values[0]).map(Value::Str)
+                }
+                "parse_csv" if args_values.len() == 1 || args_values.len() == 2 => {
+                    let headers = match args_values.get(1) {
+                        None => true,
+                        Some(Value::Bool(headers)) => *headers,
+                        Some(other) => return Err(format!("parse_csv() headers flag must be a bool, got {}", other.type_name())),
+                    };
+                    match &args_values[0] {
+                        Value::Str(csv_str) => builtins::parse_csv(csv_str, headers),
+                        other => Err(format!("parse_csv() requires string, got {}", other.type_name())),
+                    }
+                }
+                "to_csv" if args_values.len() == 1 => {
+                    builtins::to_csv(&args_values[0]).map(Value::Str)
+                }
+                "http_get" if args_values.len() == 1 || args_values.len() == 2 => {
+                    builtins::http_get(&args_values[0], args_values.get(1))
+                }
+                "http_post" if args_values.len() == 2 || args_values.len() == 3 => {
+                    builtins::http_post(&args_values[0], &args_values[1], args_values.get(2))
+                }
+
+                // Sequences
+                "range" if args_values.len() == 2 => {
+                    builtins::range(&args_values[0], &args_values[1], None)
+                }
+                "range" if args_values.len() == 3 => {
+                    builtins::range(&args_values[0], &args_values[1], Some(&args_values[2]))
+                }
+                "enumerate" if args_values.len() == 1 => {
+                    builtins::enumerate(&args_values[0])
+                }
+                "first" if args_values.len() == 1 => {
+                    builtins::first(&args_values[0])
+                }
+                "last" if args_values.len() == 1 => {
+                    builtins::last(&args_values[0])
+                }
+                "nth" if args_values.len() == 2 => {
+                    builtins::nth(&args_values[0], &args_values[1])
+                }
+                "unique" if args_values.len() == 1 => {
+                    builtins::unique(&args_values[0])
+                }
+                "flatten" if args_values.len() == 1 => {
+                    builtins::flatten(&args_values[0], None)
+                }
+                "flatten" if args_values.len() == 2 => {
+                    let depth = match &args_values[1] {
+                        Value::Int(d) if *d >= 0 => *d as usize,
+                        other => return Err(format!("flatten() depth must be a non-negative int, got {}", other.display())),
+                    };
+                    builtins::flatten(&args_values[0], Some(depth))
+                }
+
+                // Predicates
+                "any" if args_values.len() == 1 => {
+                    builtins::any(&args_values[0])
+                }
+                "all" if args_values.len() == 1 => {
+                    builtins::all(&args_values[0])
+                }
                 _ => Err(format!("Unknown function or wrong arity: {}/{}", name, args.len())),
             }
         }
@@ -1278,6 +1670,18 @@ pub fn evaluate(expr: &Expr, env: &Env) -> Result<Value, String> {
                 (value, "to_json") => {
                     crate::core::builtins::to_json(&value).map(|s| Value::Str(s))
                 },
+                (Value::Str(yaml_str), "parse_yaml") => {
+                    crate::core::builtins::parse_yaml(&yaml_str)
+                },
+                (value, "to_yaml") => {
+                    crate::core::builtins::to_yaml(&value).map(Value::Str)
+                },
+                (Value::Str(csv_str), "parse_csv") => {
+                    crate::core::builtins::parse_csv(&csv_str, true)
+                },
+                (value, "to_csv") => {
+                    crate::core::builtins::to_csv(&value).map(Value::Str)
+                },
                 
                 // JSON Path methods
                 (Value::Dict(_) | Value::Json(_), "get") if !evaluated_args.is_empty() => {
@@ -1406,6 +1810,12 @@ pub fn evaluate(expr: &Expr, env: &Env) -> Result<Value, String> {
     }
 }
 
+fn int_operands(left: Value, right: Value, op: &str) -> Result<(i64, i64), String> {
+    match (&left, &right) {
+        (Value::Int(a), Value::Int(b)) => Ok((*a, *b)),
+        _ => Err(format!("Cannot apply {} to {} and {} - must be int", op, left.type_name(), right.type_name())),
+    }
+}
 
 pub fn extract_variables(expr: &Expr) -> Vec<String> {
     let mut vars = HashSet::new();
@@ -1421,7 +1831,15 @@ fn extract_variables_recursive(expr: &Expr, vars: &mut HashSet<String>) {
         Expr::Add(left, right)
         | Expr::Subtract(left, right)
         | Expr::Multiply(left, right)
-        | Expr::Divide(left, right) => {
+        | Expr::Divide(left, right)
+        | Expr::IntDivide(left, right)
+        | Expr::Modulo(left, right)
+        | Expr::Power(left, right)
+        | Expr::BitAnd(left, right)
+        | Expr::BitOr(left, right)
+        | Expr::BitXor(left, right)
+        | Expr::Shl(left, right)
+        | Expr::Shr(left, right) => {
             extract_variables_recursive(left, vars);
             extract_variables_recursive(right, vars);
         }
@@ -1436,7 +1854,7 @@ fn extract_variables_recursive(expr: &Expr, vars: &mut HashSet<String>) {
             extract_variables_recursive(left, vars);
             extract_variables_recursive(right, vars);
         }
-        Expr::Not(expr) => {
+        Expr::Not(expr) | Expr::BitNot(expr) => {
             extract_variables_recursive(expr, vars);
         }
         Expr::FunctionCall(_, args) => {
@@ -1493,6 +1911,37 @@ fn extract_variables_recursive(expr: &Expr, vars: &mut HashSet<String>) {
     Err("Invalid index access".to_string())
 }*/
 
+// Replace whole-word uses of a variable name in expression source, leaving
+// string literals and field accesses (`obj.name`) alone
+pub fn rename_identifier(source: &str, old: &str, new: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut result = String::new();
+    let mut in_quotes = false;
+    let mut i = 0;
+    
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == '"' && (i == 0 || chars[i - 1] != '\\') {
+            in_quotes = !in_quotes;
+        }
+        if in_quotes || !is_ident(ch) || (i > 0 && (is_ident(chars[i - 1]) || chars[i - 1] == '.')) {
+            result.push(ch);
+            i += 1;
+            continue;
+        }
+        
+        let start = i;
+        while i < chars.len() && is_ident(chars[i]) {
+            i += 1;
+        }
+        let word: String = chars[start..i].iter().collect();
+        result.push_str(if word == old { new } else { &word });
+    }
+    
+    result
+}
+
 fn parse_dict_from_cleaned(token: &str) -> Expr {
     if !(token.starts_with('{') && token.ends_with('}')) {
         return Expr::Variable(token.to_string());
@@ -2277,13 +2726,442 @@ pub fn parse_propagation_suffix(input: &str) -> Result<(String, usize, usize), S
             } else {
                 return Err(format!("Invalid number after ~-: {}", delay_str));
             }
-        } else {
-            return Err(format!("Invalid propagation control syntax: {}", control_part));
         }
+        // Anything else is the bitwise complement operator, e.g. `set mask = ~flags`
     }
     
     // No propagation control syntax found
     let result = (input.to_string(), 0, usize::MAX);
      
     Ok(result) // No delay, no limit
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coalesce_treats_undefined_as_null() {
+        let mut env = Env::new();
+        env.set_direct("username", Value::Str("ann".to_string()));
+        let expr = parse_expression("coalesce(nickname, username, \"anonymous\")").unwrap();
+        assert_eq!(evaluate(&expr, &env).unwrap(), Value::Str("ann".to_string()));
+    }
+
+    fn eval(input: &str, env: &Env) -> Value {
+        evaluate(&parse_expression(input).unwrap(), env).unwrap()
+    }
+
+    fn eval_err(input: &str, env: &Env) -> String {
+        evaluate(&parse_expression(input).unwrap(), env).unwrap_err()
+    }
+
+    #[test]
+    fn test_modulo() {
+        let env = Env::new();
+        assert_eq!(eval("7 % 3", &env), Value::Int(1));
+        assert_eq!(eval("-7 % 3", &env), Value::Int(-1));
+        assert_eq!(eval("7.5 % 2", &env), Value::Float(1.5));
+        assert_eq!(eval_err("7 % 0", &env), "Modulo by zero");
+        assert_eq!(eval_err("7.0 % 0", &env), "Modulo by zero");
+    }
+
+    #[test]
+    fn test_power() {
+        let env = Env::new();
+        assert_eq!(eval("2 ** 10", &env), Value::Float(1024.0));
+        assert_eq!(eval("2 ** -1", &env), Value::Float(0.5));
+        // Right-associative: 2 ** (3 ** 2), not (2 ** 3) ** 2
+        assert_eq!(eval("2 ** 3 ** 2", &env), Value::Float(512.0));
+    }
+
+    #[test]
+    fn test_int_divide() {
+        let mut env = Env::new();
+        env.set_direct("n", Value::Int(-7));
+        assert_eq!(eval("7 // 2", &env), Value::Int(3));
+        assert_eq!(eval("n // 2", &env), Value::Int(-4));
+        assert_eq!(eval("-7 // 2", &env), Value::Int(-4));
+        assert_eq!(eval("7.5 // 2", &env), Value::Int(3));
+        assert_eq!(eval_err("7 // 0", &env), "Division by zero");
+    }
+
+    #[test]
+    fn test_bitwise() {
+        let env = Env::new();
+        assert_eq!(eval("6 & 3", &env), Value::Int(2));
+        assert_eq!(eval("6 | 3", &env), Value::Int(7));
+        assert_eq!(eval("6 ^ 3", &env), Value::Int(5));
+        assert_eq!(eval("~5", &env), Value::Int(-6));
+        assert_eq!(eval("1 << 4", &env), Value::Int(16));
+        assert_eq!(eval("256 >> 2", &env), Value::Int(64));
+        assert_eq!(eval_err("1 << 64", &env), "Shift amount 64 must be between 0 and 63");
+        assert!(eval_err("1 << -1", &env).starts_with("Shift amount -1"));
+        assert!(eval_err("1.5 & 1", &env).starts_with("Cannot apply & to float and int"));
+    }
+
+    #[test]
+    fn test_negative_literal() {
+        let env = Env::new();
+        assert_eq!(eval("-5", &env), Value::Int(-5));
+        assert_eq!(eval("-5 + 8", &env), Value::Int(3));
+    }
+
+    #[test]
+    fn test_parenthesized_negative() {
+        let env = Env::new();
+        assert_eq!(eval("(-5)", &env), Value::Int(-5));
+        assert_eq!(eval("2 * (-5)", &env), Value::Int(-10));
+    }
+
+    #[test]
+    fn test_subtract_negative() {
+        let env = Env::new();
+        assert_eq!(eval("0 - -5", &env), Value::Int(5));
+    }
+
+    #[test]
+    fn test_multiply_by_negative() {
+        let mut env = Env::new();
+        env.set_direct("x", Value::Int(7));
+        assert_eq!(eval("x * -1", &env), Value::Int(-7));
+        assert_eq!(eval("-x + 10", &env), Value::Int(3));
+    }
+
+    #[test]
+    fn test_operator_precedence() {
+        let env = Env::new();
+        assert_eq!(eval("1 + 2 * 3 ** 2", &env), Value::Float(19.0));
+        assert_eq!(eval("10 - 4 % 3", &env), Value::Int(9));
+        assert_eq!(eval("2 * 3 // 4", &env), Value::Int(1));
+    }
+
+    #[test]
+    fn test_sum() {
+        let env = Env::new();
+        assert_eq!(eval("sum([1, 2, 3])", &env), Value::Int(6));
+        assert_eq!(eval("sum([1, 2.5])", &env), Value::Float(3.5));
+        assert_eq!(eval("sum([])", &env), Value::Int(0));
+        assert_eq!(eval_err("sum([1, \"a\"])", &env), "sum() requires numeric list, found string");
+    }
+
+    #[test]
+    fn test_min_max() {
+        let env = Env::new();
+        assert_eq!(eval("min([3, 1, 2])", &env), Value::Int(1));
+        assert_eq!(eval("max([3, 1, 2])", &env), Value::Int(3));
+        assert_eq!(eval("min(4, 9)", &env), Value::Int(4));
+        assert_eq!(eval("max(4, 9)", &env), Value::Int(9));
+        assert_eq!(eval_err("min([])", &env), "min() requires a non-empty list");
+    }
+
+    #[test]
+    fn test_abs_and_rounding() {
+        let env = Env::new();
+        assert_eq!(eval("abs(-3)", &env), Value::Int(3));
+        assert_eq!(eval("abs(-2.5)", &env), Value::Float(2.5));
+        assert_eq!(eval("floor(2.7)", &env), Value::Int(2));
+        assert_eq!(eval("ceil(2.1)", &env), Value::Int(3));
+        assert_eq!(eval("round(2.5)", &env), Value::Int(3));
+        assert_eq!(eval("round(-2.5)", &env), Value::Int(-3));
+        assert!(evaluate(&parse_expression("floor(\"a\")").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn test_sqrt_pow() {
+        let env = Env::new();
+        assert_eq!(eval("sqrt(16)", &env), Value::Float(4.0));
+        assert_eq!(eval_err("sqrt(-1)", &env), "sqrt of negative number");
+        assert_eq!(eval("pow(2, 8)", &env), Value::Float(256.0));
+        assert_eq!(eval("pow(2, -1)", &env), Value::Float(0.5));
+    }
+
+    fn ints(values: &[i64]) -> Value {
+        Value::List(values.iter().map(|v| Value::Int(*v)).collect())
+    }
+
+    #[test]
+    fn test_range() {
+        let env = Env::new();
+        assert_eq!(eval("range(0, 5)", &env), ints(&[0, 1, 2, 3, 4]));
+        assert_eq!(eval("range(5, 0, -2)", &env), ints(&[5, 3, 1]));
+        assert_eq!(eval_err("range(0, 5, 0)", &env), "range() step cannot be zero");
+        assert_eq!(eval_err("range(0, 200000)", &env), "range() would produce more than 100000 elements");
+    }
+
+    #[test]
+    fn test_enumerate() {
+        let env = Env::new();
+        assert_eq!(
+            eval("enumerate([\"a\", \"b\"])", &env),
+            Value::List(vec![
+                Value::List(vec![Value::Int(0), Value::Str("a".to_string())]),
+                Value::List(vec![Value::Int(1), Value::Str("b".to_string())]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_any_all() {
+        let env = Env::new();
+        assert_eq!(eval("any([])", &env), Value::Bool(false));
+        assert_eq!(eval("all([])", &env), Value::Bool(true));
+        assert_eq!(eval("any([1, 0])", &env), Value::Bool(true));
+        assert_eq!(eval("all([true, false])", &env), Value::Bool(false));
+    }
+
+    #[test]
+    fn test_first_last_nth() {
+        let env = Env::new();
+        assert_eq!(eval("first([1, 2])", &env), Value::Int(1));
+        assert_eq!(eval("last([1, 2])", &env), Value::Int(2));
+        assert_eq!(eval("nth([1, 2, 3], 1)", &env), Value::Int(2));
+        assert_eq!(eval("nth([1, 2, 3], -1)", &env), Value::Int(3));
+        assert_eq!(eval_err("nth([1], 5)", &env), "Index 5 out of bounds for list of length 1");
+        assert_eq!(eval_err("first([])", &env), "first() of empty list");
+    }
+
+    #[test]
+    fn test_flatten() {
+        let env = Env::new();
+        assert_eq!(eval("flatten([1, [2, [3, [4]]]])", &env), ints(&[1, 2, 3, 4]));
+        assert_eq!(
+            eval("flatten([1, [2, [3]]], 1)", &env),
+            Value::List(vec![Value::Int(1), Value::Int(2), ints(&[3])])
+        );
+    }
+
+    #[test]
+    fn test_unique() {
+        let env = Env::new();
+        assert_eq!(eval("unique([1, 2, 1, 3, 2])", &env), ints(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_sort_by() {
+        let env = Env::new();
+        assert_eq!(
+            eval("sort_by([{\"n\": 3}, {\"n\": 1}, {\"n\": 2}], \"n\")", &env).to_string(),
+            eval("[{\"n\": 1}, {\"n\": 2}, {\"n\": 3}]", &env).to_string()
+        );
+    }
+
+    #[test]
+    fn test_group_by() {
+        let env = Env::new();
+        let groups = eval("group_by([{\"k\": \"a\", \"v\": 1}, {\"k\": \"b\", \"v\": 2}, {\"k\": \"a\", \"v\": 3}], \"k\")", &env);
+        match groups {
+            Value::Dict(map) => {
+                assert_eq!(map.len(), 2);
+                assert!(matches!(map.get("a"), Some(Value::List(items)) if items.len() == 2));
+                assert!(matches!(map.get("b"), Some(Value::List(items)) if items.len() == 1));
+            }
+            other => panic!("group_by returned {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_zip() {
+        let env = Env::new();
+        assert_eq!(
+            eval("zip([1, 2, 3], [\"a\", \"b\"])", &env),
+            Value::List(vec![
+                Value::List(vec![Value::Int(1), Value::Str("a".to_string())]),
+                Value::List(vec![Value::Int(2), Value::Str("b".to_string())]),
+            ])
+        );
+        assert_eq!(eval("zip([1], [2], [3])", &env), Value::List(vec![ints(&[1, 2, 3])]));
+    }
+
+    #[test]
+    fn test_index_of_reverse() {
+        let env = Env::new();
+        assert_eq!(eval("index_of([1, 2, 3], 2)", &env), Value::Int(1));
+        assert_eq!(eval("index_of([1], 9)", &env), Value::Int(-1));
+        assert_eq!(eval("reverse([1, 2, 3])", &env), ints(&[3, 2, 1]));
+        assert_eq!(eval("reverse(\"abc\")", &env), Value::Str("cba".to_string()));
+    }
+
+    #[test]
+    fn test_chunk() {
+        let env = Env::new();
+        assert_eq!(
+            eval("chunk([1, 2, 3, 4, 5], 2)", &env),
+            Value::List(vec![ints(&[1, 2]), ints(&[3, 4]), ints(&[5])])
+        );
+        assert_eq!(eval_err("chunk([1], 0)", &env), "chunk() size must be positive, got 0");
+    }
+
+    #[test]
+    fn test_pad_left_right() {
+        let env = Env::new();
+        assert_eq!(eval("pad_left(\"7\", 3, \"0\")", &env), Value::Str("007".to_string()));
+        assert_eq!(eval("pad_right(\"ab\", 4)", &env), Value::Str("ab  ".to_string()));
+        assert_eq!(eval("pad_left(\"abcd\", 2)", &env), Value::Str("abcd".to_string()));
+        assert!(evaluate(&parse_expression("pad_left(\"a\", 3, \"xy\")").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn test_repeat() {
+        let env = Env::new();
+        assert_eq!(eval("repeat(\"ab\", 3)", &env), Value::Str("ababab".to_string()));
+        assert!(evaluate(&parse_expression("repeat(\"a\", -1)").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn test_conversion_builtins() {
+        let env = Env::new();
+        assert_eq!(eval("to_int(\"42\")", &env), Value::Int(42));
+        assert_eq!(eval("to_int(3.9)", &env), Value::Int(3));
+        assert!(evaluate(&parse_expression("to_int(\"x\")").unwrap(), &env).is_err());
+        assert_eq!(eval("to_float(\"2.5\")", &env), Value::Float(2.5));
+        assert_eq!(eval("to_float(3)", &env), Value::Float(3.0));
+        assert_eq!(eval("to_bool(\"yes\")", &env), Value::Bool(true));
+        assert_eq!(eval("to_bool(0)", &env), Value::Bool(false));
+    }
+
+    #[test]
+    fn test_type_of() {
+        let env = Env::new();
+        for (input, expected) in [
+            ("type_of(1)", "int"),
+            ("type_of(1.5)", "float"),
+            ("type_of(\"a\")", "string"),
+            ("type_of([1])", "list"),
+            ("type_of({\"a\": 1})", "dict"),
+            ("type_of(true)", "bool"),
+        ] {
+            assert_eq!(eval(input, &env), Value::Str(expected.to_string()), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_is_defined() {
+        let mut env = Env::new();
+        env.set_direct("x", Value::Int(7));
+        assert_eq!(eval("is_defined(\"x\")", &env), Value::Bool(true));
+        assert_eq!(eval("is_defined(\"nope\")", &env), Value::Bool(false));
+    }
+
+    #[test]
+    fn test_uuid() {
+        let env = Env::new();
+        assert_eq!(eval("len(uuid())", &env), Value::Int(36));
+        assert_ne!(eval("uuid()", &env), eval("uuid()", &env));
+    }
+
+    #[test]
+    fn test_hash() {
+        let env = Env::new();
+        assert_eq!(
+            eval("hash(\"abc\", \"sha256\")", &env),
+            Value::Str("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string())
+        );
+        assert_eq!(eval("hash(\"abc\", \"md5\")", &env), Value::Str("900150983cd24fb0d6963f7d28e17f72".to_string()));
+        assert!(evaluate(&parse_expression("hash(\"abc\", \"nope\")").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn test_encode64_decode64() {
+        let env = Env::new();
+        assert_eq!(eval("encode64(\"hello\")", &env), Value::Str("aGVsbG8=".to_string()));
+        assert_eq!(eval("decode64(encode64(\"hello\"))", &env), Value::Str("hello".to_string()));
+        assert!(evaluate(&parse_expression("decode64(\"!!!\")").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn test_dates() {
+        let env = Env::new();
+        assert_eq!(eval("type_of(timestamp())", &env), Value::Str("int".to_string()));
+        assert_eq!(eval("parse_date(\"2024-03-01\", \"%Y-%m-%d\")", &env), Value::Int(1709251200));
+        assert_eq!(
+            eval("format_date(parse_date(\"2024-03-01\", \"%Y-%m-%d\"), \"%d.%m.%Y\")", &env),
+            Value::Str("01.03.2024".to_string())
+        );
+        assert!(evaluate(&parse_expression("parse_date(\"x\", \"%Y\")").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn test_env_var_default() {
+        let env = Env::new();
+        assert_eq!(eval("env_var(\"MORRIS_TEST_UNSET_VAR\")", &env), Value::Str(String::new()));
+        assert_eq!(eval("env_var(\"MORRIS_TEST_UNSET_VAR\", \"d\")", &env), Value::Str("d".to_string()));
+    }
+
+    #[test]
+    fn test_exec_shell() {
+        let env = Env::new();
+        assert_eq!(eval("exec_shell(\"echo hi\")", &env), Value::Str("hi".to_string()));
+        assert!(eval_err("exec_shell(\"echo oops >&2; exit 3\")", &env).starts_with("exec_shell() exited with 3: oops"));
+        assert!(evaluate(&parse_expression("exec_shell(1)").unwrap(), &env).is_err());
+    }
+
+    fn strs(values: &[&str]) -> Value {
+        Value::List(values.iter().map(|v| Value::Str(v.to_string())).collect())
+    }
+
+    #[test]
+    fn test_lines_words_chars() {
+        let mut env = Env::new();
+        env.set_direct("s", Value::Str("a b\nc d".to_string()));
+        assert_eq!(eval("lines(s)", &env), strs(&["a b", "c d"]));
+        assert_eq!(eval("words(s)", &env), strs(&["a", "b", "c", "d"]));
+        assert_eq!(eval("chars(\"abc\")", &env), strs(&["a", "b", "c"]));
+    }
+
+    #[test]
+    fn test_null() {
+        let mut env = Env::new();
+        env.set_direct("x", Value::Int(7));
+        assert_eq!(eval("null", &env), Value::Null);
+        assert_eq!(eval("null == null", &env), Value::Bool(true));
+        assert_eq!(eval("null == 0", &env), Value::Bool(false));
+        assert_eq!(eval("type_of(null)", &env), Value::Str("null".to_string()));
+        assert!(evaluate(&parse_expression("x + null").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn test_comparison_binds_looser_than_operators() {
+        let env = Env::new();
+        assert_eq!(eval("1 & 3 == 1", &env), Value::Bool(true));
+        assert_eq!(eval("2 + 2 == 4", &env), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_rename_identifier() {
+        assert_eq!(rename_identifier("price * qty", "price", "cost"), "cost * qty");
+        assert_eq!(rename_identifier("prices + price", "price", "cost"), "prices + cost");
+        assert_eq!(rename_identifier("\"price\" + price", "price", "cost"), "\"price\" + cost");
+        assert_eq!(rename_identifier("item.price", "price", "cost"), "item.price");
+    }
+
+    #[test]
+    fn test_http_requires_allow_http() {
+        let env = Env::new();
+        assert!(eval_err("http_get(\"https://example.com/\")", &env).contains("--allow-http"));
+        assert!(eval_err("http_post(\"https://example.com/\", [1, 2])", &env).contains("--allow-http"));
+        assert!(eval_err("http_get(1)", &env).contains("requires a URL string"));
+        assert!(eval_err("http_get(\"https://example.com/\", 0)", &env).contains("timeout must be a positive number"));
+    }
+
+    #[test]
+    fn test_yaml_functions() {
+        let mut env = Env::new();
+        env.set_direct("text", Value::Str("name: web\nports: [80, 443]".to_string()));
+        assert!(matches!(eval("parse_yaml(text)", &env), Value::Dict(map) if map.len() == 2));
+        env.set_direct("ports", Value::List(vec![Value::Int(80), Value::Int(443)]));
+        assert_eq!(eval("to_yaml(ports)", &env), Value::Str("- 80\n- 443\n".to_string()));
+        assert_eq!(eval("parse_yaml(ports.to_yaml())", &env), eval("ports", &env));
+        assert!(eval_err("parse_yaml(1)", &env).contains("requires string"));
+    }
+
+    #[test]
+    fn test_csv_functions() {
+        let mut env = Env::new();
+        env.set_direct("text", Value::Str("a,b\n1,2".to_string()));
+        assert!(matches!(eval("parse_csv(text)", &env), Value::List(rows) if rows.len() == 1));
+        assert!(matches!(eval("parse_csv(text, false)", &env), Value::List(rows) if rows.len() == 2));
+        assert_eq!(eval("to_csv(text.parse_csv())", &env), Value::Str("a,b\n1,2\n".to_string()));
+        assert!(eval_err("parse_csv(text, 1)", &env).contains("must be a bool"));
+    }
+}
@@ -0,0 +1,111 @@
+// File: src/core/feedback.rs
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+
+// One JSON object per line in ~/.morris_feedback.log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackEntry {
+    pub timestamp: String,
+    pub version: String,
+    pub message: String,
+}
+
+pub struct FeedbackLog {
+    path: PathBuf,
+}
+
+impl FeedbackLog {
+    pub fn new() -> Self {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        Self { path: home.join(".morris_feedback.log") }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn append(&self, message: &str) -> Result<FeedbackEntry, String> {
+        let entry = FeedbackEntry {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            message: message.to_string(),
+        };
+
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| format!("Failed to encode feedback: {}", e))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Cannot open feedback log '{}': {}", self.path.display(), e))?;
+
+        writeln!(file, "{}", line)
+            .map_err(|e| format!("Cannot write feedback log '{}': {}", self.path.display(), e))?;
+
+        Ok(entry)
+    }
+
+    // Lines that fail to parse are skipped rather than failing the whole log
+    pub fn entries(&self) -> Vec<FeedbackEntry> {
+        fs::read_to_string(&self.path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+}
+
+impl Default for FeedbackLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Endpoint from the project config in the current directory:
+//   [feedback]
+//   url = "https://example.com/morris/feedback"
+pub fn configured_feedback_url() -> Option<String> {
+    crate::core::project_config::get_str("feedback", "url")
+}
+
+// A dead endpoint must not hang the REPL
+const SEND_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const SEND_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub fn send_feedback(url: &str, entries: &[FeedbackEntry]) -> Result<(), String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(SEND_CONNECT_TIMEOUT)
+        .timeout_read(SEND_READ_TIMEOUT)
+        .build();
+    agent.post(url)
+        .send_json(serde_json::json!({ "feedback": entries }))
+        .map(|_| ())
+        .map_err(|e| format!("Failed to send feedback to '{}': {}", url, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_and_read_entries() {
+        let path = std::env::temp_dir().join(format!("morris_feedback_{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let log = FeedbackLog { path: path.clone() };
+        assert!(log.entries().is_empty());
+
+        log.append("first idea").unwrap();
+        fs::write(&path, fs::read_to_string(&path).unwrap() + "not json\n").unwrap();
+        log.append("second idea").unwrap();
+
+        let entries = log.entries();
+        fs::remove_file(&path).unwrap();
+        let messages: Vec<&str> = entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["first idea", "second idea"]);
+        assert_eq!(entries[0].version, env!("CARGO_PKG_VERSION"));
+    }
+}
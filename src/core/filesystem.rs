@@ -24,6 +24,10 @@ pub struct EnvironmentFile {
 pub struct SavedVariable {
     pub value: Value,
     pub is_constant: bool,
+    #[serde(default)]
+    pub is_readonly: bool,
+    #[serde(default)]
+    pub is_final: bool,
     pub source: VariableSource,
 }
 
@@ -92,6 +96,12 @@ impl FileSystem {
             if saved_var.is_constant {
                 env.freeze(name).ok(); // Ignore errors for now
             }
+            if saved_var.is_readonly {
+                env.mark_readonly(name).ok();
+            }
+            if saved_var.is_final {
+                env.mark_final(name).ok();
+            }
         }
         
         let var_count = file.variables.len();
@@ -236,6 +246,8 @@ impl FileSystem {
                 let saved_var = SavedVariable {
                     value: var.value.clone(),
                     is_constant: var.is_constant,
+                    is_readonly: var.is_readonly,
+                    is_final: var.is_final,
                     source: var.source.clone(),
                 };
                 variables.insert(name.clone(), saved_var);
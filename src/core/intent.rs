@@ -18,6 +18,9 @@ pub enum Verb {
     Freeze,
     Load,
     Save,
+    Snapshot,    // snapshot "file.msnap"
+    Restore,     // restore "file.msnap" [--replace]
+    DiffEnv,     // diff-env "before.msnap" "after.msnap"
     Read,
     Write,
     Append,
@@ -92,17 +95,81 @@ pub enum Verb {
     FromJson,
     JsonGet,    
     JsonSet,
+    ParseYaml,   // parse-yaml "yaml" | <var> [into <var>]
+    ToYaml,      // to-yaml <var>
+    ParseCsv,    // parse-csv "csv" | <var> [into <var>] [headers false]
+    ToCsv,       // to-csv <var>
 
     Examine,     // inspect intents, inspect variables, inspect engine
     Construct,   // construct intent name with params {expr}
     Evolve,      // evolve intent_name add_param name default="value"
-    Grow,        // grow new_intent from base_intent
+    Grow,        // grow "composition" with ["intent", ...]
     
     // Phase 2: Meta-programming  
     Reflect,        // meta eval "expression"
     Test,        // test intent_name with params
     Adopt,       // adopt intent_name (move to production)
 
+    // Project tooling
+    Manifest,    // manifest "project.mmf"
+
+    // Data tooling
+    Schema,      // schema "name" { field: type, ... }
+    Generate,    // generate "schema" count N into var
+    DiffDicts,   // diff dict_a and dict_b [into var]
+    Merge,       // merge dict_a and dict_b into dest [strategy=last_wins]
+    AssertSchema, // assert-schema rows against "schema" [report into var]
+    Summarize,   // summarize list [field key] [into var]
+    Pivot,       // pivot list row r col c val v into dest
+    Unpivot,     // unpivot var row r val v into dest
+    Join,        // join list_a and list_b on key [into dest]
+    LeftJoin,    // left-join list_a and list_b on key [into dest]
+    Window,      // window list size n aggregate sum|avg|max|min into dest
+    Interpolate, // interpolate list [method linear|forward|backward] [into dest]
+    Normalize,   // normalize list [method minmax|z-score|max] [into dest]
+    OneHot,      // one-hot list into dest
+    LabelEncode, // label-encode list into dest (mapping in dest_mapping)
+    Coalesce,    // coalesce [a, b, c] into dest
+    Filter,      // filter list where { item > 5 } [into dest]
+    Transform,   // transform|map list with { item * 2 } [into dest]
+    Reduce,      // reduce list with { acc + item } [starting_from 0] [into dest]
+    Sort,        // sort list [by "field"] [descending]
+
+    // Control flow
+    Switch,      // switch var { case "a" then <intent>; default <intent> }
+    Try,         // try { <intents> } catch { <intents> } finally { <intents> }
+    Raise,       // raise "error message"
+    Each,        // each item in list { <intents> }
+    Assert,      // assert <condition> [message "text"]
+    Benchmark,   // benchmark [verbose] { <intents> }
+    Capture,     // capture <var> { <intents> }
+    Redirect,    // redirect "file" [append] { <intents> }
+    Trace,       // trace on|off
+    Watch,       // watch x y z
+
+    // Script tooling
+    Log,         // log info|warning|error "message" [fields { key=value }] | log flush | log file "path"
+    Config,      // config log_file "path" | config
+    Notify,      // notify "message" [title "Morris"] | on-error notify "message"
+    EnvVar,      // env-var MORRIS_NAME into name
+    Export,      // export NAME [as OS_NAME] | export *
+    Completion,  // completions bash|zsh|fish
+    Man,         // man <verb>
+    Tutorial,    // tutorial | tutorial reset
+    Examples,    // examples <verb> | examples run <n>
+    Changelog,   // changelog [v2.0]
+    Feedback,    // feedback "message" | feedback show | feedback send
+    Alias,       // alias name = "expansion"
+    Unalias,     // unalias name
+    Aliases,     // aliases
+
+    // Variable management
+    Unset,       // unset <var>
+    Rename,      // rename <old> as <new> [--force]
+    Copy,        // copy <source> as <dest>
+    Swap,        // swap <a> <b>
+    Cast,        // cast <var> as int|bool|string
+
 }
 
 #[derive(Debug, Clone)]
@@ -146,6 +213,18 @@ pub struct Intent {
     // NEW: Safety metadata
     pub safety_level: SafetyLevel,
     pub allowed_operations: Vec<Operation>,
+    
+    // Earlier definitions replaced by `evolve`, oldest first
+    pub revisions: Vec<IntentRevision>,
+    
+    // Names of the intents a `composed_of` definition runs, in order
+    pub composition_steps: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IntentRevision {
+    pub replaced_at: DateTime<Utc>,
+    pub definition: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -197,6 +276,8 @@ impl Intent {
             integrity,
             safety_level: SafetyLevel::UserDefined,
             allowed_operations: vec![Operation::Read, Operation::Execute],
+            revisions: Vec::new(),
+            composition_steps: Vec::new(),
         };
         
         // Finalize the intent after construction
@@ -334,6 +415,35 @@ impl Intent {
         new_intent
     }
 
+    // `define intent` results, as opposed to intents to run
+    pub fn is_definition(&self) -> bool {
+        self.is_composition
+            && matches!(self.intent_source.as_deref(), Some("defined_intent") | Some("defined_composition"))
+    }
+    
+    // Human-readable form of a defined intent: (a, b="1") { body } or composed_of ["a", "b"]
+    pub fn definition_text(&self) -> String {
+        if self.intent_source.as_deref() == Some("defined_composition") {
+            let steps: Vec<String> = self.composition_steps.iter().map(|s| format!("\"{}\"", s)).collect();
+            return format!("composed_of [{}]", steps.join(", "));
+        }
+        
+        let mut params: Vec<String> = self.parameter_defs.iter()
+            .map(|(name, default)| if default.is_empty() {
+                name.clone()
+            } else {
+                format!("{}=\"{}\"", name, default)
+            })
+            .collect();
+        params.sort();
+        
+        let body = match &self.target {
+            Some(Target::Expression(expr)) => expr.clone(),
+            _ => String::new(),
+        };
+        format!("({}) {{ {} }}", params.join(", "), body)
+    }
+    
     pub fn finalize(&mut self) {
         let content = self.to_string_for_integrity();
         self.integrity.update_hash(&content);
@@ -876,20 +986,59 @@ fn parse_polish_intent(_input: &str) -> Result<Intent, String> {
     Ok(Intent::new(Verb::Polish))
 }
 
-fn parse_alloy_intent(_input: &str) -> Result<Intent, String> {
-    Ok(Intent::new(Verb::Alloy))
+fn parse_alloy_intent(input: &str) -> Result<Intent, String> {
+    // alloy <id1> <id2> - ids may be shortened or replaced by transaction names
+    let parts: Vec<&str> = input.trim_start_matches("alloy").split_whitespace().map(unquote).collect();
+    if parts.len() != 2 {
+        return Err("alloy requires two transaction ids: alloy <id1> <id2>".to_string());
+    }
+    
+    Ok(Intent::new(Verb::Alloy)
+        .with_parameter("first", parts[0])
+        .with_parameter("second", parts[1]))
 }
 
-fn parse_engrave_intent(_input: &str) -> Result<Intent, String> {
-    Ok(Intent::new(Verb::Engrave))
+fn parse_engrave_intent(input: &str) -> Result<Intent, String> {
+    // engrave ticket "PROJ-123"
+    let content = input.trim_start_matches("engrave").trim();
+    let (key, value) = content.split_once(char::is_whitespace)
+        .ok_or("engrave requires a key and a value: engrave <key> <value>")?;
+    
+    Ok(Intent::new(Verb::Engrave)
+        .with_parameter("key", key)
+        .with_parameter("value", unquote(value)))
 }
 
-fn parse_gild_intent(_input: &str) -> Result<Intent, String> {
-    Ok(Intent::new(Verb::Gild))
+fn parse_gild_intent(input: &str) -> Result<Intent, String> {
+    // gild rate budget owner
+    let names: Vec<&str> = input.trim_start_matches("gild")
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|name| !name.is_empty())
+        .collect();
+    if names.is_empty() {
+        return Err("gild requires at least one variable: gild <variables...>".to_string());
+    }
+    
+    Ok(Intent::new(Verb::Gild).with_parameter("variables", &names.join(",")))
 }
 
-fn parse_patina_intent(_input: &str) -> Result<Intent, String> {
-    Ok(Intent::new(Verb::Patina))
+fn parse_patina_intent(input: &str) -> Result<Intent, String> {
+    let name = input.trim_start_matches("patina").trim();
+    if name.is_empty() {
+        return Err("patina requires a variable: patina <variable>".to_string());
+    }
+    
+    Ok(Intent::new(Verb::Patina).with_target(Target::Variable(name.to_string())))
+}
+
+// Whether `word` starts an intent parse_to_intent understands, alone or with an
+// argument (the REPL's exit/quit included). Used to keep aliases from shadowing verbs
+pub fn is_verb_keyword(word: &str) -> bool {
+    let recognized = |input: &str| match parse_to_intent(input) {
+        Ok(_) => true,
+        Err(e) => !e.starts_with("Unknown intent"),
+    };
+    matches!(word, "exit" | "quit") || recognized(word) || recognized(&format!("{} x", word))
 }
 
 pub fn parse_to_intent(input: &str) -> Result<Intent, String> {
@@ -915,7 +1064,7 @@ pub fn parse_to_intent(input: &str) -> Result<Intent, String> {
             // Check if it's executing a defined intent
             parse_execute_intent(input)
         }
-        _ if input.starts_with("set ") => parse_set_intent(input),
+        _ if input.starts_with("set ") => parse_set_intent(&desugar_compound_assignment(input)?),
         _ if input.starts_with("ensure ") => parse_ensure_intent(input),
         _ if input.starts_with("writeout") => parse_writeout_intent(input),
         _ if input.starts_with("derive ") => parse_derive_intent(input),
@@ -925,6 +1074,9 @@ pub fn parse_to_intent(input: &str) -> Result<Intent, String> {
         _ if input.starts_with("freeze ") => parse_freeze_intent(input),
         _ if input.starts_with("load ") => parse_load_intent(input),
         _ if input.starts_with("save ") => parse_save_intent(input),
+        _ if input.starts_with("snapshot ") => parse_snapshot_intent(input, "snapshot", Verb::Snapshot),
+        _ if input.starts_with("restore ") => parse_snapshot_intent(input, "restore", Verb::Restore),
+        _ if input.starts_with("diff-env ") => parse_diff_env_intent(input),
         _ if input.starts_with("read ") => parse_read_intent(input),
         _ if input.starts_with("write ") => parse_write_intent(input),
         _ if input.starts_with("append ") => parse_append_intent(input),
@@ -981,10 +1133,10 @@ pub fn parse_to_intent(input: &str) -> Result<Intent, String> {
         
         // Transaction verbs (Phase 2 - placeholders)
         _ if input == "polish" => parse_polish_intent(input),
-        _ if input == "alloy" => parse_alloy_intent(input),
-        _ if input == "engrave" => parse_engrave_intent(input),
-        _ if input == "gild" => parse_gild_intent(input),
-        _ if input == "patina" => parse_patina_intent(input),
+        _ if input == "alloy" || input.starts_with("alloy ") => parse_alloy_intent(input),
+        _ if input == "engrave" || input.starts_with("engrave ") => parse_engrave_intent(input),
+        _ if input == "gild" || input.starts_with("gild ") => parse_gild_intent(input),
+        _ if input == "patina" || input.starts_with("patina ") => parse_patina_intent(input),
         _ if input.starts_with("what-if ") => parse_what_if_intent(input),
         _ if input.starts_with("parse_json ") => {
             let json_str = input.trim_start_matches("parse_json ").trim();
@@ -1002,6 +1154,11 @@ pub fn parse_to_intent(input: &str) -> Result<Intent, String> {
         _ if input.starts_with("to-json ") => parse_to_json_intent(input),
         _ if input.starts_with("from-json ") => parse_from_json_intent(input),
         _ if input.starts_with("json-get ") => parse_json_get_intent(input),
+        _ if input.starts_with("parse-yaml ") => parse_parse_yaml_intent(input),
+        _ if input.starts_with("to-yaml ") => parse_to_yaml_intent(input),
+        _ if input.starts_with("parse-csv ") => parse_parse_csv_intent(input),
+        _ if input.starts_with("to-csv ") => Ok(Intent::new(Verb::ToCsv)
+            .with_parameter("variable", input.trim_start_matches("to-csv ").trim())),
         _ if input.starts_with("json-set ") => parse_json_set_intent(input),
         
         _ if input.starts_with("examine ") => parse_examine_intent(input),
@@ -1013,6 +1170,72 @@ pub fn parse_to_intent(input: &str) -> Result<Intent, String> {
         _ if input.starts_with("reflect ") => parse_reflect_intent(input),
         _ if input.starts_with("test ") => parse_test_intent(input),
         _ if input.starts_with("adopt ") => parse_adopt_intent(input),
+        _ if input.starts_with("manifest ") => parse_manifest_intent(input),
+        _ if input.starts_with("schema ") => parse_schema_intent(input),
+        _ if input.starts_with("generate ") => parse_generate_intent(input),
+        _ if input.starts_with("diff ") => parse_diff_intent(input),
+        _ if input.starts_with("merge ") => parse_merge_intent(input),
+        _ if input.starts_with("assert-schema ") => parse_assert_schema_intent(input),
+        _ if input.starts_with("summarize ") => parse_summarize_intent(input),
+        _ if input.starts_with("pivot ") => parse_pivot_intent(input),
+        _ if input.starts_with("unpivot ") => parse_unpivot_intent(input),
+        _ if input.starts_with("join ") => parse_join_intent(input, "join ", Verb::Join),
+        _ if input.starts_with("left-join ") => parse_join_intent(input, "left-join ", Verb::LeftJoin),
+        _ if input.starts_with("window ") => parse_window_intent(input),
+        _ if input.starts_with("interpolate ") => parse_interpolate_intent(input),
+        _ if input.starts_with("normalize ") => parse_normalize_intent(input),
+        _ if input.starts_with("one-hot ") => parse_encode_intent(input, "one-hot ", Verb::OneHot),
+        _ if input.starts_with("label-encode ") => parse_encode_intent(input, "label-encode ", Verb::LabelEncode),
+        _ if input.starts_with("coalesce ") => parse_coalesce_intent(input),
+        _ if input.starts_with("filter ") => parse_collection_block_intent(input, "filter", "where", Verb::Filter),
+        _ if input.starts_with("transform ") => parse_collection_block_intent(input, "transform", "with", Verb::Transform),
+        _ if input.starts_with("map ") => parse_collection_block_intent(input, "map", "with", Verb::Transform),
+        _ if input.starts_with("reduce ") => parse_collection_block_intent(input, "reduce", "with", Verb::Reduce),
+        _ if input.starts_with("sort ") => parse_sort_intent(input),
+        _ if input.starts_with("switch ") => parse_switch_intent(input),
+        _ if input.starts_with("try ") || input.starts_with("try{") => parse_try_intent(input),
+        _ if input.starts_with("raise ") || input == "raise" => parse_raise_intent(input),
+        _ if input.starts_with("each ") => parse_each_intent(input),
+        _ if input.starts_with("assert ") => parse_assert_intent(input),
+        _ if input.starts_with("benchmark") => parse_benchmark_intent(input),
+        _ if input.starts_with("capture ") => parse_capture_intent(input),
+        _ if input.starts_with("redirect ") => parse_redirect_intent(input),
+        "trace" | "trace on" | "trace off" => Ok(Intent::new(Verb::Trace)
+            .with_target(Target::Expression(input.trim_start_matches("trace").trim().to_string()))),
+        _ if input.starts_with("watch ") => parse_watch_intent(input),
+        _ if input.starts_with("log ") => parse_log_intent(input),
+        _ if input == "config" || input.starts_with("config ") => parse_config_intent(input),
+        _ if input.starts_with("notify ") => parse_notify_intent(input, false),
+        _ if input.starts_with("env-var ") => parse_env_var_intent(input),
+        _ if input.starts_with("export ") => parse_export_intent(input),
+        "tutorial" => Ok(Intent::new(Verb::Tutorial)),
+        "tutorial reset" => Ok(Intent::new(Verb::Tutorial).with_parameter("reset", "true")),
+        "feedback show" => Ok(Intent::new(Verb::Feedback).with_parameter("action", "show")),
+        "feedback send" => Ok(Intent::new(Verb::Feedback).with_parameter("action", "send")),
+        _ if input.starts_with("feedback ") => Ok(Intent::new(Verb::Feedback)
+            .with_parameter("action", "add")
+            .with_parameter("message", unquote(input.trim_start_matches("feedback ")))),
+        _ if input.starts_with("changelog ") || input == "changelog" => Ok(Intent::new(Verb::Changelog)
+            .with_target(Target::Expression(input.trim_start_matches("changelog").trim().to_string()))),
+        _ if input.starts_with("examples run ") => Ok(Intent::new(Verb::Examples)
+            .with_parameter("run", input.trim_start_matches("examples run ").trim())),
+        _ if input.starts_with("examples ") || input == "examples" => Ok(Intent::new(Verb::Examples)
+            .with_target(Target::Expression(input.trim_start_matches("examples").trim().to_string()))),
+        _ if input.starts_with("man ") || input == "man" => Ok(Intent::new(Verb::Man)
+            .with_target(Target::Expression(input.trim_start_matches("man").trim().to_string()))),
+        _ if input.starts_with("completions ") => Ok(Intent::new(Verb::Completion)
+            .with_target(Target::Expression(input.trim_start_matches("completions ").trim().to_string()))),
+        _ if input.starts_with("on-error notify ") => parse_notify_intent(input.trim_start_matches("on-error "), true),
+        "aliases" => Ok(Intent::new(Verb::Aliases)),
+        _ if input.starts_with("alias ") => parse_alias_intent(input),
+        _ if input.starts_with("unalias ") => Ok(Intent::new(Verb::Unalias)
+            .with_target(Target::Expression(input.trim_start_matches("unalias ").trim().to_string()))),
+        _ if input.starts_with("unset ") => Ok(Intent::new(Verb::Unset)
+            .with_target(Target::Variable(input.trim_start_matches("unset ").trim().to_string()))),
+        _ if input.starts_with("rename ") => parse_rename_intent(input),
+        _ if input.starts_with("copy ") => parse_copy_intent(input),
+        _ if input.starts_with("swap ") => parse_swap_intent(input),
+        _ if input.starts_with("cast ") => parse_cast_intent(input),
 
         _ => Err(format!("Unknown intent: '{}'", input)),
         
@@ -1020,6 +1243,43 @@ pub fn parse_to_intent(input: &str) -> Result<Intent, String> {
 }
 
 
+// Rewrite `set counter += 1` to `set counter = counter + (1)` so the usual
+// expression path sees (and propagates from) the variable itself
+fn desugar_compound_assignment(input: &str) -> Result<String, String> {
+    let content = input.trim_start_matches("set ").trim();
+
+    let (var_part, op, value_part) = match content.find('=') {
+        Some(pos) if pos > 0 => {
+            let op = content.as_bytes()[pos - 1] as char;
+            if !matches!(op, '+' | '-' | '*' | '/') {
+                return Ok(input.to_string());
+            }
+            (content[..pos - 1].trim(), op, content[pos + 1..].trim())
+        }
+        _ => return Ok(input.to_string()),
+    };
+
+    let var_name = var_part.split(':').next().unwrap_or("").trim();
+    if var_name.is_empty() || !var_name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+        return Ok(input.to_string());
+    }
+    if value_part.is_empty() {
+        return Err(format!("Compound assignment requires a value: set {} {}= value", var_name, op));
+    }
+
+    // Keep any ~-N / ~+N propagation suffix outside the parentheses
+    let (expression, delay, limit) = parse_propagation_suffix(value_part)?;
+    let suffix = if delay > 0 {
+        format!(" ~-{}", delay)
+    } else if limit != usize::MAX {
+        format!(" ~+{}", limit)
+    } else {
+        String::new()
+    };
+
+    Ok(format!("set {} = {} {} ({}){}", var_part, var_name, op, expression, suffix))
+}
+
 fn parse_set_intent(input: &str) -> Result<Intent, String> {
     let content = input.trim_start_matches("set ").trim();
     
@@ -1031,6 +1291,7 @@ fn parse_set_intent(input: &str) -> Result<Intent, String> {
     
     let var_part = &content[..equals_pos].trim_end_matches('=').trim();
     let value_part = &content[equals_pos + 1..].trim(); // Skip the '='
+    let (value_part, modifier) = split_variable_modifier(value_part);
     
     // SECOND: Parse propagation suffix from the VALUE part only
     let (clean_value_part, delay, limit) = parse_propagation_suffix(value_part)?;
@@ -1055,9 +1316,25 @@ fn parse_set_intent(input: &str) -> Result<Intent, String> {
         intent = intent.with_parameter("propagation_limit", &limit.to_string());
     }
     
+    if let Some(modifier) = modifier {
+        intent = intent.with_parameter("modifier", modifier);
+    }
+    
     Ok(intent)
 }
 
+// Variable modifiers trail the value: `set path = "/etc" as readonly`
+fn split_variable_modifier(value: &str) -> (&str, Option<&'static str>) {
+    for modifier in ["readonly", "final", "lazy"] {
+        if let Some(stripped) = value.strip_suffix(modifier) {
+            if let Some(stripped) = stripped.trim_end().strip_suffix(" as") {
+                return (stripped.trim_end(), Some(modifier));
+            }
+        }
+    }
+    (value, None)
+}
+
 fn find_equals_position(s: &str) -> Result<usize, String> {
     let s = s.trim();
     
@@ -1419,10 +1696,7 @@ fn parse_composition_intent(name: &str, input: &str) -> Result<Intent, String> {
         .mark_as_composition(name)
         .with_source("defined_composition");
     
-    // Store intent names as parameter for now (will resolve to IDs later)
-    for intent_name in &intents {
-        intent = intent.with_parameter("sub_intent", intent_name);
-    }
+    intent.composition_steps = intents;
     
     Ok(intent)
 }
@@ -1708,6 +1982,10 @@ fn parse_examine_intent(input: &str) -> Result<Intent, String> {
             Ok(Intent::new(Verb::Examine)  // Use Verb::Examine, not Verb::Inspect
                 .with_target(Target::Expression(target.to_string())))
         }
+        _ if target.starts_with("intent ") => {
+            Ok(Intent::new(Verb::Examine)
+                .with_target(Target::Expression(target.to_string())))
+        }
         _ => Err(format!("Unknown examine target: '{}'", target))
     }
 }
@@ -1740,9 +2018,20 @@ fn parse_construct_intent(input: &str) -> Result<Intent, String> {
 }
 
 fn parse_evolve_intent(input: &str) -> Result<Intent, String> {
+    // evolve "intent_name" { new_expression }
     // evolve intent_name add_param "name" default="value"
     let content = input.trim_start_matches("evolve ").trim();
     
+    if let Some((name, body)) = block_body(content) {
+        let name = name.trim().trim_matches('"');
+        if name.is_empty() {
+            return Err("evolve requires an intent name".to_string());
+        }
+        return Ok(Intent::new(Verb::Evolve)
+            .with_target(Target::Expression(name.to_string()))
+            .with_parameter("body", body.trim()));
+    }
+    
     let parts: Vec<&str> = content.splitn(3, ' ').collect();
     if parts.len() < 3 {
         return Err("Evolve requires: evolve intent_name action params".to_string());
@@ -1759,19 +2048,28 @@ fn parse_evolve_intent(input: &str) -> Result<Intent, String> {
 }
 
 fn parse_grow_intent(input: &str) -> Result<Intent, String> {
-    // grow new_intent from base_intent
+    // grow "existing_intent" with ["sub_intent", "other"]
     let content = input.trim_start_matches("grow ").trim();
     
-    if let Some(from_pos) = content.find(" from ") {
-        let new_name = content[..from_pos].trim();
-        let base_name = content[from_pos + 6..].trim(); // " from " is 6 chars
-        
-        Ok(Intent::new(Verb::Grow)
-            .with_target(Target::Expression(new_name.to_string()))
-            .with_parameter("from", base_name))
-    } else {
-        Err("Grow requires 'from' clause".to_string())
+    let (name, list) = content.split_once(" with ")
+        .ok_or("grow requires: grow \"intent\" with [\"sub_intent\"]".to_string())?;
+    let list = list.trim();
+    if !list.starts_with('[') || !list.ends_with(']') {
+        return Err("Expected list of intents in []".to_string());
+    }
+    
+    let steps: Vec<&str> = list[1..list.len() - 1].split(',')
+        .map(|s| s.trim().trim_matches('"'))
+        .filter(|s| !s.is_empty())
+        .collect();
+    if steps.is_empty() {
+        return Err("grow needs at least one sub-intent".to_string());
     }
+    
+    // Kept as typed: `define intent "name"` stores the name with its quotes
+    Ok(Intent::new(Verb::Grow)
+        .with_target(Target::Expression(name.trim().to_string()))
+        .with_parameter("steps", &steps.join(",")))
 }
 
 fn parse_reflect_intent(input: &str) -> Result<Intent, String> {
@@ -1805,4 +2103,1529 @@ fn parse_adopt_intent(input: &str) -> Result<Intent, String> {
     
     Ok(Intent::new(Verb::Adopt)
         .with_target(Target::Expression(name.to_string())))
-}
\ No newline at end of file
+}
+
+fn parse_manifest_intent(input: &str) -> Result<Intent, String> {
+    let content = input.trim_start_matches("manifest ").trim();
+    
+    if content.is_empty() {
+        return Err("Manifest requires a .mmf file path".to_string());
+    }
+    
+    let path = if content.starts_with('"') && content.ends_with('"') && content.len() >= 2 {
+        &content[1..content.len()-1]
+    } else {
+        content
+    };
+    
+    Ok(Intent::new(Verb::Manifest)
+        .with_target(Target::File(path.to_string())))
+}
+
+// Strip surrounding double quotes from a name or path
+fn unquote(s: &str) -> &str {
+    let s = s.trim();
+    if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+        &s[1..s.len()-1]
+    } else {
+        s
+    }
+}
+
+fn parse_schema_intent(input: &str) -> Result<Intent, String> {
+    // schema "name" { field: type, field2: int(0..10) }
+    let content = input.trim_start_matches("schema ").trim();
+    
+    let brace = content.find('{')
+        .ok_or("Schema requires a field block: schema \"name\" { field: type }".to_string())?;
+    
+    let name = unquote(&content[..brace]);
+    if name.is_empty() {
+        return Err("Schema requires a name".to_string());
+    }
+    
+    Ok(Intent::new(Verb::Schema)
+        .with_target(Target::Expression(name.to_string()))
+        .with_parameter("fields", content[brace..].trim()))
+}
+
+fn parse_generate_intent(input: &str) -> Result<Intent, String> {
+    // generate "schema" count 10 into users
+    let content = input.trim_start_matches("generate ").trim();
+    
+    let (spec, dest) = content.rsplit_once(" into ")
+        .ok_or("Generate requires 'into <variable>'".to_string())?;
+    
+    let (name, count) = match spec.split_once(" count ") {
+        Some((name, count)) => (name, count.trim()),
+        None => (spec, "1"),
+    };
+    
+    count.parse::<usize>()
+        .map_err(|_| format!("Invalid count: {}", count))?;
+    
+    Ok(Intent::new(Verb::Generate)
+        .with_target(Target::Expression(unquote(name).to_string()))
+        .with_parameter("count", count)
+        .with_parameter("into", dest.trim()))
+}
+
+fn parse_diff_intent(input: &str) -> Result<Intent, String> {
+    // diff config_a and config_b [into changes]
+    let content = input.trim_start_matches("diff ").trim();
+    
+    let (pair, dest) = match content.rsplit_once(" into ") {
+        Some((pair, dest)) => (pair, Some(dest.trim())),
+        None => (content, None),
+    };
+    
+    let (left, right) = pair.split_once(" and ")
+        .ok_or("Diff requires two variables: diff <a> and <b>".to_string())?;
+    
+    let mut intent = Intent::new(Verb::DiffDicts)
+        .with_parameter("left", left.trim())
+        .with_parameter("right", right.trim());
+    
+    if let Some(dest) = dest {
+        intent = intent.with_parameter("into", dest);
+    }
+    
+    Ok(intent)
+}
+
+fn parse_merge_intent(input: &str) -> Result<Intent, String> {
+    // merge defaults and overrides into config [strategy=first_wins]
+    let mut content = input.trim_start_matches("merge ").trim();
+    let mut strategy = "last_wins";
+    
+    if let Some(pos) = content.find(" strategy=") {
+        strategy = content[pos + " strategy=".len()..].trim();
+        content = content[..pos].trim();
+    }
+    
+    let (pair, dest) = content.rsplit_once(" into ")
+        .ok_or("Merge requires 'into <variable>'".to_string())?;
+    
+    let (left, right) = pair.split_once(" and ")
+        .ok_or("Merge requires two variables: merge <a> and <b> into <dest>".to_string())?;
+    
+    Ok(Intent::new(Verb::Merge)
+        .with_parameter("left", left.trim())
+        .with_parameter("right", right.trim())
+        .with_parameter("into", dest.trim())
+        .with_parameter("strategy", strategy))
+}
+
+fn parse_assert_schema_intent(input: &str) -> Result<Intent, String> {
+    // assert-schema rows against "user" [report into errors]
+    let content = input.trim_start_matches("assert-schema ").trim();
+    
+    let (spec, dest) = match content.rsplit_once(" report into ") {
+        Some((spec, dest)) => (spec, Some(dest.trim())),
+        None => (content, None),
+    };
+    
+    let (list_var, schema) = spec.split_once(" against ")
+        .ok_or("assert-schema requires: assert-schema <list> against \"schema\"".to_string())?;
+    
+    let mut intent = Intent::new(Verb::AssertSchema)
+        .with_target(Target::Variable(list_var.trim().to_string()))
+        .with_parameter("schema", unquote(schema));
+    
+    if let Some(dest) = dest {
+        intent = intent.with_parameter("into", dest);
+    }
+    
+    Ok(intent)
+}
+
+fn parse_summarize_intent(input: &str) -> Result<Intent, String> {
+    // summarize prices | summarize orders field total [into stats]
+    let content = input.trim_start_matches("summarize ").trim();
+    
+    let (spec, dest) = match content.rsplit_once(" into ") {
+        Some((spec, dest)) => (spec, Some(dest.trim())),
+        None => (content, None),
+    };
+    
+    let (list_var, field) = match spec.split_once(" field ") {
+        Some((list_var, field)) => (list_var.trim(), Some(unquote(field))),
+        None => (spec.trim(), None),
+    };
+    
+    if list_var.is_empty() {
+        return Err("Summarize requires a list variable".to_string());
+    }
+    
+    let mut intent = Intent::new(Verb::Summarize)
+        .with_target(Target::Variable(list_var.to_string()));
+    
+    if let Some(field) = field {
+        intent = intent.with_parameter("field", field);
+    }
+    if let Some(dest) = dest {
+        intent = intent.with_parameter("into", dest);
+    }
+    
+    Ok(intent)
+}
+
+// Split "src key1 a key2 b ..." keyword clauses; returns the leading word and a clause map
+fn parse_keyword_clauses<'a>(content: &'a str, keywords: &[&str]) -> (&'a str, HashMap<String, String>) {
+    let words: Vec<&str> = content.split_whitespace().collect();
+    let mut clauses = HashMap::new();
+    let head = words.first().copied().unwrap_or("");
+
+    let mut i = 1;
+    while i < words.len() {
+        if keywords.contains(&words[i]) && i + 1 < words.len() {
+            clauses.insert(words[i].to_string(), unquote(words[i + 1]).to_string());
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+
+    (head, clauses)
+}
+
+fn parse_pivot_intent(input: &str) -> Result<Intent, String> {
+    // pivot sales row region col month val total into by_month
+    let content = input.trim_start_matches("pivot ").trim();
+    let (list_var, clauses) = parse_keyword_clauses(content, &["row", "col", "val", "into"]);
+
+    let mut intent = Intent::new(Verb::Pivot)
+        .with_target(Target::Variable(list_var.to_string()));
+    for key in ["row", "col", "val", "into"] {
+        let value = clauses.get(key)
+            .ok_or_else(|| format!("Pivot requires: pivot <list> row <key> col <key> val <key> into <dest> (missing '{}')", key))?;
+        intent = intent.with_parameter(key, value);
+    }
+
+    Ok(intent)
+}
+
+fn parse_unpivot_intent(input: &str) -> Result<Intent, String> {
+    // unpivot by_month row region val total into sales
+    let content = input.trim_start_matches("unpivot ").trim();
+    let (var, clauses) = parse_keyword_clauses(content, &["row", "val", "into"]);
+
+    let mut intent = Intent::new(Verb::Unpivot)
+        .with_target(Target::Variable(var.to_string()));
+    for key in ["row", "val", "into"] {
+        let value = clauses.get(key)
+            .ok_or_else(|| format!("Unpivot requires: unpivot <var> row <col> val <col> into <dest> (missing '{}')", key))?;
+        intent = intent.with_parameter(key, value);
+    }
+
+    Ok(intent)
+}
+
+fn parse_join_intent(input: &str, prefix: &str, verb: Verb) -> Result<Intent, String> {
+    // join users and orders on user_id [into user_orders]
+    let content = input.trim_start_matches(prefix).trim();
+
+    let (spec, dest) = match content.rsplit_once(" into ") {
+        Some((spec, dest)) => (spec, Some(dest.trim())),
+        None => (content, None),
+    };
+
+    let (pair, key) = spec.rsplit_once(" on ")
+        .ok_or_else(|| format!("{}requires: {}<list_a> and <list_b> on <key>", prefix, prefix))?;
+    let (left, right) = pair.split_once(" and ")
+        .ok_or_else(|| format!("{}requires two lists: <list_a> and <list_b>", prefix))?;
+
+    let mut intent = Intent::new(verb)
+        .with_parameter("left", left.trim())
+        .with_parameter("right", right.trim())
+        .with_parameter("key", unquote(key));
+
+    if let Some(dest) = dest {
+        intent = intent.with_parameter("into", dest);
+    }
+
+    Ok(intent)
+}
+
+fn parse_window_intent(input: &str) -> Result<Intent, String> {
+    // window prices size 3 aggregate avg into moving_avg
+    let content = input.trim_start_matches("window ").trim();
+    let (list_var, clauses) = parse_keyword_clauses(content, &["size", "aggregate", "into"]);
+
+    let size = clauses.get("size")
+        .ok_or("Window requires 'size <n>'".to_string())?;
+    size.parse::<usize>()
+        .map_err(|_| format!("Invalid window size: {}", size))?;
+
+    let mut intent = Intent::new(Verb::Window)
+        .with_target(Target::Variable(list_var.to_string()))
+        .with_parameter("size", size)
+        .with_parameter("aggregate", clauses.get("aggregate").map(|s| s.as_str()).unwrap_or("avg"));
+
+    if let Some(dest) = clauses.get("into") {
+        intent = intent.with_parameter("into", dest);
+    }
+
+    Ok(intent)
+}
+
+fn parse_interpolate_intent(input: &str) -> Result<Intent, String> {
+    // interpolate readings [method forward] [into filled]
+    let content = input.trim_start_matches("interpolate ").trim();
+    let (list_var, clauses) = parse_keyword_clauses(content, &["method", "into"]);
+
+    if list_var.is_empty() {
+        return Err("Interpolate requires a list variable".to_string());
+    }
+
+    let mut intent = Intent::new(Verb::Interpolate)
+        .with_target(Target::Variable(list_var.to_string()))
+        .with_parameter("method", clauses.get("method").map(|s| s.as_str()).unwrap_or("linear"));
+
+    if let Some(dest) = clauses.get("into") {
+        intent = intent.with_parameter("into", dest);
+    }
+
+    Ok(intent)
+}
+
+fn parse_normalize_intent(input: &str) -> Result<Intent, String> {
+    // normalize scores [method z-score] [into scaled]
+    let content = input.trim_start_matches("normalize ").trim();
+    let (list_var, clauses) = parse_keyword_clauses(content, &["method", "into"]);
+
+    if list_var.is_empty() {
+        return Err("Normalize requires a list variable".to_string());
+    }
+
+    let mut intent = Intent::new(Verb::Normalize)
+        .with_target(Target::Variable(list_var.to_string()))
+        .with_parameter("method", clauses.get("method").map(|s| s.as_str()).unwrap_or("minmax"));
+
+    if let Some(dest) = clauses.get("into") {
+        intent = intent.with_parameter("into", dest);
+    }
+
+    Ok(intent)
+}
+
+fn parse_encode_intent(input: &str, prefix: &str, verb: Verb) -> Result<Intent, String> {
+    // one-hot colors into color_flags | label-encode colors into color_ids
+    let content = input.trim_start_matches(prefix).trim();
+
+    let (list_var, dest) = content.split_once(" into ")
+        .ok_or_else(|| format!("{}requires: {}<list> into <dest>", prefix, prefix))?;
+
+    Ok(Intent::new(verb)
+        .with_target(Target::Variable(list_var.trim().to_string()))
+        .with_parameter("into", dest.trim()))
+}
+
+fn parse_coalesce_intent(input: &str) -> Result<Intent, String> {
+    // coalesce [nickname, username, "anonymous"] into display_name
+    let content = input.trim_start_matches("coalesce ").trim();
+
+    let (list, dest) = content.rsplit_once(" into ")
+        .ok_or("Coalesce requires 'into <variable>'".to_string())?;
+    let list = list.trim();
+
+    if !list.starts_with('[') || !list.ends_with(']') {
+        return Err("Coalesce requires a list: coalesce [a, b, c] into <dest>".to_string());
+    }
+
+    Ok(Intent::new(Verb::Coalesce)
+        .with_target(Target::Expression(list[1..list.len()-1].to_string()))
+        .with_parameter("into", dest.trim()))
+}
+// Split a block body into statements on `;` and newlines, keeping quoted strings
+// and nested `{ }` / `( )` / `[ ]` groups intact
+pub fn split_block_statements(body: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+
+    for c in body.chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+                current.push(c);
+            }
+            None => match c {
+                '"' => {
+                    quote = Some(c);
+                    current.push(c);
+                }
+                '{' | '(' | '[' => {
+                    depth += 1;
+                    current.push(c);
+                }
+                '}' | ')' | ']' => {
+                    depth -= 1;
+                    current.push(c);
+                }
+                ';' | '\n' if depth == 0 => {
+                    if !current.trim().is_empty() {
+                        statements.push(current.trim().to_string());
+                    }
+                    current.clear();
+                }
+                _ => current.push(c),
+            },
+        }
+    }
+
+    if !current.trim().is_empty() {
+        statements.push(current.trim().to_string());
+    }
+
+    statements
+}
+
+// Body of a `{ ... }` block: the text between the first `{` and the last `}`
+fn block_body(content: &str) -> Option<(&str, &str)> {
+    let open = content.find('{')?;
+    let close = content.rfind('}')?;
+    if close < open {
+        return None;
+    }
+    Some((content[..open].trim(), &content[open + 1..close]))
+}
+
+fn parse_switch_intent(input: &str) -> Result<Intent, String> {
+    // switch color { case "red" then writeout(stop); case "green" then writeout(go); default writeout(?) }
+    let content = input.trim_start_matches("switch ").trim();
+
+    let (var_name, body) = block_body(content)
+        .ok_or("Switch requires a case block: switch var { case \"a\" then <intent>; default <intent> }".to_string())?;
+
+    if var_name.is_empty() {
+        return Err("Switch requires a variable name".to_string());
+    }
+
+    let mut intent = Intent::new(Verb::Switch)
+        .with_target(Target::Variable(var_name.to_string()));
+
+    let mut case_count = 0;
+    let mut has_default = false;
+
+    for clause in split_block_statements(body) {
+        if let Some(rest) = clause.strip_prefix("case ") {
+            let (value, branch) = rest.split_once(" then ")
+                .ok_or_else(|| format!("Case needs 'then <intent>': {}", clause))?;
+            intent = intent
+                .with_parameter(&format!("case_{}_value", case_count), unquote(value))
+                .with_parameter(&format!("case_{}_body", case_count), branch.trim());
+            case_count += 1;
+        } else if let Some(branch) = clause.strip_prefix("default") {
+            if has_default {
+                return Err("Switch can only have one default branch".to_string());
+            }
+            let branch = branch.trim();
+            let branch = branch.strip_prefix("then ").unwrap_or(branch).trim();
+            intent = intent.with_parameter("default", branch);
+            has_default = true;
+        } else {
+            return Err(format!("Expected 'case' or 'default' in switch, got: {}", clause));
+        }
+    }
+
+    if case_count == 0 && !has_default {
+        return Err("Switch requires at least one case".to_string());
+    }
+
+    Ok(intent.with_parameter("case_count", &case_count.to_string()))
+}
+
+// Body of a /pattern/ up to the closing slash; `\/` stands for a literal slash.
+// Returns the pattern and the text after the closing slash
+fn take_catch_pattern(s: &str) -> Option<(String, &str)> {
+    let mut pattern = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '/' => return Some((pattern, &s[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, '/')) => pattern.push('/'),
+                Some((_, escaped)) => {
+                    pattern.push('\\');
+                    pattern.push(escaped);
+                }
+                None => pattern.push('\\'),
+            },
+            _ => pattern.push(c),
+        }
+    }
+    None
+}
+
+// Split a leading `{ ... }` group off `s`, returning (inner body, remainder)
+fn take_braced_block(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start();
+    if !s.starts_with('{') {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut in_quotes = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '{' if !in_quotes => depth += 1,
+            '}' if !in_quotes => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((&s[1..i], &s[i + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_try_intent(input: &str) -> Result<Intent, String> {
+    // try { <intents> } catch /not found/ { ... } catch { <intents using _error> } finally { <intents> }
+    let content = input.trim_start_matches("try").trim();
+
+    let (body, mut rest) = take_braced_block(content)
+        .ok_or("Try requires a block: try { <intents> } catch { <intents> }".to_string())?;
+
+    let mut intent = Intent::new(Verb::Try)
+        .with_parameter("body", body.trim());
+    let mut catch_count = 0;
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+
+        if let Some(after) = rest.strip_prefix("catch") {
+            if intent.parameters.contains_key("finally") {
+                return Err("'catch' clauses must come before 'finally'".to_string());
+            }
+
+            // Optional /pattern/ matched against the error message; a bare catch matches anything
+            let after = after.trim_start();
+            let (pattern, after) = match after.strip_prefix('/') {
+                Some(pattern_start) => {
+                    let (pattern, after) = take_catch_pattern(pattern_start)
+                        .ok_or("Unterminated catch pattern, expected /pattern/".to_string())?;
+                    regex::Regex::new(&pattern)
+                        .map_err(|e| format!("Invalid catch pattern /{}/: {}", pattern, e))?;
+                    (pattern, after)
+                }
+                None => (String::new(), after),
+            };
+
+            let (block, remainder) = take_braced_block(after)
+                .ok_or("'catch' requires a { ... } block".to_string())?;
+            intent = intent
+                .with_parameter(&format!("catch_{}_pattern", catch_count), &pattern)
+                .with_parameter(&format!("catch_{}_body", catch_count), block.trim());
+            catch_count += 1;
+            rest = remainder;
+        } else if let Some(after) = rest.strip_prefix("finally") {
+            if intent.parameters.contains_key("finally") {
+                return Err("Try can only have one 'finally' block".to_string());
+            }
+            let (block, remainder) = take_braced_block(after)
+                .ok_or("'finally' requires a { ... } block".to_string())?;
+            intent = intent.with_parameter("finally", block.trim());
+            rest = remainder;
+        } else {
+            return Err(format!("Expected 'catch' or 'finally' after try block, got: {}", rest));
+        }
+    }
+
+    if catch_count == 0 && !intent.parameters.contains_key("finally") {
+        return Err("Try requires a 'catch' or 'finally' block".to_string());
+    }
+
+    Ok(intent.with_parameter("catch_count", &catch_count.to_string()))
+}
+
+fn parse_raise_intent(input: &str) -> Result<Intent, String> {
+    // raise "quota exceeded for {user}"
+    let message = unquote(input.trim_start_matches("raise").trim());
+    let message = if message.is_empty() { "Error raised by script" } else { message };
+
+    Ok(Intent::new(Verb::Raise)
+        .with_target(Target::Expression(message.to_string())))
+}
+
+// Split `s` on `sep`, ignoring separators inside double-quoted strings
+fn split_outside_quotes(s: &str, sep: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in s.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        if c == sep && !in_quotes {
+            parts.push(current.trim().to_string());
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    parts
+}
+
+fn parse_log_intent(input: &str) -> Result<Intent, String> {
+    // log info "deployed {version}" fields { env="prod", retries=3 }
+    // log flush
+    // log file "~/deploy.log"
+    let content = input.trim_start_matches("log ").trim();
+    let (level, rest) = content.split_once(' ').unwrap_or((content, ""));
+    let rest = rest.trim();
+
+    match level {
+        "flush" => return Ok(Intent::new(Verb::Log).with_parameter("action", "flush")),
+        "file" => {
+            let path = unquote(rest);
+            if path.is_empty() {
+                return Err("log file requires a path: log file \"path\"".to_string());
+            }
+            return Ok(Intent::new(Verb::Log)
+                .with_parameter("action", "file")
+                .with_target(Target::File(path.to_string())));
+        }
+        _ => {}
+    }
+
+    let level = match level {
+        "debug" | "info" | "error" => level,
+        "warning" | "warn" => "warning",
+        other => return Err(format!("Unknown log level '{}'. Use debug, info, warning or error", other)),
+    };
+
+    // The message is either a quoted string or everything up to `fields {`
+    let (message, remainder) = if let Some(quoted) = rest.strip_prefix('"') {
+        let end = quoted.find('"').ok_or("Unterminated log message".to_string())?;
+        (&quoted[..end], quoted[end + 1..].trim())
+    } else {
+        match rest.find("fields {").or_else(|| rest.find("fields{")) {
+            Some(pos) => (rest[..pos].trim(), rest[pos..].trim()),
+            None => (rest, ""),
+        }
+    };
+
+    if message.is_empty() {
+        return Err(format!("log {} requires a message", level));
+    }
+
+    let mut intent = Intent::new(Verb::Log)
+        .with_parameter("action", "write")
+        .with_parameter("level", level)
+        .with_target(Target::Expression(message.to_string()));
+
+    if !remainder.is_empty() {
+        let fields = remainder.strip_prefix("fields")
+            .and_then(take_braced_block)
+            .map(|(block, _)| block)
+            .ok_or_else(|| format!("Expected 'fields {{ key=value, ... }}' after log message, got: {}", remainder))?;
+
+        for pair in split_outside_quotes(fields, ',') {
+            let (key, value) = pair.split_once('=')
+                .ok_or_else(|| format!("Log field '{}' must be key=value", pair))?;
+            intent = intent.with_parameter(&format!("field.{}", key.trim()), value.trim());
+        }
+    }
+
+    Ok(intent)
+}
+
+fn parse_notify_intent(input: &str, on_error: bool) -> Result<Intent, String> {
+    // notify "Script complete!" title "Morris"
+    let content = input.trim_start_matches("notify ").trim();
+
+    let (message, rest) = match content.strip_prefix('"') {
+        Some(quoted) => {
+            let end = quoted.find('"').ok_or("Unterminated notification message".to_string())?;
+            (&quoted[..end], quoted[end + 1..].trim())
+        }
+        None => match content.find(" title ") {
+            Some(pos) => (content[..pos].trim(), content[pos..].trim()),
+            None => (content, ""),
+        },
+    };
+
+    if message.is_empty() {
+        return Err("Notify requires a message: notify \"message\" [title \"title\"]".to_string());
+    }
+
+    let title = match rest.strip_prefix("title") {
+        Some(title) => unquote(title),
+        None if rest.is_empty() => "Morris",
+        None => return Err(format!("Expected 'title \"...\"' after notification message, got: {}", rest)),
+    };
+
+    Ok(Intent::new(Verb::Notify)
+        .with_target(Target::Expression(message.to_string()))
+        .with_parameter("title", title)
+        .with_parameter("on_error", if on_error { "true" } else { "false" }))
+}
+
+fn parse_env_var_intent(input: &str) -> Result<Intent, String> {
+    // env-var MORRIS_REGION into region
+    let content = input.trim_start_matches("env-var ").trim();
+    let (name, dest) = content.split_once(" into ")
+        .ok_or("env-var requires 'into <variable>': env-var NAME into var".to_string())?;
+
+    let name = unquote(name);
+    let dest = dest.trim();
+    if name.is_empty() || dest.is_empty() {
+        return Err("env-var requires a name and a destination variable".to_string());
+    }
+
+    Ok(Intent::new(Verb::EnvVar)
+        .with_target(Target::Expression(name.to_string()))
+        .with_parameter("into", dest))
+}
+
+fn parse_rename_intent(input: &str) -> Result<Intent, String> {
+    // rename old_name as new_name [--force]
+    let content = input.trim_start_matches("rename ").trim();
+    let (content, force) = match content.strip_suffix("--force") {
+        Some(stripped) => (stripped.trim(), true),
+        None => (content, false),
+    };
+    let (old, new) = content.split_once(" as ")
+        .ok_or("rename requires 'as': rename old_name as new_name".to_string())?;
+
+    let (old, new) = (old.trim(), new.trim());
+    if old.is_empty() || new.is_empty() {
+        return Err("rename requires an existing and a new variable name".to_string());
+    }
+
+    let mut intent = Intent::new(Verb::Rename)
+        .with_target(Target::Variable(old.to_string()))
+        .with_parameter("new_name", new);
+    if force {
+        intent = intent.with_parameter("force", "true");
+    }
+    Ok(intent)
+}
+
+fn parse_copy_intent(input: &str) -> Result<Intent, String> {
+    // copy source_var as dest_var
+    let content = input.trim_start_matches("copy ").trim();
+    let (source, dest) = content.split_once(" as ")
+        .ok_or("copy requires 'as': copy source_var as dest_var".to_string())?;
+
+    let (source, dest) = (source.trim(), dest.trim());
+    if source.is_empty() || dest.is_empty() {
+        return Err("copy requires a source and a destination variable".to_string());
+    }
+
+    Ok(Intent::new(Verb::Copy)
+        .with_target(Target::Variable(source.to_string()))
+        .with_parameter("into", dest))
+}
+
+fn parse_swap_intent(input: &str) -> Result<Intent, String> {
+    // swap a b
+    let names: Vec<&str> = input.trim_start_matches("swap ").split_whitespace().collect();
+    match names.as_slice() {
+        [a, b] => Ok(Intent::new(Verb::Swap)
+            .with_target(Target::Variable(a.to_string()))
+            .with_parameter("with", b)),
+        _ => Err("swap requires exactly two variables: swap a b".to_string()),
+    }
+}
+
+fn parse_cast_intent(input: &str) -> Result<Intent, String> {
+    // cast my_var as int
+    let content = input.trim_start_matches("cast ").trim();
+    let (var_name, type_name) = content.split_once(" as ")
+        .ok_or("cast requires 'as': cast var as int".to_string())?;
+
+    let (var_name, type_name) = (var_name.trim(), type_name.trim().trim_start_matches(':'));
+    if var_name.is_empty() || type_name.is_empty() {
+        return Err("cast requires a variable and a type".to_string());
+    }
+
+    Ok(Intent::new(Verb::Cast)
+        .with_target(Target::Variable(var_name.to_string()))
+        .with_parameter("type", type_name))
+}
+
+fn parse_each_intent(input: &str) -> Result<Intent, String> {
+    // each item in my_list { writeout({item}) }
+    let content = input.trim_start_matches("each ").trim();
+    let (item, rest) = content.split_once(" in ")
+        .ok_or("each requires 'in': each item in list { <intents> }".to_string())?;
+
+    let brace = rest.find('{')
+        .ok_or("each requires a block: each item in list { <intents> }".to_string())?;
+    let (collection, block) = rest.split_at(brace);
+    let (body, remainder) = take_braced_block(block)
+        .ok_or("Unterminated each block, expected '}'".to_string())?;
+
+    let (item, collection) = (item.trim(), collection.trim());
+    if item.is_empty() || collection.is_empty() {
+        return Err("each requires a loop variable and a collection".to_string());
+    }
+    if !remainder.trim().is_empty() {
+        return Err(format!("Unexpected input after each block: {}", remainder.trim()));
+    }
+
+    Ok(Intent::new(Verb::Each)
+        .with_target(Target::Expression(collection.to_string()))
+        .with_parameter("item", item)
+        .with_parameter("body", body.trim()))
+}
+
+// Shared shape of the collection verbs:
+//   <verb> <list> <keyword> { <expression> } [starting_from <value>] [into <dest>]
+fn parse_collection_block_intent(input: &str, verb_name: &str, keyword: &str, verb: Verb) -> Result<Intent, String> {
+    let usage = format!("{} requires: {} <list> {} {{ <expression> }} [into <dest>]", verb_name, verb_name, keyword);
+    let content = input[verb_name.len()..].trim();
+    let (collection, rest) = content.split_once(&format!(" {} ", keyword))
+        .ok_or_else(|| usage.clone())?;
+    let (body, rest) = take_braced_block(rest)
+        .ok_or_else(|| usage.clone())?;
+
+    let collection = collection.trim();
+    let body = body.trim();
+    if collection.is_empty() || body.is_empty() {
+        return Err(usage);
+    }
+
+    let mut intent = Intent::new(verb)
+        .with_target(Target::Expression(collection.to_string()))
+        .with_parameter("body", body);
+
+    let mut rest = rest.trim();
+    if let Some((before, dest)) = rest.rsplit_once("into ") {
+        if before.is_empty() || before.ends_with(' ') {
+            intent = intent.with_parameter("into", dest.trim());
+            rest = before.trim();
+        }
+    }
+    if let Some(start) = rest.strip_prefix("starting_from ") {
+        intent = intent.with_parameter("starting_from", start.trim());
+        rest = "";
+    }
+    if !rest.is_empty() {
+        return Err(format!("Unexpected input after {} block: {}", verb_name, rest));
+    }
+
+    Ok(intent)
+}
+
+fn parse_sort_intent(input: &str) -> Result<Intent, String> {
+    // sort my_list [by "field"] [ascending|descending]
+    let mut content = input.trim_start_matches("sort ").trim();
+    let mut intent = Intent::new(Verb::Sort);
+
+    if let Some(stripped) = content.strip_suffix("descending") {
+        intent = intent.with_parameter("descending", "true");
+        content = stripped.trim();
+    } else if let Some(stripped) = content.strip_suffix("ascending") {
+        content = stripped.trim();
+    }
+
+    if let Some((var_name, field)) = content.split_once(" by ") {
+        let field = unquote(field.trim());
+        if field.is_empty() {
+            return Err("sort by requires a field name: sort list by \"field\"".to_string());
+        }
+        intent = intent.with_parameter("by", field);
+        content = var_name.trim();
+    }
+
+    if content.is_empty() || content.contains(char::is_whitespace) {
+        return Err("sort requires a variable: sort list [by \"field\"] [descending]".to_string());
+    }
+
+    Ok(intent.with_target(Target::Expression(content.to_string())))
+}
+
+fn parse_assert_intent(input: &str) -> Result<Intent, String> {
+    // assert x > 0 [message "x must be positive"]
+    let content = input.trim_start_matches("assert ").trim();
+    let (condition, message) = match content.rsplit_once(" message ") {
+        Some((condition, message)) if message.trim().starts_with('"') => (condition.trim(), Some(unquote(message))),
+        _ => (content, None),
+    };
+
+    if condition.is_empty() {
+        return Err("assert requires a condition: assert x > 0".to_string());
+    }
+
+    let mut intent = Intent::new(Verb::Assert)
+        .with_target(Target::Expression(condition.to_string()));
+    if let Some(message) = message {
+        intent = intent.with_parameter("message", message);
+    }
+    Ok(intent)
+}
+
+fn parse_benchmark_intent(input: &str) -> Result<Intent, String> {
+    // benchmark [verbose] { <intents> }
+    let content = input.trim_start_matches("benchmark").trim();
+    let (verbose, content) = match content.strip_prefix("verbose") {
+        Some(rest) => (true, rest.trim()),
+        None => (false, content),
+    };
+
+    let (body, rest) = take_braced_block(content)
+        .ok_or("benchmark requires a block: benchmark { <intents> }".to_string())?;
+    if !rest.trim().is_empty() {
+        return Err(format!("Unexpected input after benchmark block: {}", rest.trim()));
+    }
+
+    let mut intent = Intent::new(Verb::Benchmark)
+        .with_parameter("body", body.trim());
+    if verbose {
+        intent = intent.with_parameter("verbose", "true");
+    }
+    Ok(intent)
+}
+
+fn parse_alias_intent(input: &str) -> Result<Intent, String> {
+    // alias save_state = "engine save && history save"
+    let content = input.trim_start_matches("alias ").trim();
+    let (name, expansion) = content.split_once('=')
+        .ok_or("alias requires '=': alias name = \"expansion\"".to_string())?;
+
+    Ok(Intent::new(Verb::Alias)
+        .with_target(Target::Expression(name.trim().to_string()))
+        .with_parameter("expansion", unquote(expansion)))
+}
+
+fn parse_export_intent(input: &str) -> Result<Intent, String> {
+    // export name [as OS_NAME] | export *
+    let content = input.trim_start_matches("export ").trim();
+    let (name, os_name) = match content.split_once(" as ") {
+        Some((name, os_name)) => (name.trim(), Some(unquote(os_name))),
+        None => (content, None),
+    };
+
+    if name.is_empty() {
+        return Err("export requires a variable name or '*'".to_string());
+    }
+    if name == "*" && os_name.is_some() {
+        return Err("export * cannot be combined with 'as'".to_string());
+    }
+
+    let mut intent = Intent::new(Verb::Export)
+        .with_target(Target::Expression(name.to_string()));
+    if let Some(os_name) = os_name {
+        if os_name.is_empty() {
+            return Err("export ... as requires an environment variable name".to_string());
+        }
+        intent = intent.with_parameter("as", os_name);
+    }
+    Ok(intent)
+}
+
+fn parse_snapshot_intent(input: &str, verb_name: &str, verb: Verb) -> Result<Intent, String> {
+    // snapshot "file.msnap" | restore "file.msnap" [--replace]
+    let content = input.trim_start_matches(verb_name).trim();
+    let (content, replace) = match content.strip_suffix("--replace") {
+        Some(stripped) if verb == Verb::Restore => (stripped.trim(), true),
+        _ => (content, false),
+    };
+
+    let path = unquote(content);
+    if path.is_empty() {
+        return Err(format!("{} requires a file: {} \"file.msnap\"", verb_name, verb_name));
+    }
+    if !path.ends_with(".msnap") {
+        return Err(format!("Expected .msnap snapshot file, got: {}", path));
+    }
+
+    let mut intent = Intent::new(verb).with_target(Target::File(path.to_string()));
+    if replace {
+        intent = intent.with_parameter("replace", "true");
+    }
+    Ok(intent)
+}
+
+fn parse_diff_env_intent(input: &str) -> Result<Intent, String> {
+    // diff-env "before.msnap" "after.msnap"
+    let mut rest = input.trim_start_matches("diff-env ").trim();
+    let mut paths = Vec::new();
+    while !rest.is_empty() {
+        let (path, remainder) = match rest.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"')
+                .ok_or("diff-env: unterminated quoted path".to_string())?,
+            None => rest.split_once(char::is_whitespace).unwrap_or((rest, "")),
+        };
+        paths.push(path.to_string());
+        rest = remainder.trim_start();
+    }
+
+    if paths.len() != 2 {
+        return Err("diff-env requires two snapshot files: diff-env \"before.msnap\" \"after.msnap\"".to_string());
+    }
+
+    Ok(Intent::new(Verb::DiffEnv)
+        .with_parameter("before", &paths[0])
+        .with_parameter("after", &paths[1]))
+}
+
+fn parse_watch_intent(input: &str) -> Result<Intent, String> {
+    // watch x y z
+    let names: Vec<&str> = input.trim_start_matches("watch")
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|name| !name.is_empty())
+        .collect();
+    if names.is_empty() {
+        return Err("watch requires at least one variable: watch <variables...>".to_string());
+    }
+
+    Ok(Intent::new(Verb::Watch).with_parameter("variables", &names.join(",")))
+}
+
+fn parse_capture_intent(input: &str) -> Result<Intent, String> {
+    // capture result { writeout("hello {name}") }
+    let content = input.trim_start_matches("capture ").trim();
+    let brace = content.find('{')
+        .ok_or("capture requires a block: capture <var> { <intents> }".to_string())?;
+    let name = content[..brace].trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(format!("capture requires a variable name, got '{}'", name));
+    }
+
+    let (body, rest) = take_braced_block(&content[brace..])
+        .ok_or("capture requires a block: capture <var> { <intents> }".to_string())?;
+    if !rest.trim().is_empty() {
+        return Err(format!("Unexpected input after capture block: {}", rest.trim()));
+    }
+
+    Ok(Intent::new(Verb::Capture)
+        .with_target(Target::Variable(name.to_string()))
+        .with_parameter("body", body.trim()))
+}
+
+fn parse_redirect_intent(input: &str) -> Result<Intent, String> {
+    // redirect "output.log" [append] { <intents> }
+    let content = input.trim_start_matches("redirect ").trim();
+    // The file must be a quoted string so braces and spaces in it are not misread
+    let (path, rest) = content.strip_prefix('"')
+        .and_then(|quoted| quoted.split_once('"'))
+        .ok_or("redirect requires a quoted file: redirect \"file\" [append] { <intents> }".to_string())?;
+    if path.is_empty() {
+        return Err("redirect requires a file: redirect \"file\" { <intents> }".to_string());
+    }
+
+    let rest = rest.trim_start();
+    let (append, rest) = match rest.strip_prefix("append") {
+        Some(after) if after.starts_with(|c: char| c.is_whitespace() || c == '{') => (true, after),
+        _ => (false, rest),
+    };
+    let (body, rest) = take_braced_block(rest)
+        .ok_or_else(|| format!("redirect expects [append] then a block after the file, got: {}", rest.trim()))?;
+    if !rest.trim().is_empty() {
+        return Err(format!("Unexpected input after redirect block: {}", rest.trim()));
+    }
+
+    let mut intent = Intent::new(Verb::Redirect)
+        .with_target(Target::File(path.to_string()))
+        .with_parameter("body", body.trim());
+    if append {
+        intent = intent.with_parameter("append", "true");
+    }
+    Ok(intent)
+}
+
+fn parse_parse_yaml_intent(input: &str) -> Result<Intent, String> {
+    // parse-yaml "name: web\nreplicas: 3" into config | parse-yaml raw_text into config
+    let content = input.trim_start_matches("parse-yaml ").trim();
+    let (source, dest) = match content.rsplit_once(" into ") {
+        Some((source, dest)) => (source.trim(), Some(dest.trim())),
+        None => (content, None),
+    };
+
+    if source.is_empty() {
+        return Err("parse-yaml requires a YAML string or a variable holding one".to_string());
+    }
+
+    // Quoted text is the document itself; a bare name is a string variable (e.g. from `read`)
+    let mut intent = Intent::new(Verb::ParseYaml);
+    intent = if source.len() >= 2 && source.starts_with('"') && source.ends_with('"') {
+        intent.with_parameter("yaml", &source[1..source.len() - 1])
+    } else {
+        intent.with_parameter("source", source)
+    };
+
+    if let Some(dest) = dest {
+        if dest.is_empty() {
+            return Err("parse-yaml ... into requires a variable name".to_string());
+        }
+        intent = intent.with_parameter("into", dest);
+    }
+    Ok(intent)
+}
+
+fn parse_to_yaml_intent(input: &str) -> Result<Intent, String> {
+    let var_name = input.trim_start_matches("to-yaml ").trim();
+
+    if var_name.is_empty() {
+        return Err("to-yaml requires variable name".to_string());
+    }
+
+    Ok(Intent::new(Verb::ToYaml)
+        .with_parameter("variable", var_name))
+}
+
+fn parse_parse_csv_intent(input: &str) -> Result<Intent, String> {
+    // parse-csv "a,b\n1,2" into data [headers false] | parse-csv raw_text into data
+    let content = input.trim_start_matches("parse-csv ").trim();
+
+    // The source is a quoted literal (which may contain spaces) or a single variable name
+    let (mut intent, clauses) = if content.starts_with('"') {
+        let close = content.rfind('"').filter(|&close| close > 0)
+            .ok_or("parse-csv: unterminated CSV string".to_string())?;
+        (Intent::new(Verb::ParseCsv).with_parameter("csv", &content[1..close]), &content[close + 1..])
+    } else {
+        let (source, clauses) = content.split_once(char::is_whitespace).unwrap_or((content, ""));
+        (Intent::new(Verb::ParseCsv).with_parameter("source", source), clauses)
+    };
+
+    let words: Vec<&str> = clauses.split_whitespace().collect();
+    let mut i = 0;
+    while i < words.len() {
+        match (words[i], words.get(i + 1)) {
+            ("into", Some(dest)) => intent = intent.with_parameter("into", dest),
+            ("headers", Some(&flag)) if flag == "true" || flag == "false" => {
+                intent = intent.with_parameter("headers", flag);
+            }
+            ("headers", Some(flag)) => return Err(format!("parse-csv headers must be true or false, got '{}'", flag)),
+            _ => return Err(format!("Unexpected '{}' in parse-csv: use [into <var>] [headers false]", words[i])),
+        }
+        i += 2;
+    }
+    Ok(intent)
+}
+
+fn parse_config_intent(input: &str) -> Result<Intent, String> {
+    // config log_file "~/deploy.log"
+    // config                          (show current settings)
+    let content = input.trim_start_matches("config").trim();
+    if content.is_empty() {
+        return Ok(Intent::new(Verb::Config));
+    }
+
+    let (key, value) = content.split_once(' ')
+        .ok_or_else(|| format!("config {} requires a value", content))?;
+    let value = unquote(value.trim());
+    if value.is_empty() {
+        return Err(format!("config {} requires a value", key));
+    }
+
+    Ok(Intent::new(Verb::Config)
+        .with_target(Target::Expression(key.to_string()))
+        .with_parameter("value", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_switch() {
+        let intent = parse_to_intent("switch env { case \"prod\" then set a = 1; case \"dev\" then set a = 2; set b = 3; default set a = 0 }");
+        assert_eq!(intent.unwrap_err(), "Expected 'case' or 'default' in switch, got: set b = 3");
+
+        let intent = parse_to_intent("switch env { case \"prod\" then { set a = 1; set b = 1 }; default set a = 0 }").unwrap();
+        assert_eq!(intent.verb, Verb::Switch);
+        assert_eq!(intent.parameters["case_count"], "1");
+        assert_eq!(intent.parameters["case_0_value"], "prod");
+        assert_eq!(intent.parameters["case_0_body"], "{ set a = 1; set b = 1 }");
+        assert_eq!(intent.parameters["default"], "set a = 0");
+
+        assert!(parse_to_intent("switch env { default set a = 0; default set a = 1 }").is_err());
+        assert!(parse_to_intent("switch env").is_err());
+    }
+
+    #[test]
+    fn test_split_block_statements() {
+        assert_eq!(
+            split_block_statements("set a = 1; if { x; y }\nwriteout(\"a;b\")"),
+            vec!["set a = 1", "if { x; y }", "writeout(\"a;b\")"]
+        );
+    }
+
+    #[test]
+    fn test_parse_try() {
+        let intent = parse_to_intent("try { set a = 1 } catch { set b = _error } finally { set c = 3 }").unwrap();
+        assert_eq!(intent.verb, Verb::Try);
+        assert_eq!(intent.parameters.get("body").unwrap(), "set a = 1");
+        assert_eq!(intent.parameters.get("catch_count").unwrap(), "1");
+        assert_eq!(intent.parameters.get("catch_0_pattern").unwrap(), "");
+        assert_eq!(intent.parameters.get("catch_0_body").unwrap(), "set b = _error");
+        assert_eq!(intent.parameters.get("finally").unwrap(), "set c = 3");
+
+        let intent = parse_to_intent("try{ set s = \"}\" } finally { set c = 3 }").unwrap();
+        assert_eq!(intent.parameters.get("body").unwrap(), "set s = \"}\"");
+        assert_eq!(intent.parameters.get("catch_count").unwrap(), "0");
+
+        assert!(parse_to_intent("try { set a = 1 }").is_err());
+        assert!(parse_to_intent("try { set a = 1 } finally { } catch { }").is_err());
+        assert!(parse_to_intent("try { set a = 1 } finally { } finally { }").is_err());
+        assert!(parse_to_intent("try { set a = 1 } otherwise { }").is_err());
+        assert!(parse_to_intent("try set a = 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_catch_patterns() {
+        let intent = parse_to_intent("try { x } catch /not found/ { a } catch /no such file \\/tmp/ { b } catch { c }").unwrap();
+        assert_eq!(intent.parameters.get("catch_count").unwrap(), "3");
+        assert_eq!(intent.parameters.get("catch_0_pattern").unwrap(), "not found");
+        assert_eq!(intent.parameters.get("catch_1_pattern").unwrap(), "no such file /tmp");
+        assert_eq!(intent.parameters.get("catch_1_body").unwrap(), "b");
+        assert_eq!(intent.parameters.get("catch_2_pattern").unwrap(), "");
+
+        assert!(parse_to_intent("try { x } catch /open { a }").is_err());
+        assert!(parse_to_intent("try { x } catch /(/ { a }").is_err());
+    }
+
+    #[test]
+    fn test_parse_raise() {
+        let intent = parse_to_intent("raise \"quota exceeded\"").unwrap();
+        assert_eq!(intent.verb, Verb::Raise);
+        assert!(matches!(intent.target, Some(Target::Expression(ref m)) if m == "quota exceeded"));
+        let intent = parse_to_intent("raise").unwrap();
+        assert!(matches!(intent.target, Some(Target::Expression(ref m)) if m == "Error raised by script"));
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let intent = parse_to_intent("log warn \"deployed {version}\" fields { env=\"prod, eu\", retries=3 }").unwrap();
+        assert_eq!(intent.verb, Verb::Log);
+        assert_eq!(intent.parameters.get("level").unwrap(), "warning");
+        assert_eq!(intent.parameters.get("field.env").unwrap(), "\"prod, eu\"");
+        assert_eq!(intent.parameters.get("field.retries").unwrap(), "3");
+        assert!(matches!(intent.target, Some(Target::Expression(ref m)) if m == "deployed {version}"));
+
+        assert_eq!(parse_to_intent("log flush").unwrap().parameters.get("action").unwrap(), "flush");
+        assert!(parse_to_intent("log file \"\"").is_err());
+        assert!(parse_to_intent("log verbose \"x\"").is_err());
+        assert!(parse_to_intent("log info \"x\" extra").is_err());
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let intent = parse_to_intent("config log_file \"~/deploy.log\"").unwrap();
+        assert_eq!(intent.verb, Verb::Config);
+        assert_eq!(intent.parameters.get("value").unwrap(), "~/deploy.log");
+        assert!(parse_to_intent("config").unwrap().target.is_none());
+        assert!(parse_to_intent("config log_file").is_err());
+    }
+
+    #[test]
+    fn test_parse_notify() {
+        let intent = parse_to_intent("notify \"Script complete!\" title \"Deploy\"").unwrap();
+        assert_eq!(intent.verb, Verb::Notify);
+        assert_eq!(intent.parameters.get("title").unwrap(), "Deploy");
+        assert_eq!(intent.parameters.get("on_error").unwrap(), "false");
+
+        let intent = parse_to_intent("on-error notify build failed").unwrap();
+        assert!(matches!(intent.target, Some(Target::Expression(ref m)) if m == "build failed"));
+        assert_eq!(intent.parameters.get("title").unwrap(), "Morris");
+        assert_eq!(intent.parameters.get("on_error").unwrap(), "true");
+
+        assert!(parse_to_intent("notify \"done\" loudly").is_err());
+        assert!(parse_to_intent("notify \"\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_env_var() {
+        let intent = parse_to_intent("env-var \"MORRIS_REGION\" into region").unwrap();
+        assert_eq!(intent.verb, Verb::EnvVar);
+        assert!(matches!(intent.target, Some(Target::Expression(ref n)) if n == "MORRIS_REGION"));
+        assert_eq!(intent.parameters.get("into").unwrap(), "region");
+        assert!(parse_to_intent("env-var MORRIS_REGION").is_err());
+    }
+
+    #[test]
+    fn test_parse_feedback() {
+        let intent = parse_to_intent("feedback \"more examples please\"").unwrap();
+        assert_eq!(intent.verb, Verb::Feedback);
+        assert_eq!(intent.parameters.get("action").unwrap(), "add");
+        assert_eq!(intent.parameters.get("message").unwrap(), "more examples please");
+        assert_eq!(parse_to_intent("feedback send").unwrap().parameters.get("action").unwrap(), "send");
+        assert_eq!(parse_to_intent("feedback show").unwrap().parameters.get("action").unwrap(), "show");
+    }
+
+    #[test]
+    fn test_compound_assignment_desugars() {
+        for (input, expected) in [
+            ("set x += 2", "x + (2)"),
+            ("set x -= 1 + 1", "x - (1 + 1)"),
+            ("set x *= 3", "x * (3)"),
+            ("set x /= 4", "x / (4)"),
+        ] {
+            let intent = parse_to_intent(input).unwrap();
+            assert_eq!(intent.parameters.get("value").map(String::as_str), Some(expected), "{}", input);
+        }
+        assert!(parse_to_intent("set x += ").is_err());
+    }
+
+    #[test]
+    fn test_parse_variable_modifier() {
+        let intent = parse_to_intent("set path = \"/etc\" as readonly").unwrap();
+        assert_eq!(intent.parameters.get("modifier").map(String::as_str), Some("readonly"));
+        assert_eq!(intent.parameters.get("value").map(String::as_str), Some("\"/etc\""));
+        let plain = parse_to_intent("set note = \"known as readonly\"").unwrap();
+        assert!(!plain.parameters.contains_key("modifier"));
+    }
+
+    #[test]
+    fn test_parse_unset() {
+        let intent = parse_to_intent("unset total").unwrap();
+        assert_eq!(intent.verb, Verb::Unset);
+        assert!(matches!(intent.target, Some(Target::Variable(ref name)) if name == "total"));
+    }
+
+    #[test]
+    fn test_parse_rename() {
+        let intent = parse_to_intent("rename price as cost --force").unwrap();
+        assert_eq!(intent.verb, Verb::Rename);
+        assert!(matches!(intent.target, Some(Target::Variable(ref name)) if name == "price"));
+        assert_eq!(intent.parameters.get("new_name").map(String::as_str), Some("cost"));
+        assert_eq!(intent.parameters.get("force").map(String::as_str), Some("true"));
+        assert!(parse_to_intent("rename price cost").is_err());
+    }
+
+    #[test]
+    fn test_parse_copy() {
+        let intent = parse_to_intent("copy total as saved").unwrap();
+        assert_eq!(intent.verb, Verb::Copy);
+        assert!(matches!(intent.target, Some(Target::Variable(ref name)) if name == "total"));
+        assert_eq!(intent.parameters.get("into").map(String::as_str), Some("saved"));
+        assert!(parse_to_intent("copy total").is_err());
+    }
+
+    #[test]
+    fn test_parse_swap() {
+        let intent = parse_to_intent("swap left right").unwrap();
+        assert_eq!(intent.verb, Verb::Swap);
+        assert!(matches!(intent.target, Some(Target::Variable(ref name)) if name == "left"));
+        assert_eq!(intent.parameters.get("with").map(String::as_str), Some("right"));
+        assert!(parse_to_intent("swap left").is_err());
+        assert!(parse_to_intent("swap a b c").is_err());
+    }
+
+    #[test]
+    fn test_parse_cast() {
+        let intent = parse_to_intent("cast port as :int").unwrap();
+        assert_eq!(intent.verb, Verb::Cast);
+        assert!(matches!(intent.target, Some(Target::Variable(ref name)) if name == "port"));
+        assert_eq!(intent.parameters.get("type").map(String::as_str), Some("int"));
+        assert!(parse_to_intent("cast port int").is_err());
+    }
+
+    #[test]
+    fn test_parse_each() {
+        let intent = parse_to_intent("each n in [1, 2] { set total = total + n }").unwrap();
+        assert_eq!(intent.verb, Verb::Each);
+        assert!(matches!(intent.target, Some(Target::Expression(ref list)) if list == "[1, 2]"));
+        assert_eq!(intent.parameters.get("item").map(String::as_str), Some("n"));
+        assert_eq!(intent.parameters.get("body").map(String::as_str), Some("set total = total + n"));
+        assert!(parse_to_intent("each n in [1, 2] { set x = n").is_err());
+        assert!(parse_to_intent("each n in [1, 2] { set x = n } extra").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let intent = parse_to_intent("filter scores where { item > 5 } into high").unwrap();
+        assert_eq!(intent.verb, Verb::Filter);
+        assert!(matches!(intent.target, Some(Target::Expression(ref list)) if list == "scores"));
+        assert_eq!(intent.parameters.get("body").map(String::as_str), Some("item > 5"));
+        assert_eq!(intent.parameters.get("into").map(String::as_str), Some("high"));
+        assert!(parse_to_intent("filter scores { item > 5 }").is_err());
+        assert!(parse_to_intent("filter scores where { item > 5 } junk").is_err());
+    }
+
+    #[test]
+    fn test_parse_transform_and_map() {
+        for input in ["transform prices with { item * 2 }", "map prices with { item * 2 }"] {
+            let intent = parse_to_intent(input).unwrap();
+            assert_eq!(intent.verb, Verb::Transform, "{}", input);
+            assert_eq!(intent.parameters.get("body").map(String::as_str), Some("item * 2"));
+        }
+    }
+
+    #[test]
+    fn test_parse_reduce() {
+        let intent = parse_to_intent("reduce nums with { acc + item } starting_from 10 into total").unwrap();
+        assert_eq!(intent.verb, Verb::Reduce);
+        assert_eq!(intent.parameters.get("body").map(String::as_str), Some("acc + item"));
+        assert_eq!(intent.parameters.get("starting_from").map(String::as_str), Some("10"));
+        assert_eq!(intent.parameters.get("into").map(String::as_str), Some("total"));
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        let intent = parse_to_intent("sort people by \"age\" descending").unwrap();
+        assert_eq!(intent.verb, Verb::Sort);
+        assert!(matches!(intent.target, Some(Target::Expression(ref list)) if list == "people"));
+        assert_eq!(intent.parameters.get("by").map(String::as_str), Some("age"));
+        assert_eq!(intent.parameters.get("descending").map(String::as_str), Some("true"));
+        assert!(parse_to_intent("sort a b").is_err());
+        assert!(parse_to_intent("sort people by \"\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_assert() {
+        let intent = parse_to_intent("assert x > 0 message \"x must be positive\"").unwrap();
+        assert_eq!(intent.verb, Verb::Assert);
+        assert!(matches!(intent.target, Some(Target::Expression(ref condition)) if condition == "x > 0"));
+        assert_eq!(intent.parameters.get("message").map(String::as_str), Some("x must be positive"));
+        let plain = parse_to_intent("assert mode == \"a message b\"").unwrap();
+        assert!(!plain.parameters.contains_key("message"));
+    }
+
+    #[test]
+    fn test_parse_benchmark() {
+        let intent = parse_to_intent("benchmark verbose { set a = 1; set b = 2 }").unwrap();
+        assert_eq!(intent.verb, Verb::Benchmark);
+        assert_eq!(intent.parameters.get("body").map(String::as_str), Some("set a = 1; set b = 2"));
+        assert_eq!(intent.parameters.get("verbose").map(String::as_str), Some("true"));
+        assert!(parse_to_intent("benchmark set a = 1").is_err());
+        assert!(parse_to_intent("benchmark { set a = 1 } extra").is_err());
+    }
+
+    #[test]
+    fn test_parse_trace() {
+        for (input, mode) in [("trace", ""), ("trace on", "on"), ("trace off", "off")] {
+            let intent = parse_to_intent(input).unwrap();
+            assert_eq!(intent.verb, Verb::Trace);
+            assert!(matches!(intent.target, Some(Target::Expression(ref m)) if m == mode), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_alias() {
+        let intent = parse_to_intent("alias save_state = \"engine save && history save\"").unwrap();
+        assert_eq!(intent.verb, Verb::Alias);
+        assert!(matches!(intent.target, Some(Target::Expression(ref name)) if name == "save_state"));
+        assert_eq!(intent.parameters.get("expansion").map(String::as_str), Some("engine save && history save"));
+        assert!(parse_to_intent("alias save_state").is_err());
+        assert_eq!(parse_to_intent("unalias save_state").unwrap().verb, Verb::Unalias);
+        assert_eq!(parse_to_intent("aliases").unwrap().verb, Verb::Aliases);
+    }
+
+    #[test]
+    fn test_is_verb_keyword() {
+        for word in ["set", "unset", "aliases", "trace", "exit"] {
+            assert!(is_verb_keyword(word), "{}", word);
+        }
+        assert!(!is_verb_keyword("deploy"));
+    }
+
+    #[test]
+    fn test_parse_export() {
+        let intent = parse_to_intent("export region as \"AWS_REGION\"").unwrap();
+        assert_eq!(intent.verb, Verb::Export);
+        assert!(matches!(intent.target, Some(Target::Expression(ref name)) if name == "region"));
+        assert_eq!(intent.parameters.get("as").map(String::as_str), Some("AWS_REGION"));
+        assert!(parse_to_intent("export * as ALL").is_err());
+        assert!(parse_to_intent("export region as \"\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_snapshot_and_restore() {
+        let intent = parse_to_intent("snapshot \"state.msnap\"").unwrap();
+        assert_eq!(intent.verb, Verb::Snapshot);
+        assert!(matches!(intent.target, Some(Target::File(ref path)) if path == "state.msnap"));
+        let intent = parse_to_intent("restore \"state.msnap\" --replace").unwrap();
+        assert_eq!(intent.verb, Verb::Restore);
+        assert_eq!(intent.parameters.get("replace").map(String::as_str), Some("true"));
+        assert!(parse_to_intent("snapshot \"state.json\"").is_err());
+        assert!(parse_to_intent("snapshot \"x.msnap\" --replace").is_err());
+    }
+
+    #[test]
+    fn test_parse_diff_env() {
+        let intent = parse_to_intent("diff-env \"my before.msnap\" after.msnap").unwrap();
+        assert_eq!(intent.verb, Verb::DiffEnv);
+        assert_eq!(intent.parameters.get("before").map(String::as_str), Some("my before.msnap"));
+        assert_eq!(intent.parameters.get("after").map(String::as_str), Some("after.msnap"));
+        assert!(parse_to_intent("diff-env \"a.msnap\"").is_err());
+        assert!(parse_to_intent("diff-env \"a.msnap b.msnap").is_err());
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        let intent = parse_to_intent("watch a, b c").unwrap();
+        assert_eq!(intent.verb, Verb::Watch);
+        assert_eq!(intent.parameters.get("variables").map(String::as_str), Some("a,b,c"));
+        assert!(parse_to_intent("watch ").is_err());
+    }
+
+    #[test]
+    fn test_parse_capture() {
+        let intent = parse_to_intent("capture out { writeout(\"hi\") }").unwrap();
+        assert_eq!(intent.verb, Verb::Capture);
+        assert!(matches!(&intent.target, Some(Target::Variable(name)) if name == "out"));
+        assert_eq!(intent.parameters.get("body").map(String::as_str), Some("writeout(\"hi\")"));
+        assert!(parse_to_intent("capture out").is_err());
+        assert!(parse_to_intent("capture { writeout(\"hi\") }").is_err());
+        assert!(parse_to_intent("capture out { writeout(\"hi\") } extra").is_err());
+    }
+
+    #[test]
+    fn test_parse_redirect() {
+        let intent = parse_to_intent("redirect \"out.log\" { writeout(\"hi\") }").unwrap();
+        assert_eq!(intent.verb, Verb::Redirect);
+        assert!(matches!(&intent.target, Some(Target::File(path)) if path == "out.log"));
+        assert!(!intent.parameters.contains_key("append"));
+
+        let intent = parse_to_intent("redirect \"a{b}.log\" append { writeout(\"hi\") }").unwrap();
+        assert!(matches!(&intent.target, Some(Target::File(path)) if path == "a{b}.log"));
+        assert!(intent.parameters.contains_key("append"));
+
+        assert!(parse_to_intent("redirect to \"o.txt\" { writeout(\"hi\") }").is_err());
+        assert!(parse_to_intent("redirect o.txt { writeout(\"hi\") }").is_err());
+        assert!(parse_to_intent("redirect \"o.txt\" appendix { writeout(\"hi\") }").is_err());
+        assert!(parse_to_intent("redirect \"o.txt\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_yaml_verbs() {
+        let intent = parse_to_intent("parse-yaml \"name: web\" into config").unwrap();
+        assert_eq!(intent.verb, Verb::ParseYaml);
+        assert_eq!(intent.parameters.get("yaml").map(String::as_str), Some("name: web"));
+        assert_eq!(intent.parameters.get("into").map(String::as_str), Some("config"));
+
+        let intent = parse_to_intent("parse-yaml raw").unwrap();
+        assert_eq!(intent.parameters.get("source").map(String::as_str), Some("raw"));
+        assert!(!intent.parameters.contains_key("into"));
+
+        let intent = parse_to_intent("to-yaml config").unwrap();
+        assert_eq!(intent.verb, Verb::ToYaml);
+        assert_eq!(intent.parameters.get("variable").map(String::as_str), Some("config"));
+    }
+
+    #[test]
+    fn test_parse_csv_verbs() {
+        let intent = parse_to_intent("parse-csv \"a,b\\n1, 2\" into rows headers false").unwrap();
+        assert_eq!(intent.verb, Verb::ParseCsv);
+        assert_eq!(intent.parameters.get("csv").map(String::as_str), Some("a,b\\n1, 2"));
+        assert_eq!(intent.parameters.get("into").map(String::as_str), Some("rows"));
+        assert_eq!(intent.parameters.get("headers").map(String::as_str), Some("false"));
+
+        let intent = parse_to_intent("parse-csv raw into rows").unwrap();
+        assert_eq!(intent.parameters.get("source").map(String::as_str), Some("raw"));
+
+        assert!(parse_to_intent("parse-csv raw headers maybe").is_err());
+        assert!(parse_to_intent("parse-csv raw onto rows").is_err());
+        assert_eq!(parse_to_intent("to-csv rows").unwrap().verb, Verb::ToCsv);
+    }
+}
@@ -1,9 +1,10 @@
 use std::collections::{HashMap, VecDeque};
 use std::env;
 use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
 use shellexpand;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bookmark {
     pub name: String,
     pub path: PathBuf,
@@ -12,7 +13,7 @@ pub struct Bookmark {
     pub annotation: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Volume {
     pub name: String,
     pub path: PathBuf,
@@ -143,6 +144,20 @@ impl Library {
         volumes
     }
     
+    // Used by `restore`; replace drops existing bookmarks and volumes first
+    pub fn restore_shelves(&mut self, bookmarks: Vec<Bookmark>, volumes: Vec<Volume>, replace: bool) {
+        if replace {
+            self.bookmarks.clear();
+            self.volumes.clear();
+        }
+        for bookmark in bookmarks {
+            self.bookmarks.insert(bookmark.name.clone(), bookmark);
+        }
+        for volume in volumes {
+            self.volumes.insert(volume.name.clone(), volume);
+        }
+    }
+    
     pub fn shelve(&mut self) -> String {
         self.shelf = Some(self.current_page.clone());
         let page_name = self.get_page_name(&self.current_page);
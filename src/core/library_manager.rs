@@ -397,7 +397,7 @@ json_set = json-set {variable}.{path} = {value}
 examine = examine {target}
 construct = construct intent {name} with {params} {expression}
 evolve = evolve {intent_name} {action} {params}
-grow = grow {new_intent} from {base_intent}
+grow = grow {composition} with [{intents}]
 reflect = reflect {expression}
 test = test {intent} with {params}
 adopt = adopt {intent_name}"#;
@@ -0,0 +1,236 @@
+// File: src/core/manifest.rs
+use std::collections::BTreeMap;
+use std::fs;
+use serde::Deserialize;
+
+use crate::core::env::Env;
+use crate::core::types::Value;
+
+// Morris manifest (.mmf) - TOML description of what a project's env should look like
+//
+//   name = "billing"
+//
+//   [variables.rate]
+//   type = "float"
+//   min = 0.0
+//   max = 1.0
+//
+//   [variables.owner]
+//   type = "string"
+//   required = false
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub variables: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ManifestEntry {
+    #[serde(rename = "type", default)]
+    pub type_name: Option<String>,
+    #[serde(default = "default_required")]
+    pub required: bool,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+#[derive(Debug, Default)]
+pub struct ManifestReport {
+    pub missing: Vec<String>,
+    pub wrong_type: Vec<(String, String, String)>,   // (name, expected, actual)
+    pub out_of_range: Vec<(String, String)>,          // (name, details)
+    pub in_range: Vec<String>,
+    pub ok: Vec<String>,
+}
+
+impl ManifestReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.wrong_type.is_empty() && self.out_of_range.is_empty()
+    }
+
+    pub fn format_summary(&self) -> String {
+        let mut summary = String::new();
+
+        if !self.missing.is_empty() {
+            summary.push_str(&format!("  ❌ Missing ({}):\n", self.missing.len()));
+            for name in &self.missing {
+                summary.push_str(&format!("    • {}\n", name));
+            }
+        }
+
+        if !self.wrong_type.is_empty() {
+            summary.push_str(&format!("  ❌ Wrong type ({}):\n", self.wrong_type.len()));
+            for (name, expected, actual) in &self.wrong_type {
+                summary.push_str(&format!("    • {}: expected {}, got {}\n", name, expected, actual));
+            }
+        }
+
+        if !self.out_of_range.is_empty() {
+            summary.push_str(&format!("  ⚠️  Out of range ({}):\n", self.out_of_range.len()));
+            for (name, details) in &self.out_of_range {
+                summary.push_str(&format!("    • {}: {}\n", name, details));
+            }
+        }
+
+        if !self.in_range.is_empty() {
+            summary.push_str(&format!("  ✓ Within range ({}): {}\n", self.in_range.len(), self.in_range.join(", ")));
+        }
+
+        if !self.ok.is_empty() {
+            summary.push_str(&format!("  ✓ Present ({}): {}\n", self.ok.len(), self.ok.join(", ")));
+        }
+
+        summary
+    }
+}
+
+impl Manifest {
+    pub fn load(path: &str) -> Result<Self, String> {
+        if !path.ends_with(".mmf") {
+            return Err(format!("Expected .mmf manifest file, got: {}", path));
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read manifest '{}': {}", path, e))?;
+
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Invalid manifest: {}", e))
+    }
+
+    pub fn validate(&self, env: &Env) -> ManifestReport {
+        let mut report = ManifestReport::default();
+
+        for (name, entry) in &self.variables {
+            let value = match env.get_value(name) {
+                Some(value) => value,
+                None => {
+                    if entry.required {
+                        report.missing.push(name.clone());
+                    }
+                    continue;
+                }
+            };
+
+            if let Some(expected) = &entry.type_name {
+                if !type_matches(expected, value) {
+                    report.wrong_type.push((name.clone(), expected.clone(), value.type_name().to_string()));
+                    continue;
+                }
+            }
+
+            if entry.min.is_none() && entry.max.is_none() {
+                report.ok.push(name.clone());
+                continue;
+            }
+
+            let number = match value {
+                Value::Int(i) => *i as f64,
+                Value::Float(f) => *f,
+                Value::Str(s) | Value::Json(s) => s.len() as f64,
+                Value::List(items) => items.len() as f64,
+                Value::Dict(map) => map.len() as f64,
+                Value::Bool(_) | Value::Null => {
+                    report.out_of_range.push((name.clone(), format!("range check not supported for {}", value.type_name())));
+                    continue;
+                }
+            };
+
+            let below = entry.min.is_some_and(|min| number < min);
+            let above = entry.max.is_some_and(|max| number > max);
+
+            if below || above {
+                report.out_of_range.push((name.clone(), format!(
+                    "{} not in [{}, {}]",
+                    value.display(),
+                    entry.min.map_or("-inf".to_string(), |m| m.to_string()),
+                    entry.max.map_or("inf".to_string(), |m| m.to_string()),
+                )));
+            } else {
+                report.in_range.push(name.clone());
+            }
+        }
+
+        report
+    }
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "any" => true,
+        "string" | "str" => matches!(value, Value::Str(_)),
+        "int" | "integer" => matches!(value, Value::Int(_)),
+        "float" | "number" => matches!(value, Value::Float(_) | Value::Int(_)),
+        "bool" | "boolean" => matches!(value, Value::Bool(_)),
+        "list" => matches!(value, Value::List(_)),
+        "dict" | "dictionary" => matches!(value, Value::Dict(_)),
+        "json" => matches!(value, Value::Json(_)),
+        other => value.type_name() == other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+name = "billing"
+
+[variables.rate]
+type = "float"
+min = 0.0
+max = 1.0
+
+[variables.owner]
+type = "string"
+
+[variables.note]
+required = false
+"#;
+
+    #[test]
+    fn test_validate_reports_each_problem() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        assert_eq!(manifest.name.as_deref(), Some("billing"));
+
+        let mut env = Env::new();
+        env.set_direct("rate", Value::Float(1.5));
+        let report = manifest.validate(&env);
+        assert!(!report.is_clean());
+        assert_eq!(report.missing, vec!["owner"]);
+        assert_eq!(report.out_of_range.len(), 1);
+        assert_eq!(report.out_of_range[0].0, "rate");
+
+        env.set_direct("owner", Value::Int(7));
+        let report = manifest.validate(&env);
+        assert_eq!(report.wrong_type, vec![("owner".to_string(), "string".to_string(), "int".to_string())]);
+    }
+
+    #[test]
+    fn test_validate_clean_env() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        let mut env = Env::new();
+        env.set_direct("rate", Value::Int(1));
+        env.set_direct("owner", Value::Str("ops".to_string()));
+        let report = manifest.validate(&env);
+        assert!(report.is_clean());
+        assert_eq!(report.in_range, vec!["rate"]);
+        assert_eq!(report.ok, vec!["owner"]);
+    }
+
+    #[test]
+    fn test_load_rejects_other_extensions() {
+        assert!(Manifest::load("project.toml").unwrap_err().starts_with("Expected .mmf"));
+        assert!(Manifest::parse("variables = 3").unwrap_err().starts_with("Invalid manifest"));
+    }
+}
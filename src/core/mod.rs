@@ -14,4 +14,16 @@ pub mod propagation;
 pub mod transaction;  
 pub mod library_manager;
 pub mod startup_validator;
-pub mod safety_guard;
\ No newline at end of file
+pub mod safety_guard;
+pub mod manifest;
+pub mod schema;
+
+pub mod script_log;
+pub mod notify;
+pub mod project_config;
+pub mod script;
+pub mod tutorial;
+pub mod examples;
+pub mod feedback;
+pub mod alias;
+pub mod snapshot;
\ No newline at end of file
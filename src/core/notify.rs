@@ -0,0 +1,66 @@
+// File: src/core/notify.rs
+use std::process::Command;
+
+// Desktop notifications through the platform's own tooling:
+// osascript on macOS, PowerShell toast on Windows, notify-send elsewhere
+pub fn send_notification(title: &str, message: &str) -> Result<(), String> {
+    match notification_command(title, message).status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("Notification command failed ({})", status)),
+        Err(e) => Err(format!("Cannot send notification: {}", e)),
+    }
+}
+
+fn notification_command(title: &str, message: &str) -> Command {
+    if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification \"{}\" with title \"{}\"",
+            escape_quotes(message),
+            escape_quotes(title)
+        );
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else if cfg!(windows) {
+        let script = format!(
+            "[reflection.assembly]::loadwithpartialname('System.Windows.Forms') | Out-Null; \
+             $n = New-Object System.Windows.Forms.NotifyIcon; \
+             $n.Icon = [System.Drawing.SystemIcons]::Information; \
+             $n.Visible = $true; \
+             $n.ShowBalloonTip(5000, '{}', '{}', 'Info')",
+            title.replace('\'', "''"),
+            message.replace('\'', "''")
+        );
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", &script]);
+        command
+    } else {
+        // `--` keeps a title or message starting with '-' from being read as an option
+        let mut command = Command::new("notify-send");
+        command.args(["--", title, message]);
+        command
+    }
+}
+
+fn escape_quotes(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_quotes() {
+        assert_eq!(escape_quotes(r#"say "hi" \ bye"#), r#"say \"hi\" \\ bye"#);
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[test]
+    fn test_notify_send_ends_options() {
+        let command = notification_command("-u critical", "--help");
+        assert_eq!(command.get_program(), "notify-send");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["--", "-u critical", "--help"]);
+    }
+}
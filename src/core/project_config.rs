@@ -0,0 +1,21 @@
+// File: src/core/project_config.rs
+use std::fs;
+
+// Written by `morris --init` and read from the current directory:
+//   [script] strict = true          same as --strict
+//   [log] file = "~/deploy.log"     default target of the log verb
+//   [feedback] url = "https://..."  endpoint for `feedback send`
+pub const PROJECT_CONFIG_FILE: &str = ".morris_config.toml";
+
+// A missing or unparsable file reads as no settings
+pub fn load() -> Option<toml::Table> {
+    fs::read_to_string(PROJECT_CONFIG_FILE).ok()?.parse::<toml::Table>().ok()
+}
+
+pub fn get_str(section: &str, key: &str) -> Option<String> {
+    load()?.get(section)?.get(key)?.as_str().map(|value| value.to_string())
+}
+
+pub fn get_bool(section: &str, key: &str) -> Option<bool> {
+    load()?.get(section)?.get(key)?.as_bool()
+}
@@ -2,11 +2,20 @@
 use std::collections::HashSet;
 use crate::core::env::Env;
 use crate::core::expr::evaluate;
+use crate::output::Printer;
 
 pub fn propagate_from(env: &mut Env, changed_var: &str) -> Result<Vec<String>, String> {
+    propagate_from_all(env, &[changed_var])
+}
+
+// One pass for several changed variables, so a dependent shared by them is
+// recomputed once rather than once per change
+pub fn propagate_from_all(env: &mut Env, changed_vars: &[&str]) -> Result<Vec<String>, String> {
     let mut updated = Vec::new();
-    let mut queue = vec![changed_var.to_string()];
+    let mut queue: Vec<String> = changed_vars.iter().map(|name| name.to_string()).collect();
     let mut processed = HashSet::new();
+    let trace = env.trace_enabled().then(Printer::new);
+    let cause = changed_vars.join(", ");
     
     while let Some(var_name) = queue.pop() {
         if processed.contains(&var_name) {
@@ -25,6 +34,17 @@ pub fn propagate_from(env: &mut Env, changed_var: &str) -> Result<Vec<String>, S
                 continue;
             }
             
+            // Lazy variables are re-armed instead of recomputed; the next read evaluates them
+            if env.get_variable(dependent).is_some_and(|var| var.is_lazy) {
+                if let Some(expr) = env.get_expression(dependent).cloned() {
+                    if let Some(var) = env.get_variable_mut(dependent) {
+                        var.lazy_expr = Some(expr);
+                    }
+                }
+                queue.push(dependent.to_string());
+                continue;
+            }
+            
             // Check propagation control - ONLY call should_propagate once per dependent
             if let Some(var) = env.get_variable_mut(dependent) {
                 
@@ -36,6 +56,9 @@ pub fn propagate_from(env: &mut Env, changed_var: &str) -> Result<Vec<String>, S
                 
                 // If propagation is allowed, proceed with evaluation and update
                 if let Some(expr) = env.get_expression(dependent) {
+                    if let Some(printer) = &trace {
+                        printer.dim(&format!("  propagating from {} → {} because {} changed", var_name, dependent, cause));
+                    }
                     
                     match evaluate(expr, env) {
                         Ok(new_value) => {
@@ -69,5 +92,11 @@ pub fn propagate_from(env: &mut Env, changed_var: &str) -> Result<Vec<String>, S
         }
     }
     
+    let touched: Vec<String> = changed_vars.iter()
+        .map(|name| name.to_string())
+        .chain(updated.iter().cloned())
+        .collect();
+    env.after_propagation(&touched);
+    
     Ok(updated)
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 use std::fmt;
+use std::sync::OnceLock;
 
 impl fmt::Display for crate::core::intent::Verb {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -7,6 +8,26 @@ impl fmt::Display for crate::core::intent::Verb {
     }
 }
 
+// Permissions fixed once by main() at startup, from the command line or the launching
+// environment. Later env changes (a script's `export`, set_var) cannot turn them on.
+static SANDBOX: OnceLock<bool> = OnceLock::new();
+static ALLOW_HTTP: OnceLock<bool> = OnceLock::new();
+
+pub fn set_sandbox(sandbox: bool) {
+    let _ = SANDBOX.set(sandbox);
+}
+
+fn sandboxed() -> bool {
+    SANDBOX.get().copied().unwrap_or(false)
+}
+
+pub fn set_allow_http(allow: bool) {
+    let _ = ALLOW_HTTP.set(allow);
+}
+
+fn http_allowed() -> bool {
+    ALLOW_HTTP.get().copied().unwrap_or(false)
+}
 
 pub struct SafetyGuard {
     pub blocked_intents: HashSet<String>,
@@ -85,6 +106,62 @@ impl SafetyGuard {
         Ok(())
     }
     
+    // Notifications leave the process (and can show on a lock screen), so every one is recorded
+    pub fn audit_notification(&self, title: &str, message: &str) {
+        eprintln!("[safety] Sending desktop notification: {} - {}", title, message);
+    }
+    
+    // Requests made on the user's behalf (feedback send, http_get/http_post) must be
+    // switched on explicitly and are never allowed in sandbox mode
+    pub fn validate_http(&self, method: &str, url: &str) -> Result<(), String> {
+        if sandboxed() {
+            return Err("HTTP requests are not permitted in sandbox mode".to_string());
+        }
+        if !http_allowed() {
+            return Err("HTTP requests are disabled; start morris with --allow-http (or MORRIS_ALLOW_HTTP=1)".to_string());
+        }
+        self.validate_network(method, url)
+    }
+    
+    // Outgoing HTTP is only allowed to http(s) URLs, and plain http only to this machine
+    pub fn validate_network(&self, method: &str, url: &str) -> Result<(), String> {
+        if self.blocked_intents.contains("network") {
+            return Err("Network access is blocked by safety policy".to_string());
+        }
+        
+        let rest = if let Some(rest) = url.strip_prefix("https://") {
+            rest
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            let host = url_host(rest);
+            if !matches!(host, "localhost" | "127.0.0.1" | "[::1]") {
+                return Err(format!("Refusing plain http to '{}': use https", host));
+            }
+            rest
+        } else {
+            return Err(format!("Unsupported URL '{}': expected http(s)://", url));
+        };
+        
+        if rest.is_empty() {
+            return Err(format!("URL '{}' has no host", url));
+        }
+        
+        eprintln!("[safety] Network {} {}", method, url);
+        Ok(())
+    }
+    
+    // exec_shell() can do anything the user can, so it is refused outright in sandbox mode
+    pub fn validate_shell(&self, command: &str) -> Result<(), String> {
+        if sandboxed() {
+            return Err("exec_shell() is not permitted in sandbox mode".to_string());
+        }
+        if self.blocked_intents.contains("shell") {
+            return Err("Shell access is blocked by safety policy".to_string());
+        }
+        
+        eprintln!("[safety] Shell: {}", command);
+        Ok(())
+    }
+    
     fn validate_definition_safety(&self, intent: &crate::core::intent::Intent) -> Result<(), String> {
         let target_str = intent.target_string();
         
@@ -184,4 +261,57 @@ impl Clone for SafetyGuard {
             current_depth: 0,  // Reset depth for new context
         }
     }
-}
\ No newline at end of file
+}
+
+// Host part of a URL after its scheme: drops any user@ prefix and the port,
+// keeping IPv6 literals in their brackets, e.g. user@[::1]:8080/x -> [::1]
+fn url_host(rest: &str) -> &str {
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    match authority.find(']') {
+        Some(end) if authority.starts_with('[') => &authority[..=end],
+        _ => authority.split(':').next().unwrap_or(""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_network_schemes() {
+        let guard = SafetyGuard::new().unwrap();
+        assert!(guard.validate_network("GET", "https://example.com/data").is_ok());
+        assert!(guard.validate_network("GET", "http://localhost:8080/health").is_ok());
+        assert!(guard.validate_network("GET", "http://example.com/").unwrap_err().contains("use https"));
+        assert!(guard.validate_network("GET", "ftp://example.com/").is_err());
+        assert!(guard.validate_network("GET", "https://").is_err());
+    }
+
+    #[test]
+    fn test_http_needs_startup_permission() {
+        // Only main() grants it; setting the variable afterwards changes nothing
+        std::env::set_var("MORRIS_ALLOW_HTTP", "1");
+        let guard = SafetyGuard::new().unwrap();
+        assert!(guard.validate_http("POST", "https://example.com/feedback").unwrap_err().contains("--allow-http"));
+    }
+
+    #[test]
+    fn test_sandbox_is_fixed_at_startup() {
+        // Only main() can turn the sandbox on; a later MORRIS_SANDBOX changes nothing
+        std::env::set_var("MORRIS_SANDBOX", "1");
+        let guard = SafetyGuard::new().unwrap();
+        assert!(guard.validate_shell("true").is_ok());
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(url_host("example.com/path"), "example.com");
+        assert_eq!(url_host("localhost:8080?q=1"), "localhost");
+        assert_eq!(url_host("user@[::1]:8080/x"), "[::1]");
+        assert_eq!(url_host("localhost@evil.com/"), "evil.com");
+        let guard = SafetyGuard::new().unwrap();
+        assert!(guard.validate_network("GET", "http://[::1]:8080/health").is_ok());
+        assert!(guard.validate_network("GET", "http://localhost@evil.com/").is_err());
+    }
+}
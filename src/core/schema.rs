@@ -0,0 +1,254 @@
+// File: src/core/schema.rs
+use crate::core::types::Value;
+
+// Named record layouts registered with:
+//   schema "user" { name: string, age: int(18..90), joined: timestamp, active: bool }
+// A trailing `?` on a field name marks it optional: `nickname?: string`
+#[derive(Debug, Clone)]
+pub struct Schema {
+    pub name: String,
+    pub fields: Vec<SchemaField>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaField {
+    pub name: String,
+    pub field_type: FieldType,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    String,
+    Int,
+    Float,
+    Bool,
+    Timestamp,
+    List,
+    Dict,
+    Any,
+}
+
+impl FieldType {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "string" | "str" => Some(FieldType::String),
+            "int" | "integer" => Some(FieldType::Int),
+            "float" | "number" => Some(FieldType::Float),
+            "bool" | "boolean" => Some(FieldType::Bool),
+            "timestamp" | "datetime" => Some(FieldType::Timestamp),
+            "list" => Some(FieldType::List),
+            "dict" => Some(FieldType::Dict),
+            "any" => Some(FieldType::Any),
+            _ => None,
+        }
+    }
+
+    pub fn accepts(&self, value: &Value) -> bool {
+        match self {
+            FieldType::String => matches!(value, Value::Str(_)),
+            FieldType::Int => matches!(value, Value::Int(_)),
+            FieldType::Float => matches!(value, Value::Float(_) | Value::Int(_)),
+            FieldType::Bool => matches!(value, Value::Bool(_)),
+            FieldType::Timestamp => match value {
+                Value::Str(s) => chrono::DateTime::parse_from_rfc3339(s).is_ok(),
+                Value::Int(_) => true,
+                _ => false,
+            },
+            FieldType::List => matches!(value, Value::List(_)),
+            FieldType::Dict => matches!(value, Value::Dict(_)),
+            FieldType::Any => true,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldType::String => "string",
+            FieldType::Int => "int",
+            FieldType::Float => "float",
+            FieldType::Bool => "bool",
+            FieldType::Timestamp => "timestamp",
+            FieldType::List => "list",
+            FieldType::Dict => "dict",
+            FieldType::Any => "any",
+        }
+    }
+}
+
+impl Schema {
+    pub fn parse(name: &str, spec: &str) -> Result<Self, String> {
+        let spec = spec.trim().trim_start_matches('{').trim_end_matches('}');
+        let mut fields = Vec::new();
+
+        for part in spec.split([',', '\n']) {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+
+            let (field_name, type_spec) = part.split_once(':')
+                .ok_or_else(|| format!("Schema field '{}' needs a type (name: type)", part))?;
+
+            let field_name = field_name.trim();
+            let (field_name, optional) = match field_name.strip_suffix('?') {
+                Some(stripped) => (stripped.trim(), true),
+                None => (field_name, false),
+            };
+
+            if field_name.is_empty() {
+                return Err(format!("Schema field '{}' has no name", part));
+            }
+
+            let type_spec = type_spec.trim();
+            let (type_name, range) = match type_spec.find('(') {
+                Some(open) if type_spec.ends_with(')') => {
+                    (&type_spec[..open], Some(&type_spec[open + 1..type_spec.len() - 1]))
+                }
+                _ => (type_spec, None),
+            };
+
+            let field_type = FieldType::parse(type_name.trim())
+                .ok_or_else(|| format!("Unknown schema type '{}' for field '{}'", type_name.trim(), field_name))?;
+
+            let (min, max) = match range {
+                Some(range) => parse_range(range)?,
+                None => (None, None),
+            };
+
+            fields.push(SchemaField {
+                name: field_name.to_string(),
+                field_type,
+                min,
+                max,
+                optional,
+            });
+        }
+
+        if fields.is_empty() {
+            return Err(format!("Schema '{}' has no fields", name));
+        }
+
+        Ok(Self { name: name.to_string(), fields })
+    }
+
+    // Check one record against the schema, returning (field, message) for every problem found
+    pub fn validate_row(&self, row: &Value) -> Vec<(String, String)> {
+        let map = match row {
+            Value::Dict(map) => map,
+            other => return vec![("*".to_string(), format!("expected dict, got {}", other.type_name()))],
+        };
+
+        let mut errors = Vec::new();
+        for field in &self.fields {
+            let value = match map.get(&field.name) {
+                Some(value) => value,
+                None => {
+                    if !field.optional {
+                        errors.push((field.name.clone(), "missing required field".to_string()));
+                    }
+                    continue;
+                }
+            };
+
+            if !field.field_type.accepts(value) {
+                errors.push((field.name.clone(), format!("expected {}, got {}", field.field_type.name(), value.type_name())));
+                continue;
+            }
+
+            let number = match value {
+                Value::Int(i) => Some(*i as f64),
+                Value::Float(f) => Some(*f),
+                _ => None,
+            };
+            if let Some(number) = number {
+                if field.min.is_some_and(|min| number < min) || field.max.is_some_and(|max| number > max) {
+                    errors.push((field.name.clone(), format!("{} out of range {}..{}",
+                        value.display(),
+                        field.min.map(|m| Value::Float(m).to_string()).unwrap_or_default(),
+                        field.max.map(|m| Value::Float(m).to_string()).unwrap_or_default())));
+                }
+            }
+        }
+
+        for key in map.keys() {
+            if !self.fields.iter().any(|field| &field.name == key) {
+                errors.push((key.clone(), "field not declared in schema".to_string()));
+            }
+        }
+
+        errors
+    }
+
+    pub fn describe(&self) -> String {
+        let parts: Vec<String> = self.fields.iter()
+            .map(|field| {
+                let mut s = format!("{}{}: {}", field.name, if field.optional { "?" } else { "" }, field.field_type.name());
+                if field.min.is_some() || field.max.is_some() {
+                    s.push_str(&format!("({}..{})",
+                        field.min.map(|m| Value::Float(m).to_string()).unwrap_or_default(),
+                        field.max.map(|m| Value::Float(m).to_string()).unwrap_or_default()));
+                }
+                s
+            })
+            .collect();
+        format!("{{ {} }}", parts.join(", "))
+    }
+}
+
+fn parse_range(range: &str) -> Result<(Option<f64>, Option<f64>), String> {
+    let (low, high) = range.split_once("..")
+        .ok_or_else(|| format!("Invalid range '{}', expected min..max", range))?;
+
+    let parse_bound = |s: &str| -> Result<Option<f64>, String> {
+        let s = s.trim();
+        if s.is_empty() {
+            Ok(None)
+        } else {
+            s.parse::<f64>().map(Some).map_err(|_| format!("Invalid range bound '{}'", s))
+        }
+    };
+
+    Ok((parse_bound(low)?, parse_bound(high)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fields() {
+        let schema = Schema::parse("user", "{ name: string, age: int(18..99), note?: str, ts: timestamp(-7..) }").unwrap();
+        assert_eq!(schema.fields.len(), 4);
+        assert_eq!(schema.fields[1].field_type, FieldType::Int);
+        assert_eq!((schema.fields[1].min, schema.fields[1].max), (Some(18.0), Some(99.0)));
+        assert!(schema.fields[2].optional);
+        assert_eq!((schema.fields[3].min, schema.fields[3].max), (Some(-7.0), None));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Schema::parse("s", "{ }").unwrap_err(), "Schema 's' has no fields");
+        assert_eq!(Schema::parse("s", "{ age }").unwrap_err(), "Schema field 'age' needs a type (name: type)");
+        assert_eq!(Schema::parse("s", "{ age: number8 }").unwrap_err(), "Unknown schema type 'number8' for field 'age'");
+        assert_eq!(Schema::parse("s", "{ age: int(a..3) }").unwrap_err(), "Invalid range bound 'a'");
+    }
+
+    #[test]
+    fn test_validate_row() {
+        let schema = Schema::parse("user", "{ name: string, age: int(0..120), nick?: string }").unwrap();
+        let row = |pairs: &[(&str, Value)]| Value::Dict(pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect());
+
+        assert!(schema.validate_row(&row(&[("name", Value::Str("ann".to_string())), ("age", Value::Int(30))])).is_empty());
+
+        let errors = schema.validate_row(&row(&[("age", Value::Int(200)), ("extra", Value::Bool(true))]));
+        assert!(errors.contains(&("name".to_string(), "missing required field".to_string())));
+        assert!(errors.iter().any(|(field, message)| field == "age" && message.contains("out of range")));
+        assert!(errors.contains(&("extra".to_string(), "field not declared in schema".to_string())));
+
+        let errors = schema.validate_row(&row(&[("name", Value::Int(1)), ("age", Value::Int(1))]));
+        assert_eq!(errors, vec![("name".to_string(), "expected string, got int".to_string())]);
+        assert_eq!(schema.validate_row(&Value::Int(1))[0].1, "expected dict, got int");
+    }
+}
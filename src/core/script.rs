@@ -0,0 +1,118 @@
+// File: src/core/script.rs
+
+// A complete statement from a script file: one line, or a `{ ... }` block /
+// continued expression gathered from several lines. `line` is 1-based.
+pub struct ScriptStatement {
+    pub line: usize,
+    pub text: String,
+}
+
+pub fn split_script_statements(content: &str) -> Vec<ScriptStatement> {
+    let mut statements = Vec::new();
+    let mut accumulated_statement = String::new();
+    let mut block_start_line = 0;
+    let mut brace_depth = 0;
+    
+    for (line_num, original_line) in content.lines().enumerate() {
+        let line = original_line.trim();
+        
+        let line_without_comment = if let Some(comment_start) = line.find('#') {
+            line[..comment_start].trim()
+        } else {
+            line
+        };
+        
+        // Skip empty lines and comments
+        if line_without_comment.is_empty() {
+            continue;
+        }
+        
+        // Inside a `{ ... }` block or a continued expression: keep accumulating
+        if !accumulated_statement.is_empty() {
+            accumulated_statement.push('\n');
+            accumulated_statement.push_str(line_without_comment);
+            brace_depth += brace_delta(line_without_comment);
+            
+            let explicit_end = line_without_comment == ";;";
+            if explicit_end || (brace_depth <= 0 && !is_multiline_block_start(line_without_comment)) {
+                let text = accumulated_statement.trim_end_matches(";;").trim_end().trim_end_matches(';').to_string();
+                statements.push(ScriptStatement { line: block_start_line + 1, text });
+                accumulated_statement.clear();
+                brace_depth = 0;
+            }
+            continue;
+        }
+        
+        // Statements may optionally be terminated with a semicolon
+        let statement_content = line_without_comment.strip_suffix(';').unwrap_or(line_without_comment).trim_end();
+        
+        // Detect multiline start: unbalanced `{` or a trailing `|`, `when`, `and`, `or`
+        if !line_without_comment.ends_with(';') && is_multiline_block_start(statement_content) {
+            accumulated_statement.push_str(statement_content);
+            block_start_line = line_num;
+            brace_depth = brace_delta(statement_content);
+            continue;
+        }
+        
+        statements.push(ScriptStatement { line: line_num + 1, text: statement_content.to_string() });
+    }
+    
+    // Unterminated block: hand it over as-is so the parser can report the problem
+    if !accumulated_statement.is_empty() {
+        statements.push(ScriptStatement {
+            line: block_start_line + 1,
+            text: accumulated_statement.trim_end_matches(';').to_string(),
+        });
+    }
+    
+    statements
+}
+
+// Net change in `{`/`}` nesting for a line, ignoring braces inside quoted strings
+fn brace_delta(line: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    
+    for c in line.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' => quote = Some(c),
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+    
+    depth
+}
+
+fn is_multiline_block_start(line: &str) -> bool {
+    let line = line.trim_end();
+    
+    // Blocks: `define intent ... {`, `switch x {`, `try {`, multi-line dicts
+    if brace_delta(line) > 0 {
+        return true;
+    }
+    
+    // Multi-line conditionals and complex expressions that likely continue
+    line.ends_with('|') ||
+    matches!(line.split_whitespace().last(), Some("when") | Some("and") | Some("or"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_script_statements_tracks_lines() {
+        let content = "# comment\nset a = 1\n\nswitch a {\n  case 1 then set b = 2\n}\nset c = 3\n";
+        let statements = split_script_statements(content);
+        let lines: Vec<usize> = statements.iter().map(|s| s.line).collect();
+        assert_eq!(lines, [2, 4, 7]);
+        assert!(statements[1].text.starts_with("switch a {"));
+        assert!(statements[1].text.ends_with('}'));
+    }
+}
@@ -0,0 +1,118 @@
+// File: src/core/script_log.rs
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::core::types::Value;
+
+// JSON-lines log written by the `log` verb, one object per entry:
+//   {"timestamp":"...","level":"info","message":"...","script":"deploy.msh","line":12,"fields":{...}}
+#[derive(Debug, Clone)]
+pub struct ScriptLog {
+    path: PathBuf,
+}
+
+impl ScriptLog {
+    // ~/.morris_script.log unless the project config sets [log] file
+    pub fn new() -> Self {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        let mut log = Self { path: home.join(".morris_script.log") };
+        if let Some(path) = crate::core::project_config::get_str("log", "file") {
+            log.set_path(&path);
+        }
+        log
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn set_path(&mut self, path: &str) {
+        self.path = PathBuf::from(shellexpand::tilde(path).to_string());
+    }
+
+    pub fn write(
+        &self,
+        level: &str,
+        message: &str,
+        script: Option<&str>,
+        line: Option<usize>,
+        fields: &BTreeMap<String, Value>,
+    ) -> Result<(), String> {
+        let mut entry = serde_json::json!({
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "level": level,
+            "message": message,
+        });
+
+        if let Some(script) = script {
+            entry["script"] = serde_json::json!(script);
+        }
+        if let Some(line) = line {
+            entry["line"] = serde_json::json!(line);
+        }
+        if !fields.is_empty() {
+            let fields: serde_json::Map<String, serde_json::Value> = fields.iter()
+                .map(|(key, value)| (key.clone(), crate::core::builtins::convert_to_json_value(value)))
+                .collect();
+            entry["fields"] = serde_json::Value::Object(fields);
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Cannot open log file '{}': {}", self.path.display(), e))?;
+
+        writeln!(file, "{}", entry)
+            .map_err(|e| format!("Cannot write to log file '{}': {}", self.path.display(), e))
+    }
+
+    pub fn flush(&self) -> Result<(), String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Cannot open log file '{}': {}", self.path.display(), e))?;
+
+        file.sync_all()
+            .map_err(|e| format!("Cannot sync log file '{}': {}", self.path.display(), e))
+    }
+}
+
+impl Default for ScriptLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_appends_json_lines() {
+        let path = std::env::temp_dir().join(format!("morris_script_log_{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut log = ScriptLog::new();
+        log.set_path(path.to_str().unwrap());
+
+        let mut fields = BTreeMap::new();
+        fields.insert("retries".to_string(), Value::Int(3));
+        log.write("info", "deployed", Some("deploy.msh"), Some(12), &fields).unwrap();
+        log.write("error", "failed", None, None, &BTreeMap::new()).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let entries: Vec<serde_json::Value> = content.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["level"], "info");
+        assert_eq!(entries[0]["script"], "deploy.msh");
+        assert_eq!(entries[0]["line"], 12);
+        assert_eq!(entries[0]["fields"]["retries"], 3);
+        assert_eq!(entries[1]["message"], "failed");
+        assert!(entries[1].get("fields").is_none());
+    }
+}
@@ -0,0 +1,192 @@
+// File: src/core/snapshot.rs
+use std::collections::BTreeMap;
+use std::fs;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+
+use crate::core::env::Env;
+use crate::core::library::{Bookmark, Library, Volume};
+use crate::core::types::Variable;
+
+// Bump whenever the layout below changes incompatibly
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+// Full checkpoint written by `snapshot "file.msnap"`. Unlike .menv files it keeps
+// every variable flag (types, frozen, readonly, propagation limits) plus the
+// library's bookmarks and volumes
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub schema_version: u32,
+    pub morris_version: String,
+    pub created: DateTime<Utc>,
+    pub variables: BTreeMap<String, Variable>,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    #[serde(default)]
+    pub volumes: Vec<Volume>,
+}
+
+impl Snapshot {
+    pub fn capture(env: &Env, library: &Library) -> Self {
+        Self {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            morris_version: env!("CARGO_PKG_VERSION").to_string(),
+            created: Utc::now(),
+            variables: env.snapshot_variables(),
+            bookmarks: library.list_bookmarks().into_iter().cloned().collect(),
+            volumes: library.list_volumes().into_iter().cloned().collect(),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to encode snapshot: {}", e))?;
+        // Same write-then-rename as save_env, so a crash never leaves half a snapshot
+        let temp_path = format!("{}.tmp", path);
+        fs::write(&temp_path, json)
+            .map_err(|e| format!("Cannot write snapshot '{}': {}", path, e))?;
+        fs::rename(&temp_path, path)
+            .map_err(|e| format!("Failed to finalize snapshot '{}': {}", path, e))
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read snapshot '{}': {}", path, e))?;
+        let snapshot: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid snapshot '{}': {}", path, e))?;
+
+        if snapshot.schema_version > SNAPSHOT_SCHEMA_VERSION {
+            return Err(format!("Snapshot '{}' uses schema version {}, this morris only reads up to {}",
+                path, snapshot.schema_version, SNAPSHOT_SCHEMA_VERSION));
+        }
+        Ok(snapshot)
+    }
+
+    // Compare against a later snapshot, by value and by expression
+    pub fn diff(&self, after: &Snapshot) -> SnapshotDiff {
+        let mut diff = SnapshotDiff::default();
+
+        for (name, var) in &self.variables {
+            match after.variables.get(name) {
+                None => diff.removed.push((name.clone(), var.value.display())),
+                Some(new_var) => {
+                    if var.value != new_var.value {
+                        diff.changed.push((name.clone(), var.value.display(), new_var.value.display()));
+                    } else if var.expression != new_var.expression {
+                        diff.changed.push((name.clone(),
+                            format!("expression {}", var.expression.as_deref().unwrap_or("(direct)")),
+                            new_var.expression.as_deref().unwrap_or("(direct)").to_string()));
+                    }
+                }
+            }
+        }
+
+        for (name, var) in &after.variables {
+            if !self.variables.contains_key(name) {
+                diff.added.push((name.clone(), var.value.display()));
+            }
+        }
+
+        diff
+    }
+
+    // Returns the restored and the skipped (frozen or readonly) variable names
+    pub fn apply(self, env: &mut Env, library: &mut Library, replace: bool) -> Result<(Vec<String>, Vec<String>), String> {
+        let names = env.restore_variables(self.variables, replace)?;
+        library.restore_shelves(self.bookmarks, self.volumes, replace);
+        Ok(names)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SnapshotDiff {
+    pub added: Vec<(String, String)>,             // (name, value)
+    pub removed: Vec<(String, String)>,           // (name, value)
+    pub changed: Vec<(String, String, String)>,   // (name, before, after)
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    // git-diff style: `-` removed, `+` added, `~` changed
+    pub fn format(&self, before: &str, after: &str) -> String {
+        let mut output = format!("--- {}\n+++ {}\n", before, after);
+        for (name, value) in &self.removed {
+            output.push_str(&format!("- {} = {}\n", name, value));
+        }
+        for (name, value) in &self.added {
+            output.push_str(&format!("+ {} = {}\n", name, value));
+        }
+        for (name, old, new) in &self.changed {
+            output.push_str(&format!("~ {}: {} → {}\n", name, old, new));
+        }
+        output.push_str(&format!("{} added, {} removed, {} changed",
+            self.added.len(), self.removed.len(), self.changed.len()));
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Value;
+
+    fn temp_path(tag: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("morris_test_{}_{}.msnap", tag, std::process::id()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let mut env = Env::new();
+        env.set_direct("port", Value::Int(8080));
+        env.mark_readonly("port").unwrap();
+        let path = temp_path("round_trip");
+        Snapshot::capture(&env, &Library::new()).save(&path).unwrap();
+
+        let snapshot = Snapshot::load(&path).unwrap();
+        assert_eq!(snapshot.schema_version, SNAPSHOT_SCHEMA_VERSION);
+        let port = &snapshot.variables["port"];
+        assert_eq!(port.value, Value::Int(8080));
+        assert!(port.is_readonly);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_load_rejects_newer_schema() {
+        let path = temp_path("newer");
+        let mut snapshot = Snapshot::capture(&Env::new(), &Library::new());
+        snapshot.schema_version = SNAPSHOT_SCHEMA_VERSION + 1;
+        snapshot.save(&path).unwrap();
+        let err = Snapshot::load(&path).unwrap_err();
+        assert!(err.contains("uses schema version 2"), "{}", err);
+        fs::remove_file(&path).ok();
+        assert!(Snapshot::load(&path).unwrap_err().starts_with("Cannot read snapshot"));
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed() {
+        let mut env = Env::new();
+        env.set_direct("kept", Value::Int(1));
+        env.set_direct("gone", Value::Int(2));
+        env.set_direct("moved", Value::Int(3));
+        let before = Snapshot::capture(&env, &Library::new());
+
+        let mut env = Env::new();
+        env.set_direct("kept", Value::Int(1));
+        env.set_direct("moved", Value::Int(4));
+        env.set_direct("new", Value::Str("n".to_string()));
+        let after = Snapshot::capture(&env, &Library::new());
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.removed, vec![("gone".to_string(), "2".to_string())]);
+        assert_eq!(diff.added, vec![("new".to_string(), "\"n\"".to_string())]);
+        assert_eq!(diff.changed, vec![("moved".to_string(), "3".to_string(), "4".to_string())]);
+        assert!(diff.format("a", "b").ends_with("1 added, 1 removed, 1 changed"));
+        assert!(before.diff(&before).is_empty());
+    }
+}
@@ -3,7 +3,7 @@ use crate::core::library_manager::{LibraryManager, LibraryState};
 use crate::core::safety_guard::{SafetyRules, ResourceLimits};  
 use std::fs;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub struct StartupValidator {
     pub library_manager: LibraryManager,
@@ -151,6 +151,76 @@ impl StartupValidator {
         Ok(())
     }
     
+    // Project health check for `morris --check <dir>`: config, entry script and every .msh
+    // file in the project must parse. Skips the ~/.morris library, so it is cheap enough for CI.
+    pub fn validate_project(project_dir: &Path) -> Result<ValidationReport, String> {
+        if !project_dir.is_dir() {
+            return Err(format!("Not a project directory: {}", project_dir.display()));
+        }
+        
+        let safety_rules = SafetyRules::load_default_rules()?;
+        let mut report = ValidationReport::new();
+        
+        let mut entry = "main.msh".to_string();
+        match fs::read_to_string(project_dir.join(".morris_config.toml")) {
+            Ok(content) => match content.parse::<toml::Table>() {
+                Ok(config) => {
+                    if let Some(configured) = config.get("project")
+                        .and_then(|project| project.get("entry"))
+                        .and_then(|entry| entry.as_str())
+                    {
+                        entry = configured.to_string();
+                    }
+                    report.info.push("Config .morris_config.toml OK".to_string());
+                }
+                Err(e) => report.add_critical(
+                    "Invalid .morris_config.toml".to_string(),
+                    e.to_string()
+                ),
+            },
+            Err(_) => report.add_warning(
+                "No .morris_config.toml".to_string(),
+                "Run 'morris --init <name>' to see the expected layout".to_string()
+            ),
+        }
+        
+        if !project_dir.join(&entry).exists() {
+            report.add_critical(
+                format!("Entry script {} missing", entry),
+                "Create it or set [project] entry in .morris_config.toml".to_string()
+            );
+        }
+        
+        let mut scripts = Vec::new();
+        collect_scripts(project_dir, &mut scripts);
+        scripts.sort();
+        
+        for script in &scripts {
+            let display = script.strip_prefix(project_dir).unwrap_or(script).display().to_string();
+            let content = match fs::read_to_string(script) {
+                Ok(content) => content,
+                Err(e) => {
+                    report.add_critical(format!("Cannot read {}", display), e.to_string());
+                    continue;
+                }
+            };
+            
+            for statement in crate::core::script::split_script_statements(&content) {
+                match crate::core::intent::parse_to_intent(&statement.text) {
+                    Ok(intent) => {
+                        if let Err(e) = safety_rules.validate_user_intent(&intent) {
+                            report.add_warning(format!("{}:{} failed safety check", display, statement.line), e);
+                        }
+                    }
+                    Err(e) => report.add_critical(format!("{}:{} does not parse", display, statement.line), e),
+                }
+            }
+        }
+        
+        report.info.push(format!("Checked {} script(s)", scripts.len()));
+        Ok(report)
+    }
+    
     // Add accessor method
     pub fn library_manager(&self) -> &LibraryManager {
         &self.library_manager
@@ -273,4 +343,77 @@ impl ValidationIssue {
     pub fn new(issue: String, details: String) -> Self {
         Self { issue, details }
     }
-}
\ No newline at end of file
+}
+// Every .msh file under `dir`, skipping hidden directories.
+// Symlinked directories are not followed, so a link back up the tree cannot loop forever.
+fn collect_scripts(dir: &Path, scripts: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+        if is_dir && !hidden {
+            collect_scripts(&path, scripts);
+        } else if path.extension().is_some_and(|ext| ext == "msh") {
+            scripts.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("morris_check_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (relative, content) in files {
+            let path = root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_validate_project_reports_parse_errors() {
+        let root = project("errors", &[
+            (".morris_config.toml", "[project]\nentry = \"run.msh\"\n"),
+            ("run.msh", "set a = 1\n"),
+            ("lib/bad.msh", "set a = 1\nfrobnicate\n"),
+            (".hidden/skipped.msh", "frobnicate\n"),
+        ]);
+        let report = StartupValidator::validate_project(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(report.critical_issues.len(), 1);
+        assert_eq!(report.critical_issues[0].issue, "lib/bad.msh:2 does not parse");
+        assert!(report.info.contains(&"Checked 2 script(s)".to_string()));
+    }
+
+    #[test]
+    fn test_validate_project_missing_entry_and_config() {
+        let root = project("empty", &[("lib/helpers.msh", "set a = 1\n")]);
+        let report = StartupValidator::validate_project(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(report.warnings[0].issue, "No .morris_config.toml");
+        assert_eq!(report.critical_issues[0].issue, "Entry script main.msh missing");
+        assert!(StartupValidator::validate_project(&root).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_collect_scripts_skips_symlinked_directories() {
+        let root = project("symlink", &[("main.msh", "set a = 1\n"), ("lib/helpers.msh", "set b = 2\n")]);
+        std::os::unix::fs::symlink(&root, root.join("lib/loop")).unwrap();
+        let mut scripts = Vec::new();
+        collect_scripts(&root, &mut scripts);
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(scripts.len(), 2);
+    }
+}
@@ -189,6 +189,50 @@ impl TransactionEngine {
         }
     }
     
+    // Recorded transactions can be found by full id, id prefix or name (most recent first)
+    pub fn find_transaction(&self, key: &str) -> Option<&Transaction> {
+        self.transaction_log.iter().rev().find(|t| {
+            t.id.to_string().starts_with(key) || t.name.as_deref() == Some(key)
+        })
+    }
+    
+    // Combine the change sets of two recorded transactions. When both touch the same
+    // variable the second one wins, and the overlap is reported as a conflict.
+    pub fn merge_transactions(&self, id1: &str, id2: &str) -> Result<(Transaction, Vec<String>), TransactionError> {
+        let first = self.find_transaction(id1)
+            .ok_or_else(|| TransactionError::MergeConflict(format!("no transaction '{}'", id1)))?;
+        let second = self.find_transaction(id2)
+            .ok_or_else(|| TransactionError::MergeConflict(format!("no transaction '{}'", id2)))?;
+        
+        if first.id == second.id {
+            return Err(TransactionError::MergeConflict(format!("'{}' and '{}' are the same transaction", id1, id2)));
+        }
+        
+        let mut merged = Transaction::new(Some(&format!(
+            "alloy {} + {}",
+            first.name.clone().unwrap_or_else(|| first.id.to_string()[..8].to_string()),
+            second.name.clone().unwrap_or_else(|| second.id.to_string()[..8].to_string()),
+        )));
+        merged.changes = first.changes.clone();
+        merged.metadata = first.metadata.clone();
+        
+        let mut conflicts = Vec::new();
+        for (var_name, change) in &second.changes {
+            if let Some(existing) = merged.changes.get(var_name) {
+                if existing.new_value != change.new_value || existing.raw_expression != change.raw_expression {
+                    conflicts.push(format!("{}: {} replaced by {}",
+                        var_name, describe_change(existing), describe_change(change)));
+                }
+            }
+            merged.changes.insert(var_name.clone(), change.clone());
+        }
+        merged.metadata.extend(second.metadata.clone());
+        merged.parent_transaction = Some(second.id);
+        conflicts.sort();
+        
+        Ok((merged, conflicts))
+    }
+    
     pub fn has_active_transaction(&self) -> bool {
         self.active_transaction.is_some()
     }
@@ -500,4 +544,10 @@ impl TransactionEngine {
         })
     }
 
-}
\ No newline at end of file
+}
+fn describe_change(change: &ValueChange) -> String {
+    match &change.raw_expression {
+        Some(expr) => expr.clone(),
+        None => change.new_value.display(),
+    }
+}
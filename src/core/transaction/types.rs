@@ -169,6 +169,7 @@ pub struct Transaction {
     pub modified_at: DateTime<Utc>,
     pub propagation_paths: Vec<Vec<String>>,
     pub failed_propagations: Vec<String>,
+    pub superseded: usize, // assignments replaced by a later one for the same variable
 }
 
 impl Transaction {
@@ -188,6 +189,7 @@ impl Transaction {
             modified_at: now,
             propagation_paths: Vec::new(),
             failed_propagations: Vec::new(),
+            superseded: 0,
         }
     }
     
@@ -201,14 +203,13 @@ impl Transaction {
     ) {
         let change = ValueChange::simple(
             variable.clone(),
-            old_value,
+            self.original_value(&variable, old_value),
             new_value,
             expression,
             dependencies,
         );
         
-        self.changes.insert(variable, change);
-        self.modified_at = Utc::now();
+        self.insert_change(variable, change);
     }
     
     pub fn add_change_with_raw_expr(
@@ -222,14 +223,27 @@ impl Transaction {
     ) {
         let change = ValueChange::new(
             variable.clone(),
-            old_value,
+            self.original_value(&variable, old_value),
             new_value,
             expression,
             raw_expression,
             dependencies,
         );
         
-        self.changes.insert(variable, change);
+        self.insert_change(variable, change);
+    }
+    
+    // A variable set twice keeps the value it had before the first set as its old value
+    fn original_value(&self, variable: &str, old_value: Value) -> Value {
+        self.changes.get(variable)
+            .map(|previous| previous.old_value.clone())
+            .unwrap_or(old_value)
+    }
+    
+    fn insert_change(&mut self, variable: String, change: ValueChange) {
+        if self.changes.insert(variable, change).is_some() {
+            self.superseded += 1;
+        }
         self.modified_at = Utc::now();
     }
     
@@ -0,0 +1,129 @@
+// File: src/core/tutorial.rs
+use std::fs;
+use std::path::PathBuf;
+use serde::{Serialize, Deserialize};
+
+use crate::core::intent::Verb;
+
+pub struct TutorialStep {
+    pub instruction: &'static str,
+    pub example: &'static str,
+    pub expected_verb: Verb,
+    pub explanation: &'static str,
+}
+
+pub fn tutorial_steps() -> Vec<TutorialStep> {
+    vec![
+        TutorialStep {
+            instruction: "Create a variable holding a number.",
+            example: "set price = 20",
+            expected_verb: Verb::Set,
+            explanation: "Literal values are stored directly.",
+        },
+        TutorialStep {
+            instruction: "Create a variable computed from another one.",
+            example: "set total = price * 3",
+            expected_verb: Verb::Set,
+            explanation: "total now depends on price and is recalculated when price changes.",
+        },
+        TutorialStep {
+            instruction: "Enforce a new value for the input variable.",
+            example: "ensure price = 25",
+            expected_verb: Verb::Ensure,
+            explanation: "ensure changes the value and propagates it: total follows automatically.",
+        },
+        TutorialStep {
+            instruction: "Print a message using the current values.",
+            example: "writeout(Total is {total})",
+            expected_verb: Verb::Writeout,
+            explanation: "Anything in {braces} is evaluated when the message is printed.",
+        },
+        TutorialStep {
+            instruction: "Inspect a variable's type and dependencies.",
+            example: "analyze total",
+            expected_verb: Verb::Analyze,
+            explanation: "analyze shows what a variable depends on and what depends on it.",
+        },
+        TutorialStep {
+            instruction: "Convert a variable to another representation.",
+            example: "derive total as json",
+            expected_verb: Verb::Derive,
+            explanation: "derive performs type-aware conversions.",
+        },
+        TutorialStep {
+            instruction: "Bookmark the current directory.",
+            example: "bookmark add \"start\" .",
+            expected_verb: Verb::Bookmark,
+            explanation: "Later, 'turn \"start\"' brings you back here.",
+        },
+        TutorialStep {
+            instruction: "Start a transaction so changes are staged, not applied.",
+            example: "craft \"price update\"",
+            expected_verb: Verb::Craft,
+            explanation: "Until you forge, changes are only previews.",
+        },
+        TutorialStep {
+            instruction: "Stage a change inside the transaction.",
+            example: "set price = 30",
+            expected_verb: Verb::Set,
+            explanation: "The change is recorded in the open transaction.",
+        },
+        TutorialStep {
+            instruction: "Apply everything that was crafted.",
+            example: "forge",
+            expected_verb: Verb::Forge,
+            explanation: "forge commits the staged changes and recalculates dependents once.",
+        },
+    ]
+}
+
+// Resumable progress, stored in ~/.morris_tutorial.json
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TutorialProgress {
+    pub completed_steps: usize,
+}
+
+impl TutorialProgress {
+    fn path() -> PathBuf {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        home.join(".morris_tutorial.json")
+    }
+
+    pub fn load() -> Self {
+        fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Cannot serialize tutorial progress: {}", e))?;
+        fs::write(Self::path(), content)
+            .map_err(|e| format!("Cannot save tutorial progress: {}", e))
+    }
+
+    pub fn reset() -> Result<(), String> {
+        Self::default().save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_match_expected_verbs() {
+        for step in tutorial_steps() {
+            let intent = crate::core::intent::parse_to_intent(step.example).unwrap();
+            assert_eq!(intent.verb, step.expected_verb, "example '{}'", step.example);
+        }
+    }
+
+    #[test]
+    fn test_progress_round_trips() {
+        let progress: TutorialProgress = serde_json::from_str(r#"{"completed_steps": 4}"#).unwrap();
+        assert_eq!(progress.completed_steps, 4);
+        assert_eq!(serde_json::to_string(&TutorialProgress::default()).unwrap(), r#"{"completed_steps":0}"#);
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
 use std::fmt;
+use crate::core::expr::Expr;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Value {
@@ -13,6 +14,7 @@ pub enum Value {
     Dict(HashMap<String, Value>),
     //Array(Vec<Value>),
     Json(String),
+    Null,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -68,6 +70,16 @@ pub struct Variable {
     pub delay_counter: usize,         // Tracks ignored propagations
     #[serde(default)]
     pub limit_counter: usize,         // Tracks successful propagations
+    #[serde(default)]
+    pub is_gilded: bool,              // Marked important with 'gild'
+    #[serde(default)]
+    pub is_readonly: bool,            // Declared with 'as readonly', never reassigned
+    #[serde(default)]
+    pub is_final: bool,               // Declared with 'as final', assigned at most once
+    #[serde(default)]
+    pub is_lazy: bool,                // Declared with 'as lazy', evaluated on first read
+    #[serde(skip)]
+    pub lazy_expr: Option<Expr>,      // Pending expression, cleared once the value is cached
 }
 
 
@@ -98,6 +110,11 @@ impl Variable {
             propagation_limit: limit,
             delay_counter: 0,         // Fix: use correct field name
             limit_counter: 0,         // Fix: use correct field name
+            is_gilded: false,
+            is_readonly: false,
+            is_final: false,
+            is_lazy: false,
+            lazy_expr: None,
         }
     }
     
@@ -190,6 +207,7 @@ impl Value {
             Value::List(_) => "list",
             Value::Dict(_) => "dict",
             Value::Json(_) => "json",
+            Value::Null => "null",
         }
     }
 
@@ -226,6 +244,7 @@ impl Value {
                 format!("[{}]", item_strings.join(", "))
             },*/
             Value::Json(json_str) => json_str.clone(),
+            Value::Null => "null".to_string(),
         }
     }
 
@@ -257,6 +276,7 @@ impl Value {
                 format!("[{}]", item_strings.join(", "))
             },*/
             Value::Json(json_str) => format!("json!{}", json_str),
+            Value::Null => "null".to_string(),
         }
     }
 }
@@ -293,6 +313,7 @@ impl fmt::Display for Value {
                 write!(f, "}}")
             },
             Value::Json(json_str) => write!(f, "json!{}", json_str),
+            Value::Null => write!(f, "null"),
         }
     }
 }
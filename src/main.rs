@@ -1,6 +1,7 @@
 mod core;
 mod output;
 mod repl;  
+mod completions;
 
 use std::io::{self, Write};
 use std::fs;
@@ -22,22 +23,175 @@ use crate::core::types::SimpleType;
 use crate::core::template::render_template;    
 
 fn main() -> io::Result<()> {
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = vec![std::env::args().next().unwrap_or_default()];
+    
+    // Flags may appear anywhere on the command line; everything else is positional.
+    // For repeated color flags the last one wins.
+    let mut color_flag = None;
+    let mut sandbox = false;
+    let mut allow_http = false;
+    // [script] strict in .morris_config.toml; --strict turns it on regardless
+    let mut options = ScriptOptions {
+        strict: crate::core::project_config::get_bool("script", "strict").unwrap_or(false),
+        ..ScriptOptions::default()
+    };
+    let mut evals = Vec::new();
+    let mut subcommand: Option<(String, String)> = None;
+    let mut raw_args = std::env::args().skip(1).peekable();
+    while let Some(arg) = raw_args.next() {
+        match arg.as_str() {
+            "--color" => color_flag = Some(true),
+            "--no-color" => color_flag = Some(false),
+            "--strict" => options.strict = true,
+            "--dry-run" => options.dry_run = true,
+            "--profile" => options.profile = true,
+            "--sandbox" => sandbox = true,
+            "--allow-http" => allow_http = true,
+            "-h" | "--help" => {
+                show_cli_help();
+                return Ok(());
+            }
+            "--completions" => {
+                let shell = raw_args.next().unwrap_or_default();
+                match crate::completions::CompletionGenerator::new().generate(&shell) {
+                    Ok(script) => {
+                        print!("{}", script);
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        println!("[-] {}", e);
+                        std::process::exit(2);
+                    }
+                }
+            }
+            // --init <name>, --check [dir]: run after the color flags are applied
+            "--init" | "--check" => {
+                let value = raw_args.next_if(|value| !value.starts_with('-'));
+                if arg == "--init" && value.is_none() {
+                    println!("[-] --init requires a project name");
+                    std::process::exit(2);
+                }
+                subcommand = Some((arg, value.unwrap_or_else(|| ".".to_string())));
+            }
+            // -e / --eval <intent> (repeatable), --var name=value (repeatable)
+            "-e" | "--eval" | "--var" => {
+                let value = match raw_args.next() {
+                    Some(value) => value,
+                    None => {
+                        println!("[-] {} requires a value", arg);
+                        std::process::exit(2);
+                    }
+                };
+                if arg == "--var" {
+                    match value.split_once('=') {
+                        Some((name, value)) if !name.trim().is_empty() => {
+                            options.vars.push((name.trim().to_string(), value.to_string()));
+                        }
+                        _ => {
+                            println!("[-] --var expects name=value, got: {}", value);
+                            std::process::exit(2);
+                        }
+                    }
+                } else {
+                    evals.push(value);
+                }
+            }
+            _ => args.push(arg),
+        }
+    }
+    
+    if let Some(use_color) = color_flag {
+        output::set_color_override(use_color);
+    }
+    crate::core::safety_guard::set_sandbox(sandbox || env_switch("MORRIS_SANDBOX"));
+    crate::core::safety_guard::set_allow_http(allow_http || env_switch("MORRIS_ALLOW_HTTP"));
+    
+    match subcommand {
+        Some((flag, name)) if flag == "--init" => {
+            match init_project(&name) {
+                Ok(created) => {
+                    let printer = Printer::new();
+                    printer.success(&format!("Created project '{}'", name));
+                    for path in created {
+                        println!("  {}", path);
+                    }
+                    return Ok(());
+                }
+                Err(e) => {
+                    Printer::new().error(&e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some((_, dir)) => {
+            let printer = Printer::new();
+            printer.header(&format!("Checking project: {}", dir));
+            match crate::core::startup_validator::StartupValidator::validate_project(std::path::Path::new(&dir)) {
+                Ok(report) => {
+                    for info in &report.info {
+                        println!("  ✓ {}", info);
+                    }
+                    print!("{}", report.format_summary());
+                    if report.has_critical_issues() {
+                        printer.error("Project check failed");
+                        std::process::exit(1);
+                    }
+                    printer.success("Project check passed");
+                    return Ok(());
+                }
+                Err(e) => {
+                    printer.error(&e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => {}
+    }
+    
+    if !evals.is_empty() {
+        if let Err(e) = execute_eval_intents(&evals, &options) {
+            Printer::new().error(&e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
         #[allow(dead_code)]
     
     if args.len() > 1 {
         let filename = &args[1];
         if !filename.ends_with(".msh") {
             println!("[-] Expected .msh file, got: {}", filename);
-            println!("[?] Usage: morris <file.msh>");
+            println!("[?] Usage: morris [options] <file.msh>   (see morris --help)");
             return Ok(());
         }
         
-        match execute_msh_file(filename) {
-            Ok(_) => {
+        if options.dry_run {
+            match dry_run_msh_file(filename) {
+                Ok((intent_count, 0)) => {
+                    println!("Script OK: {} intents, 0 errors", intent_count);
+                    return Ok(());
+                }
+                Ok((intent_count, error_count)) => {
+                    println!("Script has errors: {} intents, {} errors", intent_count, error_count);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    Printer::new().error(&e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        
+        match execute_msh_file(filename, &options) {
+            Ok((_, 0)) => {
                 let printer = Printer::new();
                 printer.success(&format!("Script '{}' executed successfully", filename));
             },
+            Ok((_, error_count)) => {
+                let printer = Printer::new();
+                printer.error(&format!("Script '{}' finished with {} error(s)", filename, error_count));
+                std::process::exit(1);
+            },
             Err(e) => {
                 let printer = Printer::new();
                 printer.error(&e);
@@ -148,8 +302,7 @@ fn interactive_mode() -> io::Result<()> {
     let mut intent_history: Vec<crate::core::intent::Intent> = Vec::new();
     
     // NEW: Load validated intents from library state
-    let mut defined_intents: HashMap<String, crate::core::intent::Intent> = 
-        library_state.user_intents.clone();
+    env.defined_intents_mut().extend(library_state.user_intents.clone());
     
     let mut history_manager = HistoryManager::new();
     let mut engine_manager = ChangeEngineManager::new();
@@ -169,10 +322,25 @@ fn interactive_mode() -> io::Result<()> {
     let safety_guard = crate::core::safety_guard::SafetyGuard::new()
         .expect("Failed to initialize safety guard");
     
+    // Aliases expand into intents queued ahead of the next prompt. Queued
+    // intents are not expanded again, so aliases cannot recurse
+    let mut aliases = crate::core::alias::AliasManager::new();
+    let mut reload_aliases = false;
+    let mut queued_inputs: std::collections::VecDeque<String> = std::collections::VecDeque::new();
+    
     // Main loop
     loop {
         let mut input = String::new();
-        match repl.read_line("intent> ") {
+        if reload_aliases {
+            aliases = crate::core::alias::AliasManager::new();
+            reload_aliases = false;
+        }
+        let from_alias = !queued_inputs.is_empty();
+        let next_line = match queued_inputs.pop_front() {
+            Some(queued) => Ok(Some(queued)),
+            None => repl.read_line("intent> "),
+        };
+        match next_line {
             Ok(Some(line)) => {
                 if line.trim_end().ends_with('{') {
                     // Multi-line block mode
@@ -196,6 +364,13 @@ fn interactive_mode() -> io::Result<()> {
                     input = line;
                 }
                 
+                if !from_alias {
+                    if let Some(expanded) = aliases.expand(&input) {
+                        queued_inputs.extend(expanded);
+                        continue;
+                    }
+                }
+                
                 // Process the input
                 match input.as_str() {
                     "exit" | "quit" => {
@@ -284,12 +459,15 @@ fn interactive_mode() -> io::Result<()> {
                         } else {
                             repl.printer().info("New propagation engine is DISABLED (using legacy)");
                         }
+                        if let Ok(status) = execute_engine_status_intent(&engine_manager, repl.printer()) {
+                            println!("{}", status);
+                        }
                         println!();
                         continue;
                     }
                     // NEW: Integrity system commands
                     "validate" => {
-                        match validator.validate_current_state(&env, &defined_intents) {
+                        match validator.validate_current_state(&env, env.defined_intents()) {
                             Ok(report) => {
                                 repl.printer().success("System validation passed");
                                 println!("{}", report.format_summary());
@@ -322,6 +500,8 @@ fn interactive_mode() -> io::Result<()> {
                         // Parse and execute the intent WITH SAFETY GUARD
                         match parse_to_intent(&input) {
                             Ok(mut intent) => {
+                                reload_aliases = matches!(intent.verb, Verb::Alias | Verb::Unalias);
+                                
                                 // NEW: Validate intent safety before execution
                                 if let Err(e) = safety_guard.validate_intent(&intent) {
                                     repl.printer().error(&format!("Safety check failed: {}", e));
@@ -357,7 +537,7 @@ fn interactive_mode() -> io::Result<()> {
                                 }
                                 
                                 // Check if it's a define intent
-                                if intent.is_composition && intent.intent_source == Some("defined_intent".to_string()) {
+                                if intent.is_definition() {
                                     if let Some(name) = &intent.composition_name {
                                         // NEW: Validate the new intent definition
                                         if let Err(e) = safety_guard.validate_new_definition(&intent) {
@@ -366,7 +546,7 @@ fn interactive_mode() -> io::Result<()> {
                                             continue;
                                         }
                                         
-                                        defined_intents.insert(name.clone(), intent.clone());
+                                        env.defined_intents_mut().insert(name.clone(), intent.clone());
                                         repl.printer().success(&format!("Intent defined: {}", name));
                                         println!();
                                         continue;
@@ -376,7 +556,7 @@ fn interactive_mode() -> io::Result<()> {
                                 // If it's an execute intent for a defined intent
                                 if intent.verb == Verb::Execute {
                                     if let Some(intent_name) = intent.parameters.get("intent_to_execute") {
-                                        if let Some(defined_intent) = defined_intents.get(intent_name) {
+                                        if let Some(defined_intent) = env.defined_intents().get(intent_name).cloned() {
                                             repl.printer().info(&format!("Executing intent: {}", intent_name));
                                             
                                             // Instantiate with parameters
@@ -584,7 +764,6 @@ fn interactive_mode_fallback() -> io::Result<()> {
     let filesystem = FileSystem::new();
     let mut library = Library::new();
     let mut intent_history: Vec<crate::core::intent::Intent> = Vec::new();
-    let mut defined_intents: HashMap<String, crate::core::intent::Intent> = HashMap::new();
     let mut running = true;
     let mut history_manager = HistoryManager::new();
     let mut engine_manager = ChangeEngineManager::new();
@@ -599,18 +778,38 @@ fn interactive_mode_fallback() -> io::Result<()> {
         printer.warning(&format!("Could not load change engine: {}", e));
     }
     
+    let mut aliases = crate::core::alias::AliasManager::new();
+    let mut reload_aliases = false;
+    let mut queued_inputs: std::collections::VecDeque<String> = std::collections::VecDeque::new();
+    
     while running {
-        print!("intent> ");
-        io::stdout().flush()?;
-        
+        if reload_aliases {
+            aliases = crate::core::alias::AliasManager::new();
+            reload_aliases = false;
+        }
+        let from_alias = !queued_inputs.is_empty();
         let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        match queued_inputs.pop_front() {
+            Some(queued) => input = queued,
+            None => {
+                print!("intent> ");
+                io::stdout().flush()?;
+                io::stdin().read_line(&mut input)?;
+            }
+        }
         let input = input.trim();
         
         if input.is_empty() {
             continue;
         }
         
+        if !from_alias {
+            if let Some(expanded) = aliases.expand(input) {
+                queued_inputs.extend(expanded);
+                continue;
+            }
+        }
+        
         match input {
             "exit" | "quit" => {
                 printer.success("Goodbye!");
@@ -641,6 +840,8 @@ fn interactive_mode_fallback() -> io::Result<()> {
         
         match parse_to_intent(input) {
             Ok(mut intent) => {
+                reload_aliases = matches!(intent.verb, Verb::Alias | Verb::Unalias);
+                
                 // Handle system commands that were parsed as intents
                 if intent.state == IntentState::NeedsClarification {
                     if let Some(cmd) = intent.get_context("system_command") {
@@ -659,9 +860,9 @@ fn interactive_mode_fallback() -> io::Result<()> {
                 }
                 
                 // Check if it's a define intent
-                if intent.is_composition && intent.intent_source == Some("defined_intent".to_string()) {
+                if intent.is_definition() {
                     if let Some(name) = &intent.composition_name {
-                        defined_intents.insert(name.clone(), intent.clone());
+                        env.defined_intents_mut().insert(name.clone(), intent.clone());
                         printer.success(&format!("Intent defined: {}", name));
                         println!();
                         continue;
@@ -671,7 +872,7 @@ fn interactive_mode_fallback() -> io::Result<()> {
                 // If it's an execute intent for a defined intent
                 if intent.verb == Verb::Execute {
                     if let Some(intent_name) = intent.parameters.get("intent_to_execute") {
-                        if let Some(defined_intent) = defined_intents.get(intent_name) {
+                        if let Some(defined_intent) = env.defined_intents().get(intent_name).cloned() {
                             printer.info(&format!("Executing intent: {}", intent_name));
                             
                             // Instantiate with parameters
@@ -679,6 +880,7 @@ fn interactive_mode_fallback() -> io::Result<()> {
                             let instantiated = defined_intent.instantiate_with_params(&params);
                             
                             // Execute the instantiated intent
+                            let defined_intents = env.defined_intents().clone();
                             match execute_defined_intent(&instantiated, &mut env, &filesystem, &mut library, &mut intent_history, &defined_intents) {
                                 Ok(output) => {
                                     println!("{}", output);
@@ -785,113 +987,190 @@ fn show_help(printer: &Printer) {
     printer.header("Available Intent Types");
     
     printer.subheader("Intent Definition");
-    println!("  define intent \"name\" with (param1, param2=\"default\") {{ expression }}");
-    println!("  define intent \"name\" composed_of [\"intent1\", \"intent2\"]");
-    println!("  execute \"intent_name\" with param1=value1, param2=value2");
+    printer.emit("  define intent \"name\" with (param1, param2=\"default\") { expression }");
+    printer.emit("  define intent \"name\" composed_of [\"intent1\", \"intent2\"]");
+    printer.emit("  execute \"intent_name\" with param1=value1, param2=value2");
+    printer.emit("  evolve \"name\" { expression }            - Replace a defined intent's body (old one is kept)");
+    printer.emit("  grow \"name\" with [\"intent\"]              - Append steps to a composed_of intent");
+    printer.emit("  examine intent \"name\"                      - Show a defined intent and its earlier definitions");
     
     printer.subheader("File Operations");
-    println!("  save \"path.menv\"              - Save environment to file");
-    println!("  read \"file.txt\" into var     - Read file into variable");
-    println!("  write \"file.txt\" \"content\"   - Write content to file");
-    println!("  append \"file.txt\" \"content\"  - Append content to file");
-    println!("  mkdir \"path/to/dir\"          - Create directory");
-    println!("  list \"path\"                  - List directory contents");
-    println!("  info \"file.txt\"              - Get file information");
-    println!("  exists \"file.txt\"            - Check if file exists");
+    printer.emit("  save \"path.menv\"              - Save environment to file");
+    printer.emit("  snapshot \"file.msnap\"         - Checkpoint all variables, flags, bookmarks and volumes");
+    printer.emit("  restore \"file.msnap\" [--replace] - Merge a snapshot back in (or replace the env)");
+    printer.emit("  diff-env \"a.msnap\" \"b.msnap\"    - Show variables added, removed and changed between snapshots");
+    printer.emit("  read \"file.txt\" into var     - Read file into variable");
+    printer.emit("  write \"file.txt\" \"content\"   - Write content to file");
+    printer.emit("  append \"file.txt\" \"content\"  - Append content to file");
+    printer.emit("  mkdir \"path/to/dir\"          - Create directory");
+    printer.emit("  list \"path\"                  - List directory contents");
+    printer.emit("  info \"file.txt\"              - Get file information");
+    printer.emit("  exists \"file.txt\"            - Check if file exists");
     
     printer.subheader("Core Operations");
-    println!("  set <var> = <value> [as <type>]");
-    println!("  ensure <condition>");
-    println!("  ensure file \"path\" exists");
-    println!("  writeout(<content>)");
-    println!("  derive <var>");
-    println!("  find <pattern>");
-    println!("  analyze <var>");
-    println!("  freeze <var>");
-    println!("  load <file.msh>");
-    println!("  set <var> = <value> [as <type>]");
-    println!("  ensure <condition>");
-    println!("  ensure file \"path\" exists");
-    println!("  writeout(<content>)");
-    println!("  derive <var>");
-    println!("  find <pattern>");
-    println!("  analyze <var>");
-    println!("  freeze <var>");
-    println!("  load <file.msh>");
-    println!("  parse-json \"json_string\"     - Parse JSON string");
-    println!("  to-json <variable>            - Convert variable to JSON");
-    println!("  from-json \"json\" into <var>   - Parse JSON into variable");
-    println!("  json-get <variable>.<path>         - Get value from JSON path");
-    println!("  json-set <variable>.<path> = value - Set value at JSON path");
+    printer.emit("  set <var> = <value> [as <type>]");
+    printer.emit("  set <var> = <value> as readonly  - Declare a variable that can never change");
+    printer.emit("  set <var> = <value> as final     - Allow a single non-null assignment");
+    printer.emit("  set <var> = <expr> as lazy       - Evaluate on first read, then cache");
+    printer.emit("  ensure <condition>");
+    printer.emit("  ensure file \"path\" exists");
+    printer.emit("  writeout(<content>)");
+    printer.emit("  derive <var>");
+    printer.emit("  find <pattern>");
+    printer.emit("  analyze <var>");
+    printer.emit("  freeze <var>");
+    printer.emit("  load <file.msh>");
+    printer.emit("  set <var> = <value> [as <type>]");
+    printer.emit("  ensure <condition>");
+    printer.emit("  ensure file \"path\" exists");
+    printer.emit("  writeout(<content>)");
+    printer.emit("  derive <var>");
+    printer.emit("  find <pattern>");
+    printer.emit("  analyze <var>");
+    printer.emit("  freeze <var>");
+    printer.emit("  load <file.msh>");
+    printer.emit("  parse-json \"json_string\"     - Parse JSON string");
+    printer.emit("  to-json <variable>            - Convert variable to JSON");
+    printer.emit("  from-json \"json\" into <var>   - Parse JSON into variable");
+    printer.emit("  parse-yaml \"yaml\"|<var> [into <var>] - Parse YAML (multi-document gives a list)");
+    printer.emit("  to-yaml <variable>            - Convert variable to YAML");
+    printer.emit("  parse-csv \"csv\"|<var> [into <var>] [headers false] - Parse CSV into a list of dicts (or lists)");
+    printer.emit("  to-csv <variable>             - Convert a list of dicts or lists to CSV");
+    printer.emit("  json-get <variable>.<path>         - Get value from JSON path");
+    printer.emit("  json-set <variable>.<path> = value - Set value at JSON path");
     
     printer.subheader("System Commands");
-    println!("  env         - Show current environment");
-    println!("  history     - Show intent history");
-    println!("  clear       - Clear screen");
-    println!("  exit        - Exit Morris");
+    printer.emit("  env         - Show current environment");
+    printer.emit("  history     - Show intent history");
+    printer.emit("  clear       - Clear screen");
+    printer.emit("  exit        - Exit Morris");
 
     printer.subheader("Book Navigation (Filesystem as Library)");
-    println!("  page                    - Show current page/directory");
-    println!("  turn <path>             - Change directory");
-    println!("  chapter <path>          - Alias for turn");
-    println!("  bookmark add \"name\" [path] - Create bookmark");
-    println!("  bookmark remove \"name\"     - Remove bookmark");
-    println!("  bookmarks               - List all bookmarks");
-    println!("  volume add \"name\" path [\"desc\"] - Define volume");
-    println!("  volumes                 - List all volumes");
-    println!("  shelve                  - Save current position");
-    println!("  unshelve                - Restore saved position");
-    println!("  back [n]                - Go back n pages (default: 1)");
-    println!("  index                   - List directory contents");
-    println!("  annotate <target> \"note\" - Add note to file/directory");
-    println!("  read_annotation <target> - Read annotation");
-    println!("  skim <file>             - Quick file preview");
-    println!("  library                 - Show library overview");
+    printer.emit("  page                    - Show current page/directory");
+    printer.emit("  turn <path>             - Change directory");
+    printer.emit("  chapter <path>          - Alias for turn");
+    printer.emit("  bookmark add \"name\" [path] - Create bookmark");
+    printer.emit("  bookmark remove \"name\"     - Remove bookmark");
+    printer.emit("  bookmarks               - List all bookmarks");
+    printer.emit("  volume add \"name\" path [\"desc\"] - Define volume");
+    printer.emit("  volumes                 - List all volumes");
+    printer.emit("  shelve                  - Save current position");
+    printer.emit("  unshelve                - Restore saved position");
+    printer.emit("  back [n]                - Go back n pages (default: 1)");
+    printer.emit("  index                   - List directory contents");
+    printer.emit("  annotate <target> \"note\" - Add note to file/directory");
+    printer.emit("  read_annotation <target> - Read annotation");
+    printer.emit("  skim <file>             - Quick file preview");
+    printer.emit("  library                 - Show library overview");
 
     printer.header("Enhanced Book Navigation");
     
     printer.subheader("Navigation Verbs");
-    println!("  page                    - Show current page");
-    println!("  turn <path>             - Turn to page (supports -1, -2, +1, etc)");
-    println!("  jump <path>             - Jump to location (alias: goto)");
-    println!("  peek [n]                - Peek n steps back (default: -1)");
-    println!("  return [n]              - Return n pages back (default: 1)");
-    println!("  mark \"name\" [desc]     - Create temporary mark");
+    printer.emit("  page                    - Show current page");
+    printer.emit("  turn <path>             - Turn to page (supports -1, -2, +1, etc)");
+    printer.emit("  jump <path>             - Jump to location (alias: goto)");
+    printer.emit("  peek [n]                - Peek n steps back (default: -1)");
+    printer.emit("  return [n]              - Return n pages back (default: 1)");
+    printer.emit("  mark \"name\" [desc]     - Create temporary mark");
     
     printer.subheader("Examples:");
-    println!("  turn ..                 - Go up one directory");
-    println!("  turn -1                 - Go back one page");
-    println!("  turn -2                 - Go back two pages");
-    println!("  turn /home/user/docs    - Absolute path");
-    println!("  turn \"My Documents\"     - Bookmark or volume");
-    println!("  peek                    - See where you'd go back to");
-    println!("  peek -2                 - See two pages back");
-    println!("  return                  - Go back one page");
-    println!("  return 3                - Go back three pages");
-    println!("  mark \"important spot\"   - Mark current location");
+    printer.emit("  turn ..                 - Go up one directory");
+    printer.emit("  turn -1                 - Go back one page");
+    printer.emit("  turn -2                 - Go back two pages");
+    printer.emit("  turn /home/user/docs    - Absolute path");
+    printer.emit("  turn \"My Documents\"     - Bookmark or volume");
+    printer.emit("  peek                    - See where you'd go back to");
+    printer.emit("  peek -2                 - See two pages back");
+    printer.emit("  return                  - Go back one page");
+    printer.emit("  return 3                - Go back three pages");
+    printer.emit("  mark \"important spot\"   - Mark current location");
 
     printer.subheader("Propagation Engine Commands");
-    println!("  engine on          - Enable new propagation engine");
-    println!("  engine off         - Disable new engine (use legacy)");
-    println!("  engine migrate     - Migrate existing variables to new engine");
-    println!("  engine visualize   - Show dependency graph visualization");
-    println!("  engine history     - Show propagation history");
-    println!("  engine status      - Show engine status");
+    printer.emit("  engine on          - Enable new propagation engine");
+    printer.emit("  engine off         - Disable new engine (use legacy)");
+    printer.emit("  engine migrate     - Migrate existing variables to new engine");
+    printer.emit("  engine visualize   - Show dependency graph visualization");
+    printer.emit("  engine history     - Show propagation history");
+    printer.emit("  engine status      - Show engine status");
 
     printer.subheader("Transaction System (Blacksmithing Metaphor)");
-    println!("  craft [\"name\"]           - Begin crafting changes (start transaction)");
-    println!("  forge                    - Finalize and apply crafted changes (commit)");
-    println!("  smelt                    - Melt down crafted changes (rollback)");
-    println!("  temper                   - Test changes without applying (dry-run)");
-    println!("  inspect                  - View current crafted changes");
-    println!("  anneal <steps>           - Apply changes gradually (staged commit)");
-    println!("  quench                   - Apply changes immediately (fast commit)");
-    println!("  polish                   - Optimize crafted changes before forging");
-    println!("  alloy <id1> <id2>        - Merge multiple crafted changes");
-    println!("  engrave <key> <value>    - Add metadata to crafted changes");
-    println!("  gild <variables...>      - Mark changes as important/golden");
-    println!("  patina <variable>        - Show transaction history for variable");
-    println!("  transaction              - Show current transaction status");
+    printer.emit("  craft [\"name\"]           - Begin crafting changes (start transaction)");
+    printer.emit("  forge                    - Finalize and apply crafted changes (commit)");
+    printer.emit("  smelt                    - Melt down crafted changes (rollback)");
+    printer.emit("  temper                   - Test changes without applying (dry-run)");
+    printer.emit("  inspect                  - View current crafted changes");
+    printer.emit("  anneal <steps>           - Apply changes gradually (staged commit)");
+    printer.emit("  quench                   - Apply changes immediately (fast commit)");
+    printer.emit("  polish                   - Optimize crafted changes before forging");
+    printer.emit("  alloy <id1> <id2>        - Merge multiple crafted changes");
+    printer.emit("  engrave <key> <value>    - Add metadata to crafted changes");
+    printer.emit("  gild <variables...>      - Mark changes as important/golden");
+    printer.emit("  patina <variable>        - Show transaction history for variable");
+    printer.emit("  transaction              - Show current transaction status");
+
+    printer.subheader("Project Tooling");
+    printer.emit("  manifest \"project.mmf\"    - Validate env against a TOML manifest");
+
+    printer.subheader("Data Tooling");
+    printer.emit("  schema \"name\" { field: type, age: int(0..99) } - Register a record schema");
+    printer.emit("  generate \"name\" count <n> into <var>           - Generate random rows from a schema");
+    printer.emit("  diff <dict_a> and <dict_b> [into <var>]          - Field-level diff of two dicts");
+    printer.emit("  merge <a> and <b> into <dest> [strategy=<s>]     - Deep merge (last_wins, first_wins, error_on_conflict, append)");
+    printer.emit("  assert-schema <list> against \"name\" [report into <var>] - Validate every row against a schema");
+    printer.emit("  summarize <list> [field <key>] [into <var>]      - Statistics (min/max/mean/median/percentiles...)");
+    printer.emit("  pivot <list> row <k> col <k> val <k> into <dest> - Reshape long rows to wide");
+    printer.emit("  unpivot <var> row <col> val <col> into <dest>    - Reshape wide rows back to long");
+    printer.emit("  join <a> and <b> on <key> [into <dest>]          - Inner join two lists of dicts");
+    printer.emit("  left-join <a> and <b> on <key> [into <dest>]     - Left join (missing fields become null)");
+    printer.emit("  window <list> size <n> aggregate <f> into <dest> - Sliding window sum/avg/max/min");
+    printer.emit("  interpolate <list> [method forward|backward] [into <dest>] - Fill null gaps");
+    printer.emit("  normalize <list> [method z-score|max] [into <dest>]        - Scale to [0,1] or z-scores");
+    printer.emit("  one-hot <list> into <dest>                       - One boolean field per category");
+    printer.emit("  label-encode <list> into <dest>                  - Categories to 0..N-1 (mapping in <dest>_mapping)");
+    printer.emit("  coalesce [a, b, c] into <dest>                   - First non-null value (also coalesce(a, b, c))");
+    printer.emit("  filter <list> where { item > 5 } [into <dest>]   - Keep elements matching the condition");
+    printer.emit("  transform <list> with { item * 2 } [into <dest>] - Apply an expression to every element (alias: map)");
+    printer.emit("  reduce <list> with { acc + item } [starting_from <v>] [into <dest>] - Fold a list into one value");
+    printer.emit("  sort <list> [by \"field\"] [descending]            - Sort a list variable in place (sort() for expressions)");
+
+    printer.subheader("Variable Management");
+    printer.emit("  unset <var>                                      - Remove a variable; dependents are re-evaluated or nulled");
+    printer.emit("  rename <old> as <new> [--force]                  - Rename a variable and update expressions using it");
+    printer.emit("  copy <source> as <dest>                          - Snapshot a variable's current value into a new one");
+    printer.emit("  swap <a> <b>                                     - Exchange two values in one step");
+    printer.emit("  cast <var> as int|bool|string                    - Convert a variable in place, showing before and after");
+
+    printer.subheader("Control Flow");
+    printer.emit("  switch <var> { case \"a\" then <intent>; default <intent> } - Run the first matching branch");
+    printer.emit("  try { ... } catch { ... } finally { ... }      - Handle errors (message bound to _error)");
+    printer.emit("  try { ... } catch /pattern/ { ... } catch { ... } - Catch errors whose message matches a regex");
+    printer.emit("  raise \"message\"                                - Fail with a user-defined error");
+    printer.emit("  each <item> in <list> { ... }                    - Run the block once per element, bound to <item>");
+    printer.emit("  assert <condition> [message \"text\"]             - Stop the script if the condition is false");
+
+    printer.subheader("Script Tooling");
+    printer.emit("  log info|warning|error \"msg\" [fields { k=v }]  - Append a JSON line to ~/.morris_script.log");
+    printer.emit("  log file \"path\"                                - Send log entries to another file");
+    printer.emit("  log flush                                        - Sync the log file to disk");
+    printer.emit("  config log_file \"path\" | config                   - Set where log writes, or show settings");
+    printer.emit("  benchmark [verbose] { ... }                      - Time a block (ms stored in __last_benchmark_ms)");
+    printer.emit("  capture <var> { ... }                            - Store a block's output in a variable, like $(...)");
+    printer.emit("  redirect \"file\" [append] { ... }                 - Show a block's output and also write it to a file");
+    printer.emit("                                                     (the interactive watch and tutorial prompts are not copied)");
+    printer.emit("  trace on|off                                     - Print each propagation step as it happens");
+    printer.emit("  watch <vars...>                                  - Live panel of values while you run intents (q exits)");
+    printer.emit("  notify \"msg\" [title \"Morris\"]                    - Desktop notification");
+    printer.emit("  on-error notify \"msg\" [title \"Morris\"]           - Notify if the script finishes with errors");
+    printer.emit("  env-var <NAME> into <var>                        - Read a process environment variable");
+    printer.emit("  export <var> [as NAME] | export *                - Expose variables to exec_shell() child processes");
+    printer.emit("  completions bash|zsh|fish                        - Print a shell completion script for morris");
+    printer.emit("  man <verb>                                       - Full manual page for a verb (man with no verb lists them)");
+    printer.emit("  tutorial [reset]                                 - Guided tour of the basics (progress is saved)");
+    printer.emit("  examples <verb> | examples run <n>               - Show worked examples, or run one by number");
+    printer.emit("  changelog [version]                              - What changed in each release");
+    printer.emit("  feedback \"msg\" | feedback show | feedback send   - Record ideas in ~/.morris_feedback.log");
+    printer.emit("  alias <name> = \"intent && intent\"               - Define a prompt shortcut (~/.morris_aliases.json)");
+    printer.emit("  unalias <name> | aliases                         - Remove a shortcut, or list them all");
 }
 
 fn show_env_clean(env: &Env, printer: &Printer) {
@@ -915,7 +1194,12 @@ fn show_env_clean(env: &Env, printer: &Printer) {
         .min(20);
     
     for (name, value) in vars.iter().take(50) {
-        printer.print_key_value(&format!("{:width$}", name, width = max_name_len), value, 2);
+        let mark = match (env.is_gilded(name), printer.use_color) {
+            (true, true) => "\x1b[33m★\x1b[0m ",  // Gold star
+            (true, false) => "★ ",
+            (false, _) => "  ",
+        };
+        printer.print_key_value(&format!("{}{:width$}", mark, name, width = max_name_len), value, 2);
     }
     
     if vars.len() > 50 {
@@ -953,13 +1237,13 @@ fn show_history_clean(intent_history: &[crate::core::intent::Intent], printer: &
                 IntentState::Executing => "\x1b[36m",  // cyan
                 _ => "\x1b[90m",                       // dark gray
             };
-            println!("  {}{:3}. {}{} {} → {}", color, i + 1, prefix, "\x1b[0m", verb_str, target_str);
+            printer.emit(&format!("  {}{:3}. {}{} {} → {}", color, i + 1, prefix, "\x1b[0m", verb_str, target_str));
         } else {
-            println!("  {:3}. {} {} → {}", i + 1, prefix, verb_str, target_str);
+            printer.emit(&format!("  {:3}. {} {} → {}", i + 1, prefix, verb_str, target_str));
         }
         
         if let Some(source) = intent.get_context("source") {
-            println!("      Source: {}", source);
+            printer.emit(&format!("      Source: {}", source));
         }
     }
     
@@ -978,9 +1262,18 @@ fn execute_intent_in_test_env(
     engine_manager: &mut ChangeEngineManager,
     printer: &Printer,
 ) -> Result<String, String> {
+    // `define intent` parses to the definition itself; outside the REPL loop
+    // (scripts, blocks) it is stored here rather than executed
+    if intent.is_definition() {
+        if let Some(name) = &intent.composition_name {
+            env.defined_intents_mut().insert(name.clone(), intent.clone());
+            return Ok(format!("[+] Intent defined: {}", name));
+        }
+    }
+    
     // This is a direct copy of the logic that would normally be in execute_intent
     // but without the recursive super::execute_intent call
-    match &intent.verb {
+    let result = match &intent.verb {
         Verb::Set => execute_set_intent_clean(intent, env, printer),
         Verb::Ensure => execute_ensure_intent_clean(intent, env, printer),
         Verb::Writeout => execute_writeout_intent_clean(intent, env, printer),
@@ -992,6 +1285,8 @@ fn execute_intent_in_test_env(
         
         // File operations
         Verb::Save => execute_save_intent_clean(intent, env, filesystem, printer),
+        Verb::Snapshot | Verb::Restore => execute_snapshot_intent(intent, env, library, printer),
+        Verb::DiffEnv => execute_diff_env_intent(intent, printer),
         Verb::Read => execute_read_intent_clean(intent, env, filesystem, printer),
         Verb::Write => execute_write_intent_clean(intent, env, filesystem, printer),
         Verb::Append => execute_append_intent_clean(intent, env, filesystem, printer),
@@ -1043,7 +1338,7 @@ fn execute_intent_in_test_env(
         
         // Transaction operations
         Verb::Craft => execute_craft_intent(intent, env, printer),
-        Verb::Forge => execute_forge_intent(env, printer),
+        Verb::Forge => execute_forge_intent(env, engine_manager, printer),
         Verb::Smelt => execute_smelt_intent(env, printer),
         Verb::Temper => execute_temper_intent(env, printer),
         Verb::Inspect => execute_inspect_intent(env, printer),
@@ -1053,7 +1348,7 @@ fn execute_intent_in_test_env(
         Verb::Alloy => execute_alloy_intent(intent, env, printer),
         Verb::Engrave => execute_engrave_intent(intent, env, printer),
         Verb::Gild => execute_gild_intent(intent, env, printer),
-        Verb::Patina => execute_patina_intent(intent, env, printer),
+        Verb::Patina => execute_patina_intent(intent, env, engine_manager, printer),
         Verb::Transaction => execute_transaction_intent(env, printer),
         
         Verb::WhatIf => execute_what_if_intent(intent, env, printer),
@@ -1062,6 +1357,10 @@ fn execute_intent_in_test_env(
         Verb::ParseJson => execute_parse_json_intent(intent, env, printer),
         Verb::ToJson => execute_to_json_intent(intent, env, printer),
         Verb::FromJson => execute_from_json_intent(intent, env, printer),
+        Verb::ParseYaml => execute_parse_yaml_intent(intent, env, printer),
+        Verb::ToYaml => execute_to_yaml_intent(intent, env, printer),
+        Verb::ParseCsv => execute_parse_csv_intent(intent, env, printer),
+        Verb::ToCsv => execute_to_csv_intent(intent, env, printer),
         Verb::JsonGet => execute_json_get_intent(intent, env, printer),
         Verb::JsonSet => execute_json_set_intent(intent, env, printer),
         
@@ -1077,7 +1376,7 @@ fn execute_intent_in_test_env(
                         intent, 
                         env,
                         library,
-                        &HashMap::new(), // You'll need actual defined_intents
+                        &env.defined_intents().clone(),
                         &validator,
                         printer
                     )
@@ -1086,20 +1385,9 @@ fn execute_intent_in_test_env(
             }
         },
         
-        Verb::Construct => {
-            let mut defined_intents_copy = HashMap::new(); // Replace with actual reference
-            execute_construct_intent(intent, &mut defined_intents_copy, printer)
-        },
-        
-        Verb::Evolve => {
-            let mut defined_intents_copy = HashMap::new(); // Replace with actual reference
-            execute_evolve_intent(intent, &mut defined_intents_copy, printer)
-        },
-        
-        Verb::Grow => {
-            let mut defined_intents_copy = HashMap::new(); // Replace with actual reference
-            execute_grow_intent(intent, &mut defined_intents_copy, printer)
-        },
+        Verb::Construct => execute_construct_intent(intent, env.defined_intents_mut(), printer),
+        Verb::Evolve => execute_evolve_intent(intent, env.defined_intents_mut(), printer),
+        Verb::Grow => execute_grow_intent(intent, env.defined_intents_mut(), printer),
         
         // Phase 3: Reflection Programming
         Verb::Reflect => {
@@ -1108,7 +1396,7 @@ fn execute_intent_in_test_env(
                     execute_reflect_intent(
                         intent,
                         env,
-                        &HashMap::new(), // You'll need actual defined_intents
+                        &env.defined_intents().clone(),
                         &validator,
                         printer
                     )
@@ -1121,16 +1409,69 @@ fn execute_intent_in_test_env(
             execute_test_intent(
                 intent,
                 env,
-                &HashMap::new(), // You'll need actual defined_intents  
+                &env.defined_intents().clone(),
                 printer
             )
         },
         
-        Verb::Adopt => {
-            let mut defined_intents_copy = HashMap::new(); // Replace with actual reference
-            execute_adopt_intent(intent, &mut defined_intents_copy, printer)
+        Verb::Adopt => execute_adopt_intent(intent, env.defined_intents_mut(), printer),
+
+        // Project tooling
+        Verb::Manifest => execute_manifest_intent(intent, env, printer),
+
+        // Data tooling
+        Verb::Schema => execute_schema_intent(intent, env, printer),
+        Verb::Generate => execute_generate_intent(intent, env, printer),
+        Verb::DiffDicts => execute_diff_dicts_intent(intent, env, printer),
+        Verb::Merge => execute_merge_intent(intent, env, printer),
+        Verb::AssertSchema => execute_assert_schema_intent(intent, env, printer),
+        Verb::Summarize => execute_summarize_intent(intent, env, printer),
+        Verb::Pivot | Verb::Unpivot => execute_pivot_intent(intent, env, printer),
+        Verb::Join | Verb::LeftJoin => execute_join_intent(intent, env, printer),
+        Verb::Window => execute_window_intent(intent, env, printer),
+        Verb::Interpolate => execute_interpolate_intent(intent, env, printer),
+        Verb::Normalize => execute_normalize_intent(intent, env, printer),
+        Verb::OneHot | Verb::LabelEncode => execute_encode_intent(intent, env, printer),
+        Verb::Coalesce => execute_coalesce_intent(intent, env, printer),
+        Verb::Filter => execute_filter_intent(intent, env, printer),
+        Verb::Transform => execute_transform_intent(intent, env, printer),
+        Verb::Reduce => execute_reduce_intent(intent, env, printer),
+        Verb::Sort => execute_sort_intent(intent, env, printer),
+        Verb::Switch => execute_switch_intent(intent, env, filesystem, library, history, history_manager, engine_manager, printer),
+        Verb::Try => execute_try_intent(intent, env, filesystem, library, history, history_manager, engine_manager, printer),
+        Verb::Raise => execute_raise_intent(intent, env, printer),
+        Verb::Each => execute_each_intent(intent, env, filesystem, library, history, history_manager, engine_manager, printer),
+        Verb::Assert => execute_assert_intent(intent, env, printer),
+        Verb::Trace => execute_trace_intent(intent, env, printer),
+        Verb::Watch => execute_watch_intent(intent, env, filesystem, library, history, history_manager, engine_manager, printer),
+        Verb::Benchmark => execute_benchmark_intent(intent, env, filesystem, library, history, history_manager, engine_manager, printer),
+        Verb::Capture => execute_capture_intent(intent, env, filesystem, library, history, history_manager, engine_manager, printer),
+        Verb::Redirect => execute_redirect_intent(intent, env, filesystem, library, history, history_manager, engine_manager, printer),
+        Verb::Log => execute_log_intent(intent, env, printer),
+        Verb::Config => execute_config_intent(intent, env, printer),
+        Verb::Notify => execute_notify_intent(intent, env, printer),
+        Verb::EnvVar => execute_env_var_intent(intent, env, printer),
+        Verb::Export => execute_export_intent(intent, env, printer),
+        Verb::Man => execute_man_intent(intent, printer),
+        Verb::Tutorial => execute_tutorial_intent(intent, env, filesystem, library, history, history_manager, engine_manager, printer),
+        Verb::Changelog => execute_changelog_intent(intent, printer),
+        Verb::Feedback => execute_feedback_intent(intent, printer),
+        Verb::Alias | Verb::Unalias | Verb::Aliases => execute_alias_intent(intent, printer),
+        Verb::Unset => execute_unset_intent(intent, env, printer),
+        Verb::Rename => execute_rename_intent(intent, env, printer),
+        Verb::Copy => execute_copy_intent(intent, env, printer),
+        Verb::Swap => execute_swap_intent(intent, env, history_manager, printer),
+        Verb::Cast => execute_cast_intent(intent, env, printer),
+        Verb::Examples => execute_examples_intent(intent, env, filesystem, library, history, history_manager, engine_manager, printer),
+        Verb::Completion => match &intent.target {
+            Some(Target::Expression(shell)) => crate::completions::CompletionGenerator::new().generate(shell),
+            _ => Err("completions requires a shell: bash, zsh or fish".to_string()),
         },
-    }
+    };
+    
+    // Lazy variables read while running the intent keep their computed value
+    env.settle_lazy();
+    result
 }
 
 pub fn execute_intent(
@@ -1156,6 +1497,45 @@ pub fn execute_intent(
 }
 
 fn execute_set_intent_clean(
+    intent: &crate::core::intent::Intent, 
+    env: &mut Env,
+    printer: &Printer,
+) -> Result<String, String> {
+    let var_name = match &intent.target {
+        Some(Target::Variable(var_name)) => var_name,
+        _ => return execute_set_value(intent, env, printer),
+    };
+    
+    env.check_writable(var_name)?;
+    let was_final = env.is_final(var_name);
+    
+    if intent.parameters.get("modifier").is_some_and(|modifier| modifier == "lazy") {
+        let value_str = intent.parameters.get("value")
+            .ok_or("No value specified in set intent")?;
+        let expr = crate::core::expr::parse_expression(value_str)?;
+        env.set_lazy(var_name, &expr);
+        return Ok(format!("[+] {} = <lazy>\n  Expression: {}", var_name, expr));
+    }
+    
+    let mut output = execute_set_value(intent, env, printer)?;
+    
+    // Setting rebuilds the variable, so modifiers are applied once it exists
+    if was_final {
+        env.mark_final(var_name)?;
+    }
+    if let Some(modifier) = intent.parameters.get("modifier") {
+        match modifier.as_str() {
+            "readonly" => env.mark_readonly(var_name)?,
+            "final" => env.mark_final(var_name)?,
+            other => return Err(format!("Unknown variable modifier '{}'", other)),
+        }
+        output.push_str(&format!("\n  Modifier: {}", modifier));
+    }
+    
+    Ok(output)
+}
+
+fn execute_set_value(
     intent: &crate::core::intent::Intent, 
     env: &mut Env,
     _printer: &Printer,
@@ -1675,11 +2055,19 @@ fn parse_multiline_json_object(input: &str) -> Result<crate::core::expr::Expr, S
 fn execute_writeout_intent_clean(
     intent: &crate::core::intent::Intent, 
     env: &Env,
-    _printer: &Printer,
+    printer: &Printer,
 ) -> Result<String, String> {
     if let Some(Target::Expression(content)) = &intent.target {
         match parse_interpolated_string(content, env) {
             Ok(result) => {
+                // Inside `capture`, the text becomes the captured value instead,
+                // without the quotes around a literal template
+                let captured = result.strip_prefix('"')
+                    .and_then(|inner| inner.strip_suffix('"'))
+                    .unwrap_or(&result);
+                if printer.capture(captured) {
+                    return Ok(String::new());
+                }
                 Ok(format!("[+] Output: {}", result))
             }
             Err(e) => Err(format!("[-] {}", e)),
@@ -1917,6 +2305,56 @@ fn execute_save_intent_clean(
     }
 }
 
+fn execute_snapshot_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    library: &mut Library,
+    _printer: &Printer,
+) -> Result<String, String> {
+    use crate::core::snapshot::Snapshot;
+    
+    let path = match &intent.target {
+        Some(Target::File(path)) => path,
+        _ => return Err("snapshot requires a file target".to_string()),
+    };
+    
+    if intent.verb == Verb::Snapshot {
+        let snapshot = Snapshot::capture(env, library);
+        snapshot.save(path)?;
+        return Ok(format!("[+] Snapshot saved to {} ({} variables, {} bookmarks, {} volumes)",
+            path, snapshot.variables.len(), snapshot.bookmarks.len(), snapshot.volumes.len()));
+    }
+    
+    let replace = intent.parameters.get("replace").is_some_and(|r| r == "true");
+    let snapshot = Snapshot::load(path)?;
+    let created = snapshot.created.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    let (names, skipped) = snapshot.apply(env, library, replace)?;
+    let mut output = format!("[+] Restored {} variables from {} ({}, taken {})",
+        names.len(), path, if replace { "replaced" } else { "merged" }, created);
+    if !skipped.is_empty() {
+        output.push_str(&format!("\n  Kept frozen/readonly: {}", skipped.join(", ")));
+    }
+    Ok(output)
+}
+
+fn execute_diff_env_intent(
+    intent: &crate::core::intent::Intent,
+    _printer: &Printer,
+) -> Result<String, String> {
+    use crate::core::snapshot::Snapshot;
+    
+    let before = intent.parameters.get("before")
+        .ok_or("diff-env requires two snapshot files".to_string())?;
+    let after = intent.parameters.get("after")
+        .ok_or("diff-env requires two snapshot files".to_string())?;
+    
+    let diff = Snapshot::load(before)?.diff(&Snapshot::load(after)?);
+    if diff.is_empty() {
+        return Ok(format!("[+] No differences between {} and {}", before, after));
+    }
+    Ok(format!("[+] Environment diff:\n{}", diff.format(before, after)))
+}
+
 fn execute_load_intent_clean(
     intent: &crate::core::intent::Intent,
     env: &mut Env,
@@ -1927,7 +2365,7 @@ fn execute_load_intent_clean(
     printer: &Printer,
 ) -> Result<String, String> {
     if let Some(Target::File(path)) = &intent.target {
-        match execute_msh_file_with_env_clean(path, env, history, history_manager, engine_manager, library, printer) {
+        match execute_msh_file_with_env_clean(path, &ScriptOptions::default(), env, history, history_manager, engine_manager, library, printer) {
             Ok((success_count, error_count)) => {
                 Ok(format!("[+] Loaded {} ({} commands, {} success, {} errors)", 
                     path, success_count + error_count, success_count, error_count))
@@ -1939,8 +2377,10 @@ fn execute_load_intent_clean(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_msh_file_with_env_clean(
     filename: &str,
+    options: &ScriptOptions,
     env: &mut Env,
     history: &mut Vec<crate::core::intent::Intent>,
     history_manager: &mut HistoryManager,      // Add this
@@ -1954,93 +2394,12 @@ fn execute_msh_file_with_env_clean(
     if !filename.ends_with(".msh") {
         printer.warning(&format!("File '{}' doesn't have .msh extension", filename));
     }
-    process_script_content(&content, env, history, history_manager, engine_manager, library, printer);
-    
-    let mut success_count = 0;
-    let mut error_count = 0;
+    let (success_count, error_count) = process_script_content(&content, filename, options, env, history, history_manager, engine_manager, library, printer)?;
     
-    for (line_num, line) in content.lines().enumerate() {
-        let line = line.trim();
-        
-        if line.is_empty() {
-            continue;
-        }
-        
-        let line = if let Some(comment_start) = line.find('#') {
-            &line[..comment_start].trim()
-        } else {
-            line
-        };
-        
-        if line.is_empty() {
-            continue;
-        }
-        
-        // Handle system commands in script
-        match line {
-            "env" => {
-                show_env_clean(env, printer);
-                success_count += 1;
-                continue;
-            }
-            "history" => {
-                show_history_clean(history, printer);
-                success_count += 1;
-                continue;
-            }
-            "clear" => {
-                print!("\x1B[2J\x1B[1;1H");
-                success_count += 1;
-                continue;
-            }
-            "help" => {
-                show_help(printer);
-                success_count += 1;
-                continue;
-            }
-            _ => {}
-        }
-        
-        match parse_to_intent(line) {
-            Ok(mut line_intent) => {
-                if line_intent.state == IntentState::NeedsClarification {
-                    continue;
-                }
-                
-                line_intent = line_intent
-                    .with_context("source", "script")
-                    .with_context("script", filename)
-                    .with_context("line", &(line_num + 1).to_string());
-                
-                line_intent.state = IntentState::Parsed;
-                history.push(line_intent.clone());
-                
-                line_intent.state = IntentState::Executing;
-                
-                // Create filesystem for script execution
-                let filesystem = FileSystem::new();
-                
-                match execute_intent(&line_intent, env, &filesystem, library, history, history_manager, engine_manager, printer) {
-                    Ok(output) => {
-                        println!("{}", output);
-                        line_intent.state = IntentState::Succeeded;
-                        success_count += 1;
-                    }
-                    Err(e) => {
-                        printer.error(&format!("Line {}: {}", line_num + 1, e));
-                        line_intent.state = IntentState::Failed;
-                        error_count += 1;
-                    }
-                }
-                
-                if let Some(last) = history.last_mut() {
-                    last.state = line_intent.state.clone();
-                    last.context.extend(line_intent.context.clone());
-                }
-            }
-            Err(e) => {
-                printer.error(&format!("Line {}: {}", line_num + 1, e));
-                error_count += 1;
+    if error_count > 0 {
+        if let Some((title, message)) = env.on_error_notification().cloned() {
+            if let Err(e) = send_audited_notification(&title, &message) {
+                printer.warning(&e);
             }
         }
     }
@@ -2055,8 +2414,12 @@ fn execute_ensure_intent_clean(
 ) -> Result<String, String> {
     match &intent.target {
         Some(Target::Variable(var_name)) => {
-            if let Some(condition) = &intent.condition {
-                let desired_value = parse_simple_value(&condition.right, None)?;
+            // `ensure x = value` arrives as a "value" parameter rather than a condition
+            let desired = intent.condition.as_ref()
+                .map(|condition| condition.right.as_str())
+                .or(intent.parameters.get("value").map(|value| value.as_str()));
+            if let Some(desired) = desired {
+                let desired_value = parse_simple_value(desired, None)?;
                 
                 let current_val = env.get_value(var_name).cloned();
                 
@@ -2070,8 +2433,13 @@ fn execute_ensure_intent_clean(
                                 return Err(format!("[-] Cannot change {}: variable is frozen", var_name));
                             }
                         }
+                        env.check_writable(var_name)?;
+                        let was_final = env.is_final(var_name);
                         
                         env.set_direct(var_name, desired_value.clone());
+                        if was_final {
+                            env.mark_final(var_name)?;
+                        }
                         
                         let propagated = crate::core::propagate::propagate_from(env, var_name)
                             .unwrap_or_default();
@@ -2184,6 +2552,16 @@ fn execute_analyze_intent_clean(
             output.push_str(&format!("\n  Type: {}", var.value.type_name()));
             output.push_str(&format!("\n  Source: {:?}", var.source));
             output.push_str(&format!("\n  Frozen: {}", var.is_constant));
+            if var.is_readonly {
+                output.push_str("\n  Readonly: true");
+            }
+            if var.is_final {
+                output.push_str("\n  Final: true");
+            }
+            if var.is_lazy {
+                let state = if var.lazy_expr.is_some() { "pending" } else { "cached" };
+                output.push_str(&format!("\n  Lazy: {}", state));
+            }
             
             if let Some(expr_str) = &var.expression {
                 output.push_str(&format!("\n  Expression: {}", expr_str));
@@ -2312,7 +2690,61 @@ fn execute_defined_intent(
     }
 }
 
-fn execute_msh_file(filename: &str) -> Result<(), String> {
+// Flags from the command line that change how a script file runs
+#[derive(Debug, Clone, Default)]
+struct ScriptOptions {
+    strict: bool,                   // --strict: stop at the first failed intent
+    vars: Vec<(String, String)>,    // --var name=value, set before the first line runs
+    dry_run: bool,                  // --dry-run: parse every statement, execute nothing
+    profile: bool,                  // --profile: time every statement and print a report
+}
+
+impl ScriptOptions {
+    fn apply_vars(&self, env: &mut Env) -> Result<(), String> {
+        for (name, value) in &self.vars {
+            env.set_direct(name, parse_simple_value(value, None)?);
+        }
+        Ok(())
+    }
+}
+
+// A MORRIS_* switch in the environment morris was launched from: set, non-empty and not "0"
+fn env_switch(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+fn show_cli_help() {
+    println!("Morris - Memory Organization & Reactive Recursive Intent-driven System");
+    println!();
+    println!("Usage:");
+    println!("  morris                         Start the interactive shell");
+    println!("  morris [options] <file.msh>    Run a script");
+    println!("  morris [options] -e <intent>   Run intents from the command line (repeatable)");
+    println!();
+    println!("Options:");
+    println!("  -e, --eval <intent>     Execute an intent and exit; several -e run in order");
+    println!("  --var <name>=<value>    Set a variable before the script starts (repeatable)");
+    println!("  --strict                Stop at the first failed intent");
+    println!("  --dry-run               Parse the script and report errors without executing");
+    println!("  --profile               Time each intent and print the slowest first");
+    println!("  --sandbox               Refuse exec_shell(), http_get()/http_post() and feedback send (same as MORRIS_SANDBOX=1)");
+    println!("  --allow-http            Permit http_get()/http_post() and feedback send (same as MORRIS_ALLOW_HTTP=1)");
+    println!("  --color, --no-color     Force ANSI colors on or off (NO_COLOR is honored)");
+    println!("  --init <name>           Create a new project directory from the built-in template");
+    println!("  --check [dir]           Validate a project's config and scripts, exit 1 on critical issues");
+    println!("  --completions <shell>   Print a bash, zsh or fish completion script");
+    println!("  -h, --help              Show this help");
+    println!();
+    println!("Passing values to scripts:");
+    println!("  morris --var env=prod --var retries=3 deploy.msh");
+    println!("    'env' and 'retries' are ordinary variables inside deploy.msh.");
+    println!("  MORRIS_ENV=prod morris deploy.msh");
+    println!("    with 'env-var MORRIS_ENV into env' at the top of deploy.msh.");
+    println!();
+    println!("Exit codes: 0 when every intent succeeds, 1 when any fails, 2 for bad arguments.");
+}
+
+fn execute_msh_file(filename: &str, options: &ScriptOptions) -> Result<(usize, usize), String> {
     let mut env = Env::new();
     let mut history: Vec<crate::core::intent::Intent> = Vec::new();
     let mut history_manager = HistoryManager::new();  // Create instance
@@ -2321,17 +2753,105 @@ fn execute_msh_file(filename: &str) -> Result<(), String> {
     let printer = Printer::new();
     
     printer.header(&format!("Executing script: {}", filename));
+    options.apply_vars(&mut env)?;
     
-    match execute_msh_file_with_env_clean(filename, &mut env, &mut history, &mut history_manager, &mut engine_manager, &mut library, &printer) {
+    match execute_msh_file_with_env_clean(filename, options, &mut env, &mut history, &mut history_manager, &mut engine_manager, &mut library, &printer) {
         Ok((success_count, error_count)) => {
             printer.success(&format!("Script complete: {} commands, {} success, {} errors", 
                 success_count + error_count, success_count, error_count));
-            Ok(())
+            Ok((success_count, error_count))
         }
         Err(e) => Err(e),
     }
 }
 
+// Project skeleton for `morris --init`, embedded so it works from any install location
+const PROJECT_TEMPLATE: &[(&str, &str)] = &[
+    ("main.msh", include_str!("templates/project/main.msh")),
+    ("lib/helpers.msh", include_str!("templates/project/helpers.msh")),
+    (".morris_config.toml", include_str!("templates/project/morris_config.toml")),
+    ("README.md", include_str!("templates/project/README.md")),
+];
+
+fn init_project(name: &str) -> Result<Vec<String>, String> {
+    let root = std::path::Path::new(name);
+    if root.exists() {
+        return Err(format!("'{}' already exists", name));
+    }
+    
+    let project_name = root.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid project name: {}", name))?;
+    
+    let mut created = Vec::new();
+    for dir in [root.to_path_buf(), root.join("lib"), root.join("data")] {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Cannot create '{}': {}", dir.display(), e))?;
+        created.push(format!("{}/", dir.display()));
+    }
+    
+    for (relative, template) in PROJECT_TEMPLATE {
+        let path = root.join(relative);
+        fs::write(&path, template.replace("{{project_name}}", &project_name))
+            .map_err(|e| format!("Cannot write '{}': {}", path.display(), e))?;
+        created.push(path.display().to_string());
+    }
+    
+    Ok(created)
+}
+
+// Parse every statement of a script without executing anything.
+// Returns (statements parsed, parse errors).
+fn dry_run_msh_file(filename: &str) -> Result<(usize, usize), String> {
+    let content = fs::read_to_string(filename)
+        .map_err(|e| format!("Cannot read file '{}': {}", filename, e))?;
+    let printer = Printer::new();
+    
+    let mut intent_count = 0;
+    let mut error_count = 0;
+    
+    for statement in crate::core::script::split_script_statements(&content) {
+        intent_count += 1;
+        if let Err(e) = parse_to_intent(&statement.text) {
+            printer.error(&format!("Line {}: {}", statement.line, e));
+            error_count += 1;
+        }
+    }
+    
+    Ok((intent_count, error_count))
+}
+
+// One-liner mode: each -e intent shares one env and prints its raw output
+fn execute_eval_intents(intents: &[String], options: &ScriptOptions) -> Result<(), String> {
+    let mut env = Env::new();
+    options.apply_vars(&mut env)?;
+    let mut history: Vec<crate::core::intent::Intent> = Vec::new();
+    let mut history_manager = HistoryManager::new();
+    let mut engine_manager = ChangeEngineManager::new();
+    let mut library = Library::new();
+    let filesystem = FileSystem::new();
+    let printer = Printer::new();
+    
+    for input in intents {
+        let intent = parse_to_intent(input)
+            .map_err(|e| format!("{}: {}", input, e))?;
+        if intent.state == IntentState::NeedsClarification {
+            continue;
+        }
+        
+        let intent = intent.with_context("source", "eval");
+        history.push(intent.clone());
+        
+        let output = execute_intent(&intent, &mut env, &filesystem, &mut library, &mut history, &mut history_manager, &mut engine_manager, &printer)
+            .map_err(|e| format!("{}: {}", input, e))?;
+        if !output.is_empty() {
+            println!("{}", output);
+        }
+    }
+    
+    Ok(())
+}
+
 // Keep all helper functions as they were
 fn parse_simple_value(input: &str, type_hint: Option<&str>) -> Result<crate::core::types::Value, String> {
     let trimmed = input.trim();
@@ -2391,6 +2911,8 @@ fn parse_simple_value(input: &str, type_hint: Option<&str>) -> Result<crate::cor
                 Ok(crate::core::types::Value::Bool(true))
             } else if cleaned == "false" {
                 Ok(crate::core::types::Value::Bool(false))
+            } else if cleaned == "null" {
+                Ok(crate::core::types::Value::Null)
             } else if let Ok(num) = cleaned.parse::<i64>() {
                 Ok(crate::core::types::Value::Int(num))
             } else {
@@ -2408,7 +2930,6 @@ fn looks_like_conditional(s: &str) -> bool {
     for i in 0..chars.len() {
         match chars[i] {
             '"' => in_quotes = !in_quotes,
-            '|' if !in_quotes => return true,
             'w' if !in_quotes && i + 4 <= chars.len() => {
                 let word: String = chars[i..i+4].iter().collect();
                 if word == "when" {
@@ -2437,7 +2958,8 @@ fn apply_type_hint(value: crate::core::types::Value, hint: Option<&str>) -> Resu
                 crate::core::types::Value::Int(i) => Ok(crate::core::types::Value::Int(i)),
                 crate::core::types::Value::Float(f) => Ok(crate::core::types::Value::Int(f as i64)),
                 crate::core::types::Value::List(_) |
-                crate::core::types::Value::Dict(_) => {
+                crate::core::types::Value::Dict(_) |
+                crate::core::types::Value::Null => {
                     Err(format!("Cannot convert {} to int", value.type_name()))
                 }
                 crate::core::types::Value::Str(s) => {
@@ -2463,7 +2985,8 @@ fn apply_type_hint(value: crate::core::types::Value, hint: Option<&str>) -> Resu
             ":bool" => match value {
                 crate::core::types::Value::Bool(b) => Ok(crate::core::types::Value::Bool(b)),
                 crate::core::types::Value::List(_) | 
-                crate::core::types::Value::Dict(_) => {
+                crate::core::types::Value::Dict(_) |
+                crate::core::types::Value::Null => {
                     Err(format!("Cannot convert {} to bool", value.type_name()))
                 }
                 crate::core::types::Value::Str(s) => match s.to_lowercase().as_str() {
@@ -2951,6 +3474,18 @@ fn execute_engine_status_intent(
     output.push_str(&format!("\n  Intent Definitions: {}", stats.intent_definitions));
     output.push_str(&format!("\n  Propagation Rules: {}", stats.propagation_rules));
     output.push_str(&format!("\n  Hooks: {}", stats.hooks));
+    output.push_str(&format!("\n  Forged Transactions: {}", stats.forged_transactions));
+    
+    if let Some(last) = engine_manager.engine.forge_records.last() {
+        let when = chrono::DateTime::from_timestamp(last.forged_at, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        output.push_str(&format!("\n  Last Forge: {} {} @ {} ({} changes)",
+            &last.transaction_id[..8], last.name.as_deref().unwrap_or("unnamed"), when, last.changes.len()));
+        for (key, value) in &last.metadata {
+            output.push_str(&format!("\n    {} = {}", key, value));
+        }
+    }
     
     Ok(output)
 }
@@ -3056,10 +3591,27 @@ fn execute_craft_intent(
 
 fn execute_forge_intent(
     env: &mut Env,
+    engine_manager: &mut ChangeEngineManager,
     _printer: &Printer,
 ) -> Result<String, String> {
     match env.forge() {
         Ok(applied) => {
+            // Keep a durable record of what this forge changed and why (engrave metadata)
+            if let Some(transaction) = env.last_transaction().filter(|_| !applied.is_empty()) {
+                let record = crate::core::change_engine::ForgeRecord {
+                    transaction_id: transaction.id.to_string(),
+                    name: transaction.name.clone(),
+                    forged_at: chrono::Utc::now().timestamp(),
+                    metadata: transaction.metadata.iter()
+                        .map(|(key, value)| (key.clone(), value.to_string()))
+                        .collect(),
+                    changes: applied.iter()
+                        .filter_map(|name| env.get_value(name).map(|value| (name.clone(), value.display())))
+                        .collect(),
+                };
+                engine_manager.record_forge(record);
+            }
+            
             if applied.is_empty() {
                 Ok("[🛠] Forged empty transaction (no changes)".to_string())
             } else {
@@ -3084,7 +3636,7 @@ fn execute_smelt_intent(
 
 fn execute_temper_intent(
     env: &mut Env,
-    _printer: &Printer,
+    printer: &Printer,
 ) -> Result<String, String> {
     match env.temper() {
         Ok(preview) => {
@@ -3144,8 +3696,13 @@ fn execute_temper_intent(
             if !preview.detailed_changes.is_empty() {
                 output.push_str("\n  📋 Detailed Changes:\n");
                 for change in &preview.detailed_changes {
-                    output.push_str(&format!("    • {} = {} (was: {})\n", 
-                        change.variable, change.new_value, change.old_value));
+                    let bullet = if env.is_gilded(&change.variable) {
+                        if printer.use_color { "\x1b[33m★\x1b[0m" } else { "★" }
+                    } else {
+                        "•"
+                    };
+                    output.push_str(&format!("    {} {} = {} (was: {})\n", 
+                        bullet, change.variable, change.new_value, change.old_value));
                     if !change.propagation_targets.is_empty() {
                         output.push_str(&format!("      → Affects: {}\n", change.propagation_targets.join(", ")));
                     }
@@ -3210,37 +3767,110 @@ fn execute_quench_intent(
     }
 }
 
-// Placeholders for Phase 2
-fn execute_polish_intent(_intent: &crate::core::intent::Intent, _env: &mut Env, printer: &Printer) -> Result<String, String> {
-    printer.info("Polish verb will be implemented in Phase 2");
-    Ok("[?] Polish - coming soon".to_string())
-}
-
-fn execute_alloy_intent(_intent: &crate::core::intent::Intent, _env: &mut Env, printer: &Printer) -> Result<String, String> {
-    printer.info("Alloy verb will be implemented in Phase 2");
-    Ok("[?] Alloy - coming soon".to_string())
+fn execute_alloy_intent(intent: &crate::core::intent::Intent, env: &mut Env, _printer: &Printer) -> Result<String, String> {
+    let first = intent.parameters.get("first").ok_or("alloy requires two transaction ids")?;
+    let second = intent.parameters.get("second").ok_or("alloy requires two transaction ids")?;
+    
+    let (variables, conflicts) = env.alloy(first, second)?;
+    
+    let mut output = format!("[🛠] Alloyed {} and {} into the active transaction: {} change(s)", first, second, variables.len());
+    if !variables.is_empty() {
+        output.push_str(&format!("\n  Variables: {}", variables.join(", ")));
+    }
+    if !conflicts.is_empty() {
+        output.push_str("\n\n  ⚠️  Conflicts (later change kept):\n");
+        for conflict in &conflicts {
+            output.push_str(&format!("    • {}\n", conflict));
+        }
+    }
+    output.push_str("\n  Use 'forge' to apply or 'smelt' to discard");
+    Ok(output)
 }
 
-fn execute_engrave_intent(_intent: &crate::core::intent::Intent, _env: &mut Env, printer: &Printer) -> Result<String, String> {
-    printer.info("Engrave verb will be implemented in Phase 2");
-    Ok("[?] Engrave - coming soon".to_string())
+fn execute_engrave_intent(intent: &crate::core::intent::Intent, env: &mut Env, _printer: &Printer) -> Result<String, String> {
+    let key = intent.parameters.get("key").ok_or("engrave requires a key")?;
+    let value = intent.parameters.get("value").ok_or("engrave requires a value")?;
+    
+    env.engrave(key, value)?;
+    Ok(format!("[🛠] Engraved {} = \"{}\" on the active transaction", key, value))
 }
 
-fn execute_gild_intent(_intent: &crate::core::intent::Intent, _env: &mut Env, printer: &Printer) -> Result<String, String> {
-    printer.info("Gild verb will be implemented in Phase 2");
-    Ok("[?] Gild - coming soon".to_string())
+fn execute_gild_intent(intent: &crate::core::intent::Intent, env: &mut Env, _printer: &Printer) -> Result<String, String> {
+    let names: Vec<String> = intent.parameters.get("variables")
+        .map(|list| list.split(',').map(|name| name.to_string()).collect())
+        .unwrap_or_default();
+    
+    env.gild(&names)?;
+    Ok(format!("[★] Gilded {}", names.join(", ")))
 }
 
-fn execute_patina_intent(_intent: &crate::core::intent::Intent, _env: &mut Env, printer: &Printer) -> Result<String, String> {
-    printer.info("Patina verb will be implemented in Phase 2");
-    Ok("[?] Patina - coming soon".to_string())
-}
+fn execute_patina_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    engine_manager: &ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    let name = match &intent.target {
+        Some(Target::Variable(name)) => name,
+        _ => return Err("patina requires a variable".to_string()),
+    };
+    
+    let records = engine_manager.history_for(name);
+    if records.is_empty() {
+        return Ok(format!("[+] No forged changes recorded for '{}'", name));
+    }
+    
+    // One line per forge, newest first, in the spirit of `git log --oneline`
+    let mut output = format!("[+] Patina of {} ({} forges):", name, records.len());
+    for record in records {
+        let when = chrono::DateTime::from_timestamp(record.forged_at, 0)
+            .map(|dt| dt.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let id = if printer.use_color {
+            format!("\x1b[33m{}\x1b[0m", &record.transaction_id[..8])
+        } else {
+            record.transaction_id[..8].to_string()
+        };
+        
+        output.push_str(&format!("\n  {} {} {} = {}", id, when, name, record.changes[name]));
+        if let Some(transaction_name) = &record.name {
+            output.push_str(&format!(" ({})", transaction_name));
+        }
+        for (key, value) in &record.metadata {
+            output.push_str(&format!(" {}={}", key, value));
+        }
+    }
+    
+    if let Some(current) = env.get_value(name) {
+        output.push_str(&format!("\n  Current: {}", current.display()));
+    }
+    
+    Ok(output)
+}
+
+fn execute_polish_intent(_intent: &crate::core::intent::Intent, env: &mut Env, _printer: &Printer) -> Result<String, String> {
+    let summary = env.polish_transaction()?;
+    Ok(format!("[✨] Polished transaction:\n  {}", summary.join("\n  ")))
+}
+
+
+
+
+
 
 fn execute_transaction_intent(
     env: &mut Env,
     _printer: &Printer,
 ) -> Result<String, String> {
-    Ok(env.transaction_status())
+    let mut output = env.transaction_status();
+    let history = env.get_transaction_history(10);
+    if !history.is_empty() {
+        output.push_str("\n  Recent transactions:");
+        for line in history {
+            output.push_str(&format!("\n    {}", line));
+        }
+    }
+    Ok(output)
 }
 
 fn execute_what_if_intent(
@@ -3819,6 +4449,121 @@ fn execute_from_json_intent(
     }
 }
 
+fn execute_parse_yaml_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    // Script lines cannot hold a real newline inside quotes, so literals use \n escapes
+    let yaml = match (intent.parameters.get("yaml"), intent.parameters.get("source")) {
+        (Some(literal), _) => decode_escapes(literal),
+        (None, Some(name)) => match env.get_value(name) {
+            Some(Value::Str(text)) => text.clone(),
+            Some(other) => return Err(format!("parse-yaml requires a string, but '{}' is {}", name, other.type_name())),
+            None => return Err(format!("Variable '{}' not found", name)),
+        },
+        (None, None) => return Err("parse-yaml requires YAML string parameter".to_string()),
+    };
+    
+    let value = crate::core::builtins::parse_yaml(&yaml)?;
+    
+    match intent.parameters.get("into") {
+        Some(var_name) => {
+            env.check_writable(var_name)?;
+            env.set_direct(var_name, value.clone());
+            Ok(format!("[+] Parsed YAML into {}: {}", var_name, value.display()))
+        }
+        None => Ok(format!("[+] Parsed YAML: {}", value.display())),
+    }
+}
+
+fn execute_to_yaml_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(var_name) = intent.parameters.get("variable") {
+        if let Some(value) = env.get_value(var_name) {
+            let yaml_str = crate::core::builtins::to_yaml(value)?;
+            Ok(format!("[+] YAML:\n{}", yaml_str.trim_end()))
+        } else {
+            Err(format!("Variable '{}' not found", var_name))
+        }
+    } else {
+        Err("to-yaml requires variable name parameter".to_string())
+    }
+}
+
+fn execute_parse_csv_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let csv_text = match (intent.parameters.get("csv"), intent.parameters.get("source")) {
+        (Some(literal), _) => decode_escapes(literal),
+        (None, Some(name)) => match env.get_value(name) {
+            Some(Value::Str(text)) => text.clone(),
+            Some(other) => return Err(format!("parse-csv requires a string, but '{}' is {}", name, other.type_name())),
+            None => return Err(format!("Variable '{}' not found", name)),
+        },
+        (None, None) => return Err("parse-csv requires CSV string parameter".to_string()),
+    };
+    let headers = intent.parameters.get("headers").is_none_or(|flag| flag != "false");
+    
+    let value = crate::core::builtins::parse_csv(&csv_text, headers)?;
+    let row_count = match &value {
+        Value::List(rows) => rows.len(),
+        _ => 0,
+    };
+    
+    match intent.parameters.get("into") {
+        Some(var_name) => {
+            env.check_writable(var_name)?;
+            env.set_direct(var_name, value.clone());
+            Ok(format!("[+] Parsed CSV into {} ({} rows): {}", var_name, row_count, value.display()))
+        }
+        None => Ok(format!("[+] Parsed CSV ({} rows): {}", row_count, value.display())),
+    }
+}
+
+fn execute_to_csv_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let var_name = intent.parameters.get("variable")
+        .filter(|name| !name.is_empty())
+        .ok_or("to-csv requires variable name".to_string())?;
+    let value = env.get_value(var_name)
+        .ok_or_else(|| format!("Variable '{}' not found", var_name))?;
+    
+    let csv_text = crate::core::builtins::to_csv(value)?;
+    Ok(format!("[+] CSV:\n{}", csv_text.trim_end()))
+}
+
+fn decode_escapes(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => decoded.push('\n'),
+            Some('t') => decoded.push('\t'),
+            Some('"') => decoded.push('"'),
+            Some('\\') => decoded.push('\\'),
+            Some(other) => {
+                decoded.push('\\');
+                decoded.push(other);
+            }
+            None => decoded.push('\\'),
+        }
+    }
+    decoded
+}
+
 fn execute_json_get_intent(
     intent: &crate::core::intent::Intent,
     env: &mut Env,
@@ -3873,8 +4618,11 @@ fn execute_json_set_intent(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn process_script_content(
     content: &str,
+    script_name: &str,
+    options: &ScriptOptions,
     env: &mut Env,
     history: &mut Vec<crate::core::intent::Intent>,
     history_manager: &mut HistoryManager,
@@ -3884,112 +4632,16 @@ fn process_script_content(
 ) -> Result<(usize, usize), String> {
     let mut success_count = 0;
     let mut error_count = 0;
+    let mut timings: Vec<(usize, String, String, std::time::Duration)> = Vec::new();
     
-    let mut lines = content.lines().enumerate().peekable();
-    let mut accumulated_statement = String::new();
-    let mut in_multiline_block = false;
-    let mut multiline_type = MultilineType::Generic;
-    
-    while let Some((line_num, original_line)) = lines.next() {
-        let line = original_line.trim();
-        
-        // Skip empty lines and comments
-        if line.is_empty() {
-            // Only accumulate if we're in a multiline context
-            if in_multiline_block || !accumulated_statement.is_empty() {
-                if !accumulated_statement.is_empty() {
-                    accumulated_statement.push_str(original_line);
-                    accumulated_statement.push('\n');
-                }
-            }
-            continue;
-        }
-        
-        let line_without_comment = if let Some(comment_start) = line.find('#') {
-            &line[..comment_start].trim()
-        } else {
-            line
-        };
-        
-        if line_without_comment.is_empty() {
-            if in_multiline_block || !accumulated_statement.is_empty() {
-                if !accumulated_statement.is_empty() {
-                    accumulated_statement.push_str(original_line);
-                    accumulated_statement.push('\n');
-                }
-            }
-            continue;
-        }
-        
-        // Check for statement termination with semicolon
-        let ends_with_semicolon = line_without_comment.ends_with(';');
-        let statement_content = if ends_with_semicolon {
-            &line_without_comment[..line_without_comment.len()-1]
-        } else {
-            line_without_comment
-        };
-        
-        // If we have accumulated content, add current line
-        if !accumulated_statement.is_empty() || (!ends_with_semicolon && !in_multiline_block) {
-            if accumulated_statement.is_empty() {
-                accumulated_statement.push_str(original_line);
-            } else {
-                accumulated_statement.push_str(&format!("\n{}", original_line));
-            }
-        }
-        
-        // If statement is terminated with semicolon, process it
-        if ends_with_semicolon && !in_multiline_block {
-            // Process the complete statement
-            match execute_script_command(&statement_content, env, history, history_manager, engine_manager, library, printer) {
-                Ok(_) => success_count += 1,
-                Err(e) => {
-                    printer.error(&format!("Line {}: {}", line_num + 1, e));
-                    error_count += 1;
-                }
-            }
-            accumulated_statement.clear();
-            continue;
-        }
-        
-        // Detect multiline start (only if not already accumulating)
-        if !in_multiline_block && accumulated_statement.is_empty() {
-            if is_multiline_block_start(statement_content) {
-                in_multiline_block = true;
-                multiline_type = detect_multiline_type(statement_content);
-                accumulated_statement.push_str(original_line);
-                accumulated_statement.push('\n');
-                continue;
-            }
-        }
-        
-        // Handle multiline block end
-        if in_multiline_block {
-            accumulated_statement.push_str(original_line);
-            accumulated_statement.push('\n');
-            
-            if is_multiline_block_end(line_without_comment) || line_without_comment == ";;" {
-                in_multiline_block = false;
-                
-                // Execute the complete multi-line block
-                match execute_script_command(&accumulated_statement, env, history, history_manager, engine_manager, library, printer) {
-                    Ok(_) => success_count += 1,
-                    Err(e) => {
-                        printer.error(&format!("Block ending line {}: {}", line_num + 1, e));
-                        error_count += 1;
-                    }
-                }
-                
-                accumulated_statement.clear();
-                continue;
-            }
-            
-            // Continue accumulating
-            continue;
+    for statement in crate::core::script::split_script_statements(content) {
+        if options.strict && error_count > 0 {
+            printer.warning("Stopped at first error (--strict)");
+            break;
         }
         
         // Handle system commands (these don't need semicolons)
-        match statement_content {
+        match statement.text.as_str() {
             "env" => {
                 show_env_clean(env, printer);
                 success_count += 1;
@@ -4005,103 +4657,63 @@ fn process_script_content(
                 success_count += 1;
                 continue;
             }
+            "help" => {
+                show_help(printer);
+                success_count += 1;
+                continue;
+            }
             _ => {}
         }
         
-        // If we reach here and have accumulated content, it means we're in a multiline
-        // statement that hasn't been terminated with semicolon
-        if !accumulated_statement.is_empty() && !ends_with_semicolon {
-            // Continue accumulating until we get a semicolon or explicit multiline end
-            continue;
-        }
+        let started = std::time::Instant::now();
+        let result = execute_script_command(&statement.text, (script_name, statement.line), env, history, history_manager, engine_manager, library, printer);
         
-        // Execute single-line commands that don't need semicolons for backward compatibility
-        // But warn about missing semicolons eventually
-        if accumulated_statement.is_empty() {
-            match execute_script_command(statement_content, env, history, history_manager, engine_manager, library, printer) {
-                Ok(_) => success_count += 1,
-                Err(e) => {
-                    printer.error(&format!("Line {}: {}", line_num + 1, e));
-                    error_count += 1;
-                }
+        if options.profile {
+            if let Ok(intent) = parse_to_intent(&statement.text) {
+                timings.push((statement.line, format!("{:?}", intent.verb), intent.target_string(), started.elapsed()));
             }
         }
-    }
-    
-    // Handle any remaining accumulated statement (backward compatibility)
-    if !accumulated_statement.is_empty() {
-        // Strip any trailing semicolons for backward compatibility
-        let final_content = if accumulated_statement.trim_end().ends_with(';') {
-            let trimmed = accumulated_statement.trim_end();
-            &trimmed[..trimmed.len()-1]
-        } else {
-            &accumulated_statement
-        };
         
-        if !final_content.trim().is_empty() {
-            match execute_script_command(final_content, env, history, history_manager, engine_manager, library, printer) {
-                Ok(_) => success_count += 1,
-                Err(e) => {
-                    printer.error(&format!("Incomplete statement: {}", e));
-                    error_count += 1;
+        match result {
+            Ok(_) => success_count += 1,
+            Err(e) => {
+                printer.error(&format!("Line {}: {}", statement.line, e));
+                error_count += 1;
+                if e.starts_with(ASSERTION_FAILED) {
+                    printer.warning("Script halted by failed assertion");
+                    break;
                 }
             }
         }
     }
     
-    Ok((success_count, error_count))
-}
-
-#[derive(Debug, Clone)]
-enum MultilineType {
-    Generic,
-    MatchExpression,
-    ConditionalExpression,
-    Dictionary,
-    List,
-}
-
-fn detect_multiline_type(line: &str) -> MultilineType {
-    let line = line.trim();
-    if line.starts_with("match ") {
-        MultilineType::MatchExpression
-    } else if line.contains('|') && (line.contains("when") || line.contains("otherwise")) {
-        MultilineType::ConditionalExpression
-    } else if line.starts_with('{') || line.contains('{') {
-        MultilineType::Dictionary
-    } else if line.starts_with('[') {
-        MultilineType::List
-    } else {
-        MultilineType::Generic
+    if options.profile {
+        show_profile(&mut timings, printer);
     }
-}
-
-fn is_multiline_block_start(line: &str) -> bool {
-    line.ends_with('{') || 
-    line.starts_with("define intent") ||
-    line.starts_with("define ") && line.contains('{') ||
-    line.contains('{') && line.contains('}') ||
-    
-    // NEW: Match expressions
-    line.trim_start().starts_with("match ") ||
     
-    // NEW: Multi-line conditionals
-    (line.contains('|') && (line.contains("when") || line.contains("otherwise") || line.contains("else"))) ||
-    
-    // NEW: Complex expressions that likely continue
-    line.trim_end().ends_with("when") ||
-    line.trim_end().ends_with("and") ||
-    line.trim_end().ends_with("or") ||
-    line.trim_end().ends_with("|")
+    Ok((success_count, error_count))
 }
 
-fn is_multiline_block_end(line: &str) -> bool {
-    line.trim() == "}" || 
-    line.starts_with('}') && !line.contains('{')
+fn show_profile(timings: &mut [(usize, String, String, std::time::Duration)], printer: &Printer) {
+    timings.sort_by_key(|t| std::cmp::Reverse(t.3));
+    let total: std::time::Duration = timings.iter().map(|t| t.3).sum();
+    
+    printer.header(&format!("Profile ({} intents, {:.3} ms total)", timings.len(), total.as_secs_f64() * 1000.0));
+    printer.emit(&format!("  {:>6}  {:<14} {:<36} {:>10}", "line", "verb", "target", "ms"));
+    for (line, verb, target, duration) in timings.iter() {
+        let target = if target.chars().count() > 36 {
+            format!("{}...", target.chars().take(33).collect::<String>())
+        } else {
+            target.clone()
+        };
+        printer.emit(&format!("  {:>6}  {:<14} {:<36} {:>10.3}", line, verb, target, duration.as_secs_f64() * 1000.0));
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_script_command(
     command: &str,
+    (script_name, line): (&str, usize),
     env: &mut Env,
     history: &mut Vec<crate::core::intent::Intent>,
     history_manager: &mut HistoryManager,
@@ -4117,30 +4729,33 @@ fn execute_script_command(
             
             intent = intent
                 .with_context("source", "script")
+                .with_context("script", script_name)
+                .with_context("line", &line.to_string())
                 .with_context("timestamp", &chrono::Utc::now().to_rfc3339());
             
             intent.state = IntentState::Parsed;
             history.push(intent.clone());
             
-            match execute_intent(&intent, env, &FileSystem::new(), library, history, history_manager, engine_manager, printer) {
+            let result = match execute_intent(&intent, env, &FileSystem::new(), library, history, history_manager, engine_manager, printer) {
                 Ok(output) => {
                     if !output.is_empty() {
-                        println!("{}", output);
+                        printer.emit(&output);
                     }
                     intent.state = IntentState::Succeeded;
+                    Ok(())
                 }
                 Err(e) => {
-                    printer.error(&e);
                     intent.state = IntentState::Failed;
+                    Err(e)
                 }
-            }
+            };
             
             if let Some(last) = history.last_mut() {
                 last.state = intent.state.clone();
                 last.context.extend(intent.context.clone());
             }
             
-            Ok(())
+            result
         }
         Err(e) => Err(e),
     }
@@ -4267,6 +4882,29 @@ fn execute_examine_intent(
                 Ok(output)
             }
             
+            other if other.starts_with("intent ") => {
+                let name = other.trim_start_matches("intent ").trim().trim_matches('"');
+                let intent_def = all_intents.get(name)
+                    .ok_or_else(|| format!("No defined intent '{}'", name))?;
+                
+                let mut output = format!("[+] Intent '{}'\n", name);
+                output.push_str(&format!("  Definition: {}\n", intent_def.definition_text()));
+                if let Some(source) = &intent_def.intent_source {
+                    output.push_str(&format!("  Source: {}\n", source));
+                }
+                output.push_str(&format!("  Modifications: {}\n", intent_def.integrity.modification_count));
+                
+                if !intent_def.revisions.is_empty() {
+                    output.push_str(&format!("  Earlier definitions ({}):\n", intent_def.revisions.len()));
+                    for (i, revision) in intent_def.revisions.iter().enumerate().rev() {
+                        output.push_str(&format!("    {}. {} @ {}\n",
+                            i + 1, revision.definition, revision.replaced_at.format("%Y-%m-%d %H:%M:%S")));
+                    }
+                }
+                
+                Ok(output)
+            }
+            
             _ => Err(format!("Unknown examine target: {}", target_type)),
         }
     } else {
@@ -4301,23 +4939,89 @@ fn execute_construct_intent(
     }
 }
 
-// Placeholder executions for other new verbs
 fn execute_evolve_intent(
-    _intent: &crate::core::intent::Intent,
-    _defined_intents: &mut HashMap<String, crate::core::intent::Intent>,
-    printer: &Printer,
+    intent: &crate::core::intent::Intent,
+    defined_intents: &mut HashMap<String, crate::core::intent::Intent>,
+    _printer: &Printer,
 ) -> Result<String, String> {
-    printer.info("Evolve functionality coming soon");
-    Ok("[?] Evolve - placeholder".to_string())
+    let name = match &intent.target {
+        Some(Target::Expression(name)) => name.trim_matches('"'),
+        _ => return Err("evolve requires an intent name".to_string()),
+    };
+    
+    let current = defined_intents.get(name)
+        .ok_or_else(|| format!("No defined intent '{}'", name))?;
+    
+    // Build the new definition on a copy; the map is only touched once it is valid
+    let mut evolved = current.clone();
+    let change = if let Some(body) = intent.parameters.get("body") {
+        crate::core::expr::parse_expression(body)
+            .map_err(|e| format!("Cannot evolve '{}': new body does not parse ({}); definition unchanged", name, e))?;
+        evolved.target = Some(Target::Expression(body.clone()));
+        format!("body is now {{ {} }}", body)
+    } else {
+        match intent.parameters.get("action").map(|a| a.as_str()) {
+            Some("add_param") => {
+                // add_param "name" [default="value"]
+                let params = intent.parameters.get("params").map(|p| p.as_str()).unwrap_or("");
+                let (param, default) = match params.split_once(" default=") {
+                    Some((param, default)) => (param.trim().trim_matches('"'), default.trim().trim_matches('"')),
+                    None => (params.trim().trim_matches('"'), ""),
+                };
+                if param.is_empty() {
+                    return Err("add_param requires a parameter name".to_string());
+                }
+                evolved.parameter_defs.insert(param.to_string(), default.to_string());
+                format!("added parameter '{}'", param)
+            }
+            Some(other) => return Err(format!("Unknown evolve action '{}'", other)),
+            None => return Err("evolve requires a new body: evolve \"name\" { expression }".to_string()),
+        }
+    };
+    
+    evolved.revisions.push(crate::core::intent::IntentRevision {
+        replaced_at: chrono::Utc::now(),
+        definition: current.definition_text(),
+    });
+    evolved.finalize();
+    
+    let revision = evolved.revisions.len();
+    defined_intents.insert(name.to_string(), evolved);
+    Ok(format!("[+] Evolved intent '{}' (revision {}): {}", name, revision + 1, change))
 }
 
 fn execute_grow_intent(
-    _intent: &crate::core::intent::Intent,
-    _defined_intents: &mut HashMap<String, crate::core::intent::Intent>,
+    intent: &crate::core::intent::Intent,
+    defined_intents: &mut HashMap<String, crate::core::intent::Intent>,
     printer: &Printer,
 ) -> Result<String, String> {
-    printer.info("Grow functionality coming soon");
-    Ok("[?] Grow - placeholder".to_string())
+    let name = match &intent.target {
+        Some(Target::Expression(name)) => name.as_str(),
+        _ => return Err("grow requires an intent name".to_string()),
+    };
+    
+    let composition = defined_intents.get_mut(name)
+        .ok_or_else(|| format!("No defined intent '{}'", name))?;
+    if !composition.is_composition || composition.intent_source.as_deref() != Some("defined_composition") {
+        return Err(format!("'{}' is not a composition; define it with composed_of [...]", name));
+    }
+    
+    let mut added = Vec::new();
+    for step in intent.parameters.get("steps").map(|s| s.split(',')).into_iter().flatten() {
+        if composition.composition_steps.iter().any(|existing| existing == step) {
+            printer.warning(&format!("'{}' is already part of '{}'", step, name));
+        } else {
+            composition.composition_steps.push(step.to_string());
+            added.push(step);
+        }
+    }
+    
+    if added.is_empty() {
+        return Ok(format!("[+] '{}' unchanged", name));
+    }
+    
+    composition.finalize();
+    Ok(format!("[+] Grew '{}' with {}: now {} step(s)", name, added.join(", "), composition.composition_steps.len()))
 }
 
 fn execute_reflect_intent(
@@ -4477,40 +5181,2517 @@ fn execute_adopt_intent(
     }
 }
 
-fn show_morris_logo(printer: &Printer) {
-    // Clear screen for clean startup
-    print!("\x1B[2J\x1B[1;1H");
-    
-    if printer.use_color {
-        println!("\x1b[1;38;5;39m"); // Bright professional blue (same as original)
+fn execute_manifest_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::File(path)) = &intent.target {
+        let manifest = crate::core::manifest::Manifest::load(path)?;
+        let report = manifest.validate(env);
+
+        let title = manifest.name.as_deref().unwrap_or(path.as_str());
+        let mut output = String::new();
+
+        if report.is_clean() {
+            output.push_str(&format!("[✓] Manifest '{}' satisfied ({} variables checked)\n",
+                title, manifest.variables.len()));
+        } else {
+            output.push_str(&format!("[-] Manifest '{}' not satisfied ({} variables checked)\n",
+                title, manifest.variables.len()));
+        }
+        output.push_str(&report.format_summary());
+
+        Ok(output.trim_end().to_string())
+    } else {
+        Err("Manifest requires a .mmf file path".to_string())
     }
-    
-    println!(r"
-        ╔══════════════════════════════════════════╗
-        ║                                          ║
-        ║             M O R R I S                  ║
-        ║                                          ║
-        ║    Memory Organization as a Reactive     ║
-        ║    & Recursive Intent-driven System      ║
-        ║                                          ║
-        ║    ────────────────────────────────      ║
-        ║                                          ║
-        ║    We shape our tools,                   ║
-        ║    and our tools shape us...             ║
-        ║                                          ║
-        ║    ────────────────────────────────      ║
-        ║                                          ║
-        ║    v3.0 • Meta-programmability           ║
-        ║                                          ║
-        ╚══════════════════════════════════════════╝
-    ");
-    
-    if printer.use_color {
-        println!("\x1b[0m"); // Reset
+}
+
+fn execute_schema_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Expression(name)) = &intent.target {
+        let fields = intent.parameters.get("fields")
+            .ok_or("Schema requires a field block".to_string())?;
+
+        let schema = crate::core::schema::Schema::parse(name, fields)?;
+        let output = format!("[+] Schema '{}' registered: {}", name, schema.describe());
+        env.define_schema(schema);
+        Ok(output)
+    } else {
+        Err("Schema requires a name".to_string())
     }
-    
-    println!();
 }
 
+fn execute_generate_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Expression(name)) = &intent.target {
+        let count = intent.parameters.get("count")
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(1);
+        let dest = intent.parameters.get("into")
+            .ok_or("Generate requires 'into <variable>'".to_string())?;
+
+        let schema = env.get_schema(name)
+            .ok_or_else(|| format!("Schema '{}' not found (define it with: schema \"{}\" {{ ... }})", name, name))?;
 
+        let data = crate::core::builtins::generate_data(schema, count)?;
+        env.set_direct(dest, data);
 
+        Ok(format!("[+] Generated {} '{}' rows into {}", count, name, dest))
+    } else {
+        Err("Generate requires a schema name".to_string())
+    }
+}
+
+fn execute_diff_dicts_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let left = intent.parameters.get("left")
+        .ok_or("Diff requires a left variable".to_string())?;
+    let right = intent.parameters.get("right")
+        .ok_or("Diff requires a right variable".to_string())?;
+
+    let a = env.get_value(left)
+        .ok_or_else(|| format!("Variable '{}' not found", left))?;
+    let b = env.get_value(right)
+        .ok_or_else(|| format!("Variable '{}' not found", right))?;
+
+    let diff = crate::core::builtins::diff_dicts(a, b)?;
+    let mut output = format!("[+] Diff {} → {}:\n", left, right);
+    output.push_str(&crate::core::builtins::format_dict_diff(&diff, 2));
+
+    if let Some(dest) = intent.parameters.get("into") {
+        env.set_direct(dest, diff);
+        output.push_str(&format!("  → Stored in {}", dest));
+    }
+
+    Ok(output.trim_end().to_string())
+}
+
+fn execute_merge_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let left = intent.parameters.get("left")
+        .ok_or("Merge requires a left variable".to_string())?;
+    let right = intent.parameters.get("right")
+        .ok_or("Merge requires a right variable".to_string())?;
+    let dest = intent.parameters.get("into")
+        .ok_or("Merge requires 'into <variable>'".to_string())?;
+    let strategy_name = intent.parameters.get("strategy").map(|s| s.as_str()).unwrap_or("last_wins");
+    let strategy = crate::core::builtins::MergeStrategy::parse(strategy_name)?;
+
+    let a = env.get_value(left)
+        .ok_or_else(|| format!("Variable '{}' not found", left))?;
+    let b = env.get_value(right)
+        .ok_or_else(|| format!("Variable '{}' not found", right))?;
+
+    let merged = crate::core::builtins::merge_deep(a, b, strategy)?;
+    let display = merged.display();
+    env.set_direct(dest, merged);
+
+    Ok(format!("[+] Merged {} and {} into {} ({}): {}", left, right, dest, strategy_name, display))
+}
+
+fn execute_assert_schema_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Variable(list_var)) = &intent.target {
+        let schema_name = intent.parameters.get("schema")
+            .ok_or("assert-schema requires a schema name".to_string())?;
+
+        let schema = env.get_schema(schema_name)
+            .ok_or_else(|| format!("Schema '{}' not found", schema_name))?;
+        let rows = match env.get_value(list_var) {
+            Some(Value::List(rows)) => rows,
+            Some(other) => return Err(format!("assert-schema requires a list, got {}", other.type_name())),
+            None => return Err(format!("Variable '{}' not found", list_var)),
+        };
+
+        // Collect every problem instead of stopping at the first one
+        let mut errors = Vec::new();
+        let mut invalid_rows = 0;
+        for (index, row) in rows.iter().enumerate() {
+            let row_errors = schema.validate_row(row);
+            if !row_errors.is_empty() {
+                invalid_rows += 1;
+            }
+            for (field, message) in row_errors {
+                let mut error = HashMap::new();
+                error.insert("row".to_string(), Value::Int(index as i64));
+                error.insert("field".to_string(), Value::Str(field));
+                error.insert("message".to_string(), Value::Str(message));
+                errors.push(Value::Dict(error));
+            }
+        }
+
+        let total = rows.len();
+        let mut output = format!("[{}] Schema '{}' check on {}: {} rows checked, {} valid, {} invalid",
+            if invalid_rows == 0 { "✓" } else { "-" },
+            schema_name, list_var, total, total - invalid_rows, invalid_rows);
+
+        for error in errors.iter().take(5) {
+            if let Value::Dict(e) = error {
+                output.push_str(&format!("\n  • row {}: {}: {}",
+                    e["row"], e["field"].to_string(), e["message"].to_string()));
+            }
+        }
+        if errors.len() > 5 {
+            output.push_str(&format!("\n  ... and {} more errors", errors.len() - 5));
+        }
+
+        if let Some(dest) = intent.parameters.get("into") {
+            env.set_direct(dest, Value::List(errors));
+            output.push_str(&format!("\n  → Report stored in {}", dest));
+        }
+
+        Ok(output)
+    } else {
+        Err("assert-schema requires a list variable".to_string())
+    }
+}
+
+fn execute_summarize_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Variable(list_var)) = &intent.target {
+        let field = intent.parameters.get("field").map(|s| s.as_str());
+        let list = env.get_value(list_var)
+            .ok_or_else(|| format!("Variable '{}' not found", list_var))?;
+
+        let summary = crate::core::builtins::summarize(list, field)?;
+
+        let title = match field {
+            Some(field) => format!("{}.{}", list_var, field),
+            None => list_var.clone(),
+        };
+        let mut output = format!("[+] Summary of {}:", title);
+
+        // Fixed order so the table reads the same every time
+        let order = ["count", "null_count", "unique_count", "min", "max", "mean", "median",
+                     "p25", "p75", "p99", "std_dev", "most_common", "min_length", "max_length"];
+        if let Value::Dict(stats) = &summary {
+            for key in order {
+                if let Some(value) = stats.get(key) {
+                    output.push_str(&format!("\n  {:<14} {}", key, value.display()));
+                }
+            }
+        }
+
+        if let Some(dest) = intent.parameters.get("into") {
+            env.set_direct(dest, summary);
+            output.push_str(&format!("\n  → Stored in {}", dest));
+        }
+
+        Ok(output)
+    } else {
+        Err("Summarize requires a list variable".to_string())
+    }
+}
+
+fn execute_pivot_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Variable(source)) = &intent.target {
+        let param = |key: &str| intent.parameters.get(key)
+            .ok_or_else(|| format!("{:?} requires '{}'", intent.verb, key));
+        let value = env.get_value(source)
+            .ok_or_else(|| format!("Variable '{}' not found", source))?;
+
+        let result = if intent.verb == Verb::Pivot {
+            crate::core::builtins::pivot(value, param("row")?, param("col")?, param("val")?)?
+        } else {
+            crate::core::builtins::unpivot(value, param("row")?, param("val")?)?
+        };
+
+        let dest = param("into")?;
+        let rows = match &result {
+            Value::List(items) => items.len(),
+            _ => 0,
+        };
+        env.set_direct(dest, result);
+
+        Ok(format!("[+] {}ed {} into {} ({} rows)",
+            if intent.verb == Verb::Pivot { "Pivot" } else { "Unpivot" }, source, dest, rows))
+    } else {
+        Err(format!("{:?} requires a source variable", intent.verb))
+    }
+}
+
+fn execute_join_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let left = intent.parameters.get("left")
+        .ok_or("Join requires a left list".to_string())?;
+    let right = intent.parameters.get("right")
+        .ok_or("Join requires a right list".to_string())?;
+    let key = intent.parameters.get("key")
+        .ok_or("Join requires 'on <key>'".to_string())?;
+
+    let a = env.get_value(left)
+        .ok_or_else(|| format!("Variable '{}' not found", left))?;
+    let b = env.get_value(right)
+        .ok_or_else(|| format!("Variable '{}' not found", right))?;
+
+    let (joined, kind) = if intent.verb == Verb::LeftJoin {
+        (crate::core::builtins::left_join(a, b, key)?, "Left-joined")
+    } else {
+        (crate::core::builtins::inner_join(a, b, key)?, "Joined")
+    };
+
+    let rows = match &joined {
+        Value::List(items) => items.len(),
+        _ => 0,
+    };
+
+    match intent.parameters.get("into") {
+        Some(dest) => {
+            env.set_direct(dest, joined);
+            Ok(format!("[+] {} {} and {} on {} into {} ({} rows)", kind, left, right, key, dest, rows))
+        }
+        None => Ok(format!("[+] {} {} and {} on {} ({} rows): {}", kind, left, right, key, rows, joined.display())),
+    }
+}
+
+fn execute_window_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Variable(list_var)) = &intent.target {
+        let size = intent.parameters.get("size")
+            .and_then(|s| s.parse::<usize>().ok())
+            .ok_or("Window requires 'size <n>'".to_string())?;
+        let func = intent.parameters.get("aggregate").map(|s| s.as_str()).unwrap_or("avg");
+
+        let list = env.get_value(list_var)
+            .ok_or_else(|| format!("Variable '{}' not found", list_var))?;
+        let result = crate::core::builtins::sliding_window(list, size, func)?;
+
+        match intent.parameters.get("into") {
+            Some(dest) => {
+                let output = format!("[+] Window {}({}) over {} into {}: {}", func, size, list_var, dest, result.display());
+                env.set_direct(dest, result);
+                Ok(output)
+            }
+            None => Ok(format!("[+] Window {}({}) over {}: {}", func, size, list_var, result.display())),
+        }
+    } else {
+        Err("Window requires a list variable".to_string())
+    }
+}
+
+fn execute_interpolate_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Variable(list_var)) = &intent.target {
+        let method = intent.parameters.get("method").map(|s| s.as_str()).unwrap_or("linear");
+        let list = env.get_value(list_var)
+            .ok_or_else(|| format!("Variable '{}' not found", list_var))?;
+
+        let filled = match method {
+            "linear" => crate::core::builtins::interpolate_linear(list)?,
+            "forward" | "ffill" => crate::core::builtins::interpolate_forward_fill(list)?,
+            "backward" | "bfill" => crate::core::builtins::interpolate_backward_fill(list)?,
+            other => return Err(format!("Unknown interpolation method '{}' (use linear, forward, backward)", other)),
+        };
+
+        // Replace in place unless a destination was given
+        let dest = intent.parameters.get("into").unwrap_or(list_var);
+        let output = format!("[+] Interpolated {} ({}) into {}: {}", list_var, method, dest, filled.display());
+        env.set_direct(dest, filled);
+        Ok(output)
+    } else {
+        Err("Interpolate requires a list variable".to_string())
+    }
+}
+
+fn execute_normalize_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Variable(list_var)) = &intent.target {
+        let method = intent.parameters.get("method").map(|s| s.as_str()).unwrap_or("minmax");
+        let list = env.get_value(list_var)
+            .ok_or_else(|| format!("Variable '{}' not found", list_var))?;
+
+        let scaled = match method {
+            "minmax" | "min-max" => crate::core::builtins::normalize_minmax(list)?,
+            "z-score" | "zscore" => crate::core::builtins::normalize_zscore(list)?,
+            "max" => crate::core::builtins::normalize_max(list)?,
+            other => return Err(format!("Unknown normalize method '{}' (use minmax, z-score, max)", other)),
+        };
+
+        let dest = intent.parameters.get("into").unwrap_or(list_var);
+        let output = format!("[+] Normalized {} ({}) into {}: {}", list_var, method, dest, scaled.display());
+        env.set_direct(dest, scaled);
+        Ok(output)
+    } else {
+        Err("Normalize requires a list variable".to_string())
+    }
+}
+
+fn execute_encode_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Variable(list_var)) = &intent.target {
+        let dest = intent.parameters.get("into")
+            .ok_or("Encoding requires 'into <dest>'".to_string())?;
+        let list = env.get_value(list_var)
+            .ok_or_else(|| format!("Variable '{}' not found", list_var))?;
+
+        if intent.verb == Verb::OneHot {
+            let encoded = crate::core::builtins::one_hot_encode(list)?;
+            let output = format!("[+] One-hot encoded {} into {}: {}", list_var, dest, encoded.display());
+            env.set_direct(dest, encoded);
+            Ok(output)
+        } else {
+            let (codes, mapping) = crate::core::builtins::label_encode(list)?;
+            let mapping_var = format!("{}_mapping", dest);
+            let output = format!("[+] Label encoded {} into {}: {}\n  → Mapping stored in {}: {}",
+                list_var, dest, codes.display(), mapping_var, mapping.display());
+            env.set_direct(dest, codes);
+            env.set_direct(&mapping_var, mapping);
+            Ok(output)
+        }
+    } else {
+        Err("Encoding requires a list variable".to_string())
+    }
+}
+
+fn execute_coalesce_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    if let Some(Target::Expression(items)) = &intent.target {
+        let dest = intent.parameters.get("into")
+            .ok_or("Coalesce requires 'into <variable>'".to_string())?;
+
+        // Same semantics as the coalesce() expression function
+        let expr = crate::core::expr::parse_expression(&format!("coalesce({})", items))?;
+        let value = crate::core::expr::evaluate(&expr, env)?;
+
+        let output = format!("[+] {} = {} (coalesce)", dest, value.display());
+        env.set_direct(dest, value);
+        Ok(output)
+    } else {
+        Err("Coalesce requires a list of variables".to_string())
+    }
+}
+
+fn switch_case_matches(value: &Value, case_value: &str) -> bool {
+    match value {
+        Value::Int(i) => case_value.parse::<f64>().is_ok_and(|c| c == *i as f64),
+        Value::Float(f) => case_value.parse::<f64>().is_ok_and(|c| c == *f),
+        Value::Str(s) => s == case_value,
+        other => other.to_string() == case_value,
+    }
+}
+
+fn strip_block_braces(body: &str) -> &str {
+    let body = body.trim();
+    if body.starts_with('{') && body.ends_with('}') {
+        &body[1..body.len() - 1]
+    } else {
+        body
+    }
+}
+
+// Run a branch body - a single intent or a `{ a; b }` block of intents
+#[allow(clippy::too_many_arguments)]
+fn execute_intent_block(
+    body: &str,
+    outputs: &mut Vec<String>,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<(), String> {
+    for statement in crate::core::intent::split_block_statements(strip_block_braces(body)) {
+        let branch_intent = parse_to_intent(&statement)?;
+        if branch_intent.state == IntentState::NeedsClarification {
+            continue;
+        }
+        let output = execute_intent(&branch_intent, env, filesystem, library, history, history_manager, engine_manager, printer)?;
+        if !output.is_empty() {
+            outputs.push(output);
+        }
+    }
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_switch_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    let var_name = match &intent.target {
+        Some(Target::Variable(name)) => name.clone(),
+        _ => return Err("Switch requires a variable".to_string()),
+    };
+    
+    let value = env.get_value(&var_name)
+        .ok_or_else(|| format!("Variable '{}' not found", var_name))?
+        .clone();
+    
+    let case_count: usize = intent.parameters.get("case_count")
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0);
+    
+    // First matching case wins - no fall-through
+    let mut branch = None;
+    for i in 0..case_count {
+        let case_value = intent.parameters.get(&format!("case_{}_value", i)).cloned().unwrap_or_default();
+        if switch_case_matches(&value, &case_value) {
+            branch = intent.parameters.get(&format!("case_{}_body", i))
+                .map(|body| (format!("case \"{}\"", case_value), body.clone()));
+            break;
+        }
+    }
+    
+    let (label, body) = match branch.or_else(|| intent.parameters.get("default").map(|body| ("default".to_string(), body.clone()))) {
+        Some(branch) => branch,
+        None => return Ok(format!("[-] switch {}: no case matched {}", var_name, value.display())),
+    };
+    
+    let mut outputs = Vec::new();
+    execute_intent_block(&body, &mut outputs, env, filesystem, library, history, history_manager, engine_manager, printer)?;
+    
+    let mut output = format!("[+] switch {} = {} → {}", var_name, value.display(), label);
+    for branch_output in outputs {
+        output.push_str(&format!("\n{}", branch_output));
+    }
+    Ok(output)
+}
+
+// Error raised inside a try block, matched against the catch clauses in declaration order.
+// Intents keep returning Result<_, String>; the message is wrapped here at the try boundary
+#[derive(Debug, Clone)]
+struct CatchableError {
+    message: String,
+}
+
+impl CatchableError {
+    fn new(error: String) -> Self {
+        Self { message: error.trim_start_matches("[-] ").to_string() }
+    }
+    
+    // An empty pattern is a bare `catch { ... }` and matches anything
+    fn matches(&self, pattern: &str) -> bool {
+        pattern.is_empty() || regex::Regex::new(pattern).is_ok_and(|re| re.is_match(&self.message))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_try_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    let body = intent.parameters.get("body").cloned().unwrap_or_default();
+    let catch_count: usize = intent.parameters.get("catch_count")
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0);
+    let mut outputs = Vec::new();
+    
+    let mut result = execute_intent_block(&body, &mut outputs, env, filesystem, library, history, history_manager, engine_manager, printer)
+        .map_err(CatchableError::new);
+    
+    if let Err(error) = result.clone() {
+        let handler = (0..catch_count).find(|i| {
+            let pattern = intent.parameters.get(&format!("catch_{}_pattern", i)).map(String::as_str).unwrap_or("");
+            error.matches(pattern)
+        });
+        
+        if let Some(catch_body) = handler.and_then(|i| intent.parameters.get(&format!("catch_{}_body", i))) {
+            outputs.push(format!("[-] Caught error: {}", error.message));
+            env.set_direct("_error", Value::Str(error.message.clone()));
+            result = execute_intent_block(catch_body, &mut outputs, env, filesystem, library, history, history_manager, engine_manager, printer)
+                .map_err(CatchableError::new);
+        }
+    }
+    
+    // finally always runs, even when the error is propagated
+    if let Some(finally_body) = intent.parameters.get("finally") {
+        let finally_result = execute_intent_block(finally_body, &mut outputs, env, filesystem, library, history, history_manager, engine_manager, printer)
+            .map_err(CatchableError::new);
+        if result.is_ok() {
+            result = finally_result;
+        }
+    }
+    
+    match result {
+        Ok(()) => Ok(outputs.join("\n")),
+        Err(error) => {
+            for output in &outputs {
+                printer.emit(output);
+            }
+            Err(error.message)
+        }
+    }
+}
+
+// Always fails: the rendered message travels up as the Err of the current intent,
+// so an enclosing try block catches it and a script marks the line as failed
+fn execute_raise_intent(
+    intent: &crate::core::intent::Intent,
+    env: &Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let message = match &intent.target {
+        Some(Target::Expression(message)) => message,
+        _ => return Err("Raise requires a message".to_string()),
+    };
+    
+    match parse_interpolated_string(message, env) {
+        Ok(rendered) => Err(rendered),
+        Err(_) => Err(message.clone()),
+    }
+}
+
+// Session settings; log_file is the same switch as `log file "path"`
+fn execute_config_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let key = match &intent.target {
+        Some(Target::Expression(key)) => key.as_str(),
+        _ => return Ok(format!("[+] Settings:\n  log_file = {}", env.script_log().path().display())),
+    };
+    let value = intent.parameters.get("value").map(String::as_str).unwrap_or("");
+    
+    match key {
+        "log_file" => {
+            env.script_log_mut().set_path(value);
+            Ok(format!("[+] log_file = {}", env.script_log().path().display()))
+        }
+        other => Err(format!("Unknown config setting '{}'. Available: log_file", other)),
+    }
+}
+
+fn execute_log_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    match intent.parameters.get("action").map(String::as_str) {
+        Some("flush") => {
+            env.script_log().flush()?;
+            Ok(format!("[+] Log flushed: {}", env.script_log().path().display()))
+        }
+        Some("file") => {
+            if let Some(Target::File(path)) = &intent.target {
+                env.script_log_mut().set_path(path);
+            }
+            Ok(format!("[+] Logging to {}", env.script_log().path().display()))
+        }
+        _ => {
+            let level = intent.parameters.get("level").map(String::as_str).unwrap_or("info");
+            let message = match &intent.target {
+                Some(Target::Expression(message)) => parse_interpolated_string(message, env).unwrap_or_else(|_| message.clone()),
+                _ => return Err("Log requires a message".to_string()),
+            };
+            
+            // Bare field values naming a variable log that variable's value
+            let mut fields = std::collections::BTreeMap::new();
+            for (key, raw) in &intent.parameters {
+                if let Some(field) = key.strip_prefix("field.") {
+                    let value = match env.get_value(raw) {
+                        Some(value) => value.clone(),
+                        None => parse_simple_value(raw, None)?,
+                    };
+                    fields.insert(field.to_string(), value);
+                }
+            }
+            
+            let script = intent.context.get("script").map(String::as_str);
+            let line = intent.context.get("line").and_then(|l| l.parse().ok());
+            env.script_log().write(level, &message, script, line, &fields)?;
+            
+            Ok(format!("[+] Logged [{}] {}", level, message))
+        }
+    }
+}
+
+fn send_audited_notification(title: &str, message: &str) -> Result<(), String> {
+    crate::core::safety_guard::SafetyGuard::new()?.audit_notification(title, message);
+    crate::core::notify::send_notification(title, message)
+}
+
+fn execute_notify_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let message = match &intent.target {
+        Some(Target::Expression(message)) => parse_interpolated_string(message, env).unwrap_or_else(|_| message.clone()),
+        _ => return Err("Notify requires a message".to_string()),
+    };
+    let title = intent.parameters.get("title").map(String::as_str).unwrap_or("Morris");
+    
+    if intent.parameters.get("on_error").is_some_and(|v| v == "true") {
+        env.set_on_error_notification(title, &message);
+        return Ok(format!("[+] Will notify \"{}\" if the script fails", message));
+    }
+    
+    send_audited_notification(title, &message)?;
+    Ok(format!("[+] Notification sent: {}", message))
+}
+
+fn execute_env_var_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let name = match &intent.target {
+        Some(Target::Expression(name)) => name,
+        _ => return Err("env-var requires a variable name".to_string()),
+    };
+    let dest = intent.parameters.get("into")
+        .ok_or("env-var requires 'into <variable>'".to_string())?;
+    
+    let raw = std::env::var(name)
+        .map_err(|_| format!("Environment variable '{}' is not set", name))?;
+    let value = parse_simple_value(&raw, None)?;
+    
+    env.set_direct(dest, value.clone());
+    Ok(format!("[+] {} = {} (from ${})", dest, value.display(), name))
+}
+
+// MORRIS_* names configure morris itself, so scripts cannot export them
+fn is_reserved_env_name(name: &str) -> bool {
+    name.to_ascii_uppercase().starts_with("MORRIS_")
+}
+
+// Only child processes (exec_shell) see exported values, never the parent shell
+fn execute_export_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let name = match &intent.target {
+        Some(Target::Expression(name)) => name,
+        _ => return Err("export requires a variable name".to_string()),
+    };
+    
+    let mut skipped = Vec::new();
+    let names: Vec<String> = if name == "*" {
+        let mut names: Vec<String> = env.list().into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| !name.starts_with('_'))
+            .collect();
+        names.sort();
+        let (reserved, names): (Vec<String>, Vec<String>) = names.into_iter()
+            .partition(|name| is_reserved_env_name(name));
+        skipped = reserved;
+        names
+    } else {
+        if env.get_variable(name).is_none() {
+            return Err(format!("Variable '{}' not found", name));
+        }
+        vec![name.clone()]
+    };
+    
+    let mut exported = Vec::new();
+    for name in &names {
+        let os_name = intent.parameters.get("as").unwrap_or(name);
+        if os_name.contains('=') || os_name.contains('\0') {
+            return Err(format!("Invalid environment variable name '{}'", os_name));
+        }
+        if is_reserved_env_name(os_name) {
+            return Err(format!("Cannot export '{}': MORRIS_* names are reserved for morris settings", os_name));
+        }
+        let value = crate::core::expr::evaluate(&crate::core::expr::Expr::Variable(name.clone()), env)?;
+        let raw = value.to_string();
+        if raw.contains('\0') {
+            return Err(format!("Cannot export '{}': value contains a NUL byte", name));
+        }
+        std::env::set_var(os_name, &raw);
+        exported.push(format!("{}={}", os_name, raw));
+    }
+    
+    let mut output = if exported.is_empty() {
+        "[+] No variables to export".to_string()
+    } else {
+        format!("[+] Exported {} variable(s)", exported.len())
+    };
+    for line in exported {
+        output.push_str(&format!("\n  {}", line));
+    }
+    if !skipped.is_empty() {
+        output.push_str(&format!("\n  Skipped (reserved): {}", skipped.join(", ")));
+    }
+    Ok(output)
+}
+
+// Manual pages, one embedded text file per verb
+const MAN_PAGES: &[(&str, &str)] = &[
+    ("set", include_str!("man/set.txt")),
+    ("ensure", include_str!("man/ensure.txt")),
+    ("writeout", include_str!("man/writeout.txt")),
+    ("load", include_str!("man/load.txt")),
+    ("freeze", include_str!("man/freeze.txt")),
+    ("craft", include_str!("man/craft.txt")),
+    ("forge", include_str!("man/forge.txt")),
+    ("smelt", include_str!("man/smelt.txt")),
+    ("bookmark", include_str!("man/bookmark.txt")),
+    ("switch", include_str!("man/switch.txt")),
+    ("try", include_str!("man/try.txt")),
+    ("raise", include_str!("man/raise.txt")),
+    ("log", include_str!("man/log.txt")),
+    ("schema", include_str!("man/schema.txt")),
+];
+
+fn man_pages() -> HashMap<String, &'static str> {
+    MAN_PAGES.iter().map(|(verb, page)| (verb.to_string(), *page)).collect()
+}
+
+const MAN_PAGE_HEIGHT: usize = 24;
+
+fn execute_man_intent(
+    intent: &crate::core::intent::Intent,
+    printer: &Printer,
+) -> Result<String, String> {
+    let pages = man_pages();
+    let verb = match &intent.target {
+        Some(Target::Expression(verb)) if !verb.is_empty() => verb.as_str(),
+        _ => {
+            let mut verbs: Vec<&String> = pages.keys().collect();
+            verbs.sort();
+            return Ok(format!("[+] Manual pages: {}", verbs.iter().map(|v| v.as_str()).collect::<Vec<_>>().join(", ")));
+        }
+    };
+    
+    let page = pages.get(verb)
+        .ok_or_else(|| format!("No manual entry for '{}'. Try 'man' for the list", verb))?;
+    
+    Ok(show_paged(&format!("man {}", verb), page, printer))
+}
+
+// Page through long text only when a person is reading at a terminal;
+// otherwise the whole text is returned as the command output
+fn show_paged(title: &str, text: &str, printer: &Printer) -> String {
+    use std::io::IsTerminal;
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        return text.trim_end().to_string();
+    }
+    
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+    printer.header(title);
+    for (i, line) in lines.iter().enumerate() {
+        printer.emit(line);
+        if (i + 1) % MAN_PAGE_HEIGHT == 0 && i + 1 < lines.len() {
+            print!("-- More -- (Enter to continue, q to quit) ");
+            let _ = io::stdout().flush();
+            let mut answer = String::new();
+            if io::stdin().read_line(&mut answer).is_err() || answer.trim() == "q" {
+                break;
+            }
+        }
+    }
+    
+    String::new()
+}
+
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+// (version header, section body) for every "## " section of the changelog
+fn changelog_sections(text: &str) -> Vec<(&str, &str)> {
+    text.split("\n## ")
+        .skip(1)
+        .map(|section| section.split_once('\n').unwrap_or((section, "")))
+        .map(|(header, body)| (header.trim(), body))
+        .collect()
+}
+
+fn execute_changelog_intent(
+    intent: &crate::core::intent::Intent,
+    printer: &Printer,
+) -> Result<String, String> {
+    let version = match &intent.target {
+        Some(Target::Expression(version)) if !version.is_empty() => version.trim_start_matches('v'),
+        _ => return Ok(show_paged("changelog", CHANGELOG, printer)),
+    };
+    
+    let sections = changelog_sections(CHANGELOG);
+    let (header, body) = sections.iter()
+        .find(|(header, _)| {
+            let name = header.split_whitespace().next().unwrap_or("");
+            name.trim_start_matches('v').eq_ignore_ascii_case(version)
+        })
+        .ok_or_else(|| format!("No changelog entry for '{}'. Versions: {}", version,
+            sections.iter().map(|(h, _)| h.split_whitespace().next().unwrap_or("")).collect::<Vec<_>>().join(", ")))?;
+    
+    Ok(show_paged(&format!("changelog {}", version), &format!("## {}\n{}", header, body), printer))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_tutorial_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    use crate::core::tutorial::{tutorial_steps, TutorialProgress};
+    
+    if intent.parameters.contains_key("reset") {
+        TutorialProgress::reset()?;
+        return Ok("[+] Tutorial progress reset. Type 'tutorial' to start again".to_string());
+    }
+    
+    let steps = tutorial_steps();
+    let mut progress = TutorialProgress::load();
+    if progress.completed_steps >= steps.len() {
+        return Ok("[+] Tutorial already completed. Use 'tutorial reset' to go through it again".to_string());
+    }
+    
+    printer.header("Morris Tutorial");
+    println!("Type the intent for each step. 'skip' moves on, 'quit' saves and leaves.");
+    
+    while progress.completed_steps < steps.len() {
+        let step = &steps[progress.completed_steps];
+        printer.subheader(&format!("Step {}/{}", progress.completed_steps + 1, steps.len()));
+        println!("{}", step.instruction);
+        println!("  e.g. {}", step.example);
+        
+        loop {
+            print!("tutorial> ");
+            let _ = io::stdout().flush();
+            
+            let mut input = String::new();
+            if io::stdin().read_line(&mut input).map_err(|e| e.to_string())? == 0 {
+                progress.save()?;
+                return Ok("[+] Tutorial paused".to_string());
+            }
+            let input = input.trim();
+            
+            match input {
+                "" => continue,
+                "quit" | "exit" => {
+                    progress.save()?;
+                    return Ok(format!("[+] Tutorial paused at step {}. Type 'tutorial' to resume", progress.completed_steps + 1));
+                }
+                "skip" => break,
+                _ => {}
+            }
+            
+            match parse_to_intent(input) {
+                Ok(attempt) if attempt.verb == step.expected_verb => {
+                    match execute_intent(&attempt, env, filesystem, library, history, history_manager, engine_manager, printer) {
+                        Ok(output) => {
+                            if !output.is_empty() {
+                                println!("{}", output);
+                            }
+                            printer.success(step.explanation);
+                            break;
+                        }
+                        Err(e) => printer.error(&format!("{} - try again", e)),
+                    }
+                }
+                Ok(attempt) => printer.warning(&format!(
+                    "That is a '{}' intent; this step needs '{}'. Try: {}",
+                    format!("{:?}", attempt.verb).to_lowercase(),
+                    format!("{:?}", step.expected_verb).to_lowercase(),
+                    step.example,
+                )),
+                Err(e) => printer.error(&format!("{} - try: {}", e, step.example)),
+            }
+        }
+        
+        progress.completed_steps += 1;
+        progress.save()?;
+    }
+    
+    Ok("[+] Tutorial complete! See 'help' and 'man <verb>' for everything else".to_string())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_examples_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    use crate::core::examples::{examples_for, example_by_number, EXAMPLES};
+    
+    if let Some(n) = intent.parameters.get("run") {
+        let number: usize = n.parse()
+            .map_err(|_| format!("examples run expects an example number, got '{}'", n))?;
+        let example = example_by_number(number)
+            .ok_or_else(|| format!("No example #{} (there are {})", number, EXAMPLES.len()))?;
+        
+        printer.subheader(&format!("#{} {}", number, example.title));
+        printer.print_code(example.code);
+        
+        let mut outputs = Vec::new();
+        let result = execute_intent_block(example.code, &mut outputs, env, filesystem, library, history, history_manager, engine_manager, printer);
+        for output in outputs.iter().filter(|o| !o.is_empty()) {
+            printer.emit(output);
+        }
+        result?;
+        return Ok(format!("[+] Example #{} finished", number));
+    }
+    
+    let verb = match &intent.target {
+        Some(Target::Expression(verb)) if !verb.is_empty() => verb.as_str(),
+        _ => {
+            let mut verbs: Vec<&str> = EXAMPLES.iter().map(|example| example.verb).collect();
+            verbs.dedup();
+            return Ok(format!("[+] Examples available for: {}", verbs.join(", ")));
+        }
+    };
+    
+    let examples = examples_for(verb);
+    if examples.is_empty() {
+        return Err(format!("No examples for '{}'. Try 'examples' for the list", verb));
+    }
+    
+    printer.header(&format!("examples {}", verb));
+    for (number, example) in examples {
+        printer.subheader(&format!("#{} {}", number, example.title));
+        printer.print_code(example.code);
+        printer.emit(&format!("  {}", example.explanation));
+        printer.emit("");
+    }
+    
+    Ok("[+] Run one with 'examples run <n>'".to_string())
+}
+
+fn execute_feedback_intent(
+    intent: &crate::core::intent::Intent,
+    printer: &Printer,
+) -> Result<String, String> {
+    use crate::core::feedback::{configured_feedback_url, send_feedback, FeedbackLog};
+    
+    let log = FeedbackLog::new();
+    match intent.parameters.get("action").map(|a| a.as_str()) {
+        Some("show") => {
+            let entries = log.entries();
+            if entries.is_empty() {
+                return Ok("[+] No feedback recorded yet".to_string());
+            }
+            printer.header(&format!("Feedback ({})", log.path().display()));
+            for entry in &entries {
+                printer.emit(&format!("  {} (v{})  {}", entry.timestamp, entry.version, entry.message));
+            }
+            Ok(format!("[+] {} feedback entries", entries.len()))
+        }
+        Some("send") => {
+            let url = configured_feedback_url()
+                .ok_or("No feedback endpoint configured. Add [feedback] url = \"...\" to .morris_config.toml")?;
+            let entries = log.entries();
+            if entries.is_empty() {
+                return Ok("[+] No feedback to send".to_string());
+            }
+            // Explicit opt-in: --allow-http at startup
+            crate::core::safety_guard::SafetyGuard::new()?.validate_http("POST", &url)?;
+            send_feedback(&url, &entries)?;
+            Ok(format!("[+] Sent {} feedback entries to {}", entries.len(), url))
+        }
+        _ => {
+            let message = intent.parameters.get("message").map(|m| m.trim()).unwrap_or("");
+            if message.is_empty() {
+                return Err("Usage: feedback \"message\" | feedback show | feedback send".to_string());
+            }
+            let entry = log.append(message)?;
+            Ok(format!("[+] Feedback recorded at {} - thank you!", entry.timestamp))
+        }
+    }
+}
+
+fn execute_unset_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let var_name = match &intent.target {
+        Some(Target::Variable(name)) if !name.is_empty() => name,
+        _ => return Err("unset requires a variable name".to_string()),
+    };
+    
+    let dependents = env.get_dependents(var_name);
+    env.unset(var_name)?;
+    
+    let mut output = format!("[+] Unset '{}'", var_name);
+    for dependent in dependents {
+        if let Some(value) = env.get_value(&dependent) {
+            output.push_str(&format!("\n  → {} = {}", dependent, value.display()));
+        }
+    }
+    Ok(output)
+}
+
+fn execute_rename_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let old = match &intent.target {
+        Some(Target::Variable(name)) => name,
+        _ => return Err("rename requires a variable name".to_string()),
+    };
+    let new = intent.parameters.get("new_name")
+        .ok_or("rename requires a new name")?;
+    let force = intent.parameters.get("force").is_some_and(|f| f == "true");
+    
+    let dependents = env.get_dependents(old);
+    env.rename(old, new, force)?;
+    
+    let mut output = format!("[+] Renamed '{}' to '{}'", old, new);
+    if !dependents.is_empty() {
+        output.push_str(&format!("\n  Updated expressions: {}", dependents.join(", ")));
+    }
+    Ok(output)
+}
+
+// The copy is a plain direct variable: computed sources are snapshotted,
+// so later changes to their inputs do not reach the copy
+fn execute_copy_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let source = match &intent.target {
+        Some(Target::Variable(name)) => name,
+        _ => return Err("copy requires a source variable".to_string()),
+    };
+    let dest = intent.parameters.get("into")
+        .ok_or("copy requires a destination variable")?;
+    
+    let declared_type = env.get_variable(source)
+        .ok_or_else(|| format!("Variable '{}' not found", source))?
+        .declared_type.clone();
+    if env.get_variable(dest).is_some() {
+        return Err(format!("Variable '{}' already exists", dest));
+    }
+    
+    // Evaluated rather than read so a pending lazy source yields its real value
+    let value = crate::core::expr::evaluate(&crate::core::expr::Expr::Variable(source.clone()), env)?;
+    env.set_direct_with_type(dest, value.clone(), declared_type);
+    
+    Ok(format!("[+] Copied {} → {} = {}", source, dest, value.display()))
+}
+
+fn execute_swap_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    history_manager: &mut HistoryManager,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let a = match &intent.target {
+        Some(Target::Variable(name)) => name,
+        _ => return Err("swap requires two variables".to_string()),
+    };
+    let b = intent.parameters.get("with")
+        .ok_or("swap requires two variables")?;
+    
+    let propagated = env.swap(a, b)?;
+    
+    // The history gets one set per side, linked by the swap's id, so replaying
+    // either half on its own is visible as part of the pair
+    let mut output = format!("[+] Swapped {} ⇄ {}", a, b);
+    for name in [a, b] {
+        let value = env.get_value(name).cloned().unwrap_or(Value::Null);
+        let change = crate::core::intent::Intent::new(Verb::Set)
+            .with_target(Target::Variable(name.clone()))
+            .with_parameter("value", &value.to_string())
+            .with_context("swap_id", &intent.id.to_string());
+        history_manager.record(&change, &format!("{} = {}", name, value.display()), IntentState::Succeeded);
+        output.push_str(&format!("\n  {} = {}", name, value.display()));
+    }
+    
+    if !propagated.is_empty() {
+        output.push_str(&format!("\n  → Updated: {}", propagated.join(", ")));
+    }
+    Ok(output)
+}
+
+// Unlike `set x = ... as int`, cast converts an existing variable in place and
+// fails loudly when the conversion is impossible. The result is a direct value
+// so later propagation cannot undo the conversion
+fn execute_cast_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let var_name = match &intent.target {
+        Some(Target::Variable(name)) => name,
+        _ => return Err("cast requires a variable".to_string()),
+    };
+    let type_name = intent.parameters.get("type")
+        .ok_or("cast requires a target type")?;
+    if !matches!(type_name.as_str(), "int" | "bool" | "string") {
+        return Err(format!("Cannot cast to '{}': expected int, bool or string", type_name));
+    }
+    
+    let var = env.get_variable(var_name)
+        .ok_or_else(|| format!("Variable '{}' not found", var_name))?;
+    if var.is_constant {
+        return Err(format!("[-] Cannot cast {}: variable is frozen", var_name));
+    }
+    env.check_writable(var_name)?;
+    
+    let before = var.value.clone();
+    let after = match (&before, type_name.as_str()) {
+        (Value::Str(_), "string") => before.clone(),
+        _ => apply_type_hint(before.clone(), Some(&format!(":{}", type_name)))
+            .map_err(|e| format!("Cannot cast {}: {}", var_name, e))?,
+    };
+    
+    env.set_direct_with_type(var_name, after.clone(), parse_simple_type(type_name));
+    let propagated = crate::core::propagate::propagate_from(env, var_name).unwrap_or_default();
+    
+    let mut output = format!("[+] Cast {}: {} {} → {} {}",
+        var_name, before.type_name(), before.display(), after.type_name(), after.display());
+    if !propagated.is_empty() {
+        output.push_str(&format!("\n  → Updated: {}", propagated.join(", ")));
+    }
+    Ok(output)
+}
+
+// Evaluate the collection named by a loop verb
+fn evaluate_collection_target(intent: &crate::core::intent::Intent, env: &Env, verb: &str) -> Result<Value, String> {
+    let source = match &intent.target {
+        Some(Target::Expression(source)) | Some(Target::Variable(source)) => source,
+        _ => return Err(format!("{} requires a list", verb)),
+    };
+    let expr = crate::core::expr::parse_expression(source)?;
+    crate::core::expr::evaluate(&expr, env)
+}
+
+fn evaluate_list_target(intent: &crate::core::intent::Intent, env: &Env, verb: &str) -> Result<Vec<Value>, String> {
+    match evaluate_collection_target(intent, env, verb)? {
+        Value::List(items) => Ok(items),
+        other => Err(format!("{} requires a list, got {}", verb, other.type_name())),
+    }
+}
+
+// Loop variables are only bound while the loop runs; afterwards the name
+// goes back to whatever it held before, or disappears
+fn restore_loop_variable(env: &mut Env, name: &str, previous: Option<Value>) {
+    match previous {
+        Some(value) => env.set_direct(name, value),
+        None => env.remove_variable(name),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_each_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    let item = intent.parameters.get("item")
+        .ok_or("each requires a loop variable")?;
+    let body = intent.parameters.get("body").cloned().unwrap_or_default();
+    let items = evaluate_list_target(intent, env, "each")?;
+    
+    env.check_writable(item)?;
+    let previous = env.get_value(item).cloned();
+    let mut outputs = Vec::new();
+    let mut result = Ok(());
+    
+    for value in items {
+        env.set_direct(item, value);
+        result = execute_intent_block(&body, &mut outputs, env, filesystem, library, history, history_manager, engine_manager, printer);
+        if result.is_err() {
+            break;
+        }
+    }
+    restore_loop_variable(env, item, previous);
+    
+    match result {
+        Ok(()) if outputs.is_empty() => Ok("[+] each: no output".to_string()),
+        Ok(()) => Ok(outputs.join("\n")),
+        Err(e) => {
+            for output in &outputs {
+                printer.emit(output);
+            }
+            Err(e)
+        }
+    }
+}
+
+// Evaluate a collection verb's block once per element with `item` bound
+fn evaluate_per_item(env: &mut Env, items: &[Value], body: &str) -> Result<Vec<Value>, String> {
+    let expr = crate::core::expr::parse_expression(body)?;
+    env.check_writable("item")?;
+    let previous = env.get_value("item").cloned();
+    
+    let mut results = Vec::new();
+    let mut outcome = Ok(());
+    for value in items {
+        env.set_direct("item", value.clone());
+        match crate::core::expr::evaluate(&expr, env) {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                outcome = Err(format!("Cannot evaluate {{ {} }} for item {}: {}", body, value.display(), e));
+                break;
+            }
+        }
+    }
+    restore_loop_variable(env, "item", previous);
+    
+    outcome.map(|_| results)
+}
+
+// Collection verbs write to `into <dest>` when given, otherwise back to the
+// source variable, propagating the change like a set would
+fn store_collection_result(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    verb: &str,
+    value: Value,
+) -> Result<String, String> {
+    let dest = match (intent.parameters.get("into"), &intent.target) {
+        (Some(dest), _) => dest.clone(),
+        (None, Some(Target::Expression(source)))
+            if !source.is_empty() && source.chars().all(|c| c.is_alphanumeric() || c == '_') => source.clone(),
+        _ => return Err(format!("{} on an expression needs 'into <dest>'", verb)),
+    };
+    
+    if env.get_variable(&dest).is_some_and(|var| var.is_constant) {
+        return Err(format!("[-] Cannot change {}: variable is frozen", dest));
+    }
+    env.check_writable(&dest)?;
+    
+    env.set_direct(&dest, value.clone());
+    let propagated = crate::core::propagate::propagate_from(env, &dest).unwrap_or_default();
+    
+    let mut output = format!("[+] {} → {} = {}", verb, dest, value.display());
+    if !propagated.is_empty() {
+        output.push_str(&format!("\n  → Updated: {}", propagated.join(", ")));
+    }
+    Ok(output)
+}
+
+fn execute_filter_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let body = intent.parameters.get("body")
+        .ok_or("filter requires a condition block")?;
+    let items = evaluate_list_target(intent, env, "filter")?;
+    
+    let verdicts = evaluate_per_item(env, &items, body)?;
+    let kept: Vec<Value> = items.into_iter()
+        .zip(verdicts)
+        .filter(|(_, verdict)| crate::core::builtins::is_truthy(verdict))
+        .map(|(item, _)| item)
+        .collect();
+    
+    store_collection_result(intent, env, "filter", Value::List(kept))
+}
+
+// Dicts are transformed value by value, keeping their keys
+fn execute_transform_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let body = intent.parameters.get("body")
+        .ok_or("transform requires an expression block")?;
+    
+    let transformed = match evaluate_collection_target(intent, env, "transform")? {
+        Value::List(items) => Value::List(evaluate_per_item(env, &items, body)?),
+        Value::Dict(map) => {
+            let (keys, values): (Vec<String>, Vec<Value>) = map.into_iter().unzip();
+            let results = evaluate_per_item(env, &values, body)?;
+            Value::Dict(keys.into_iter().zip(results).collect())
+        }
+        other => return Err(format!("transform requires a list or dict, got {}", other.type_name())),
+    };
+    
+    store_collection_result(intent, env, "transform", transformed)
+}
+
+// Without starting_from the first element seeds the accumulator. The result is
+// only stored when `into` is given; the source list is never overwritten
+fn execute_reduce_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let body = intent.parameters.get("body")
+        .ok_or("reduce requires an accumulator block")?;
+    let expr = crate::core::expr::parse_expression(body)?;
+    let mut items = evaluate_list_target(intent, env, "reduce")?.into_iter();
+    
+    let initial = match intent.parameters.get("starting_from") {
+        Some(start) => crate::core::expr::evaluate(&crate::core::expr::parse_expression(start)?, env)?,
+        None => items.next().ok_or("reduce of an empty list needs 'starting_from <value>'")?,
+    };
+    
+    env.check_writable("acc")?;
+    env.check_writable("item")?;
+    let previous_acc = env.get_value("acc").cloned();
+    let previous_item = env.get_value("item").cloned();
+    
+    let mut acc = Ok(initial);
+    for value in items {
+        let current = match acc {
+            Ok(current) => current,
+            Err(_) => break,
+        };
+        env.set_direct("acc", current);
+        env.set_direct("item", value.clone());
+        acc = crate::core::expr::evaluate(&expr, env)
+            .map_err(|e| format!("Cannot evaluate {{ {} }} for item {}: {}", body, value.display(), e));
+    }
+    restore_loop_variable(env, "acc", previous_acc);
+    restore_loop_variable(env, "item", previous_item);
+    let result = acc?;
+    
+    if intent.parameters.contains_key("into") {
+        return store_collection_result(intent, env, "reduce", result);
+    }
+    Ok(format!("[+] reduce = {}", result.display()))
+}
+
+fn execute_sort_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let list = Value::List(evaluate_list_target(intent, env, "sort")?);
+    
+    let sorted = match intent.parameters.get("by") {
+        Some(field) => crate::core::builtins::sort_by(&list, &Value::Str(field.clone()))?,
+        None => crate::core::builtins::sort(&list)?,
+    };
+    let sorted = if intent.parameters.contains_key("descending") {
+        crate::core::builtins::reverse(&sorted)?
+    } else {
+        sorted
+    };
+    
+    store_collection_result(intent, env, "sort", sorted)
+}
+
+// Failed asserts halt scripts; the REPL just reports them like any other error
+const ASSERTION_FAILED: &str = "Assertion failed";
+
+fn execute_assert_intent(
+    intent: &crate::core::intent::Intent,
+    env: &Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let condition = match &intent.target {
+        Some(Target::Expression(condition)) => condition,
+        _ => return Err("assert requires a condition".to_string()),
+    };
+    
+    let expr = crate::core::expr::parse_condition_expression(condition)?;
+    let outcome = crate::core::expr::evaluate(&expr, env)?;
+    if crate::core::builtins::is_truthy(&outcome) {
+        return Ok(format!("[+] Assertion passed: {}", condition));
+    }
+    
+    let mut names = crate::core::expr::extract_variables(&expr);
+    names.sort();
+    let values: Vec<String> = names.iter()
+        .map(|name| match env.get_value(name) {
+            Some(value) => format!("{} = {}", name, value.display()),
+            None => format!("{} undefined", name),
+        })
+        .collect();
+    
+    let mut error = match intent.parameters.get("message") {
+        Some(message) => format!("{}: {} ({})", ASSERTION_FAILED, message, condition),
+        None => format!("{}: {}", ASSERTION_FAILED, condition),
+    };
+    if !values.is_empty() {
+        error.push_str(&format!("\n  where {}", values.join(", ")));
+    }
+    Err(error)
+}
+
+// Resident set size from /proc; None on platforms without it
+fn resident_memory_kb() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    status.lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|rest| rest.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_benchmark_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    let body = intent.parameters.get("body").cloned().unwrap_or_default();
+    let verbose = intent.parameters.contains_key("verbose");
+    
+    let memory_before = resident_memory_kb();
+    let started = std::time::Instant::now();
+    let mut outputs = Vec::new();
+    let result = execute_intent_block(&body, &mut outputs, env, filesystem, library, history, history_manager, engine_manager, printer);
+    let elapsed = started.elapsed();
+    let memory_after = resident_memory_kb();
+    
+    env.set_direct("__last_benchmark_ms", Value::Int(elapsed.as_millis() as i64));
+    
+    for output in &outputs {
+        printer.emit(output);
+    }
+    result?;
+    
+    let mut report = format!("[+] Benchmark: {:.3} ms", elapsed.as_secs_f64() * 1000.0);
+    if verbose {
+        match (memory_before, memory_after) {
+            (Some(before), Some(after)) => report.push_str(&format!(
+                "\n  Memory: {} kB → {} kB ({:+} kB)", before, after, after as i64 - before as i64)),
+            _ => report.push_str("\n  Memory: unavailable on this platform"),
+        }
+    }
+    Ok(report)
+}
+
+// Like $(...) in a shell: everything the block would print - writeout text, printer
+// lines and each intent's output - is collected into the variable instead of shown
+#[allow(clippy::too_many_arguments)]
+fn execute_capture_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    let dest = match &intent.target {
+        Some(Target::Variable(name)) => name.clone(),
+        _ => return Err("capture requires a variable name".to_string()),
+    };
+    let body = intent.parameters.get("body").cloned().unwrap_or_default();
+    
+    if env.get_variable(&dest).is_some_and(|var| var.is_constant) {
+        return Err(format!("[-] Cannot change {}: variable is frozen", dest));
+    }
+    env.check_writable(&dest)?;
+    
+    let outer = printer.begin_capture();
+    // Statement by statement, so intent outputs stay in order with the writeout text
+    let mut result = Ok(());
+    for statement in crate::core::intent::split_block_statements(strip_block_braces(&body)) {
+        let mut outputs = Vec::new();
+        result = execute_intent_block(&statement, &mut outputs, env, filesystem, library, history, history_manager, engine_manager, printer);
+        for output in &outputs {
+            printer.capture(output);
+        }
+        if result.is_err() {
+            break;
+        }
+    }
+    let captured = printer.end_capture(outer);
+    result?;
+    
+    let value = Value::Str(captured);
+    env.set_direct(&dest, value.clone());
+    let propagated = crate::core::propagate::propagate_from(env, &dest).unwrap_or_default();
+    
+    let mut output = format!("[+] Captured {} = {}", dest, value.display());
+    if !propagated.is_empty() {
+        output.push_str(&format!("\n  → Updated: {}", propagated.join(", ")));
+    }
+    Ok(output)
+}
+
+// Like `tee`: the block's output is shown as usual and copied, without color
+// codes, to the file. Nested redirects write to every enclosing file too.
+// Only text printed through the Printer is copied, so the interactive watch
+// and tutorial prompts (which draw on the terminal directly) are left out
+#[allow(clippy::too_many_arguments)]
+fn execute_redirect_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    let path = match &intent.target {
+        Some(Target::File(path)) => path,
+        _ => return Err("redirect requires a file".to_string()),
+    };
+    let body = intent.parameters.get("body").cloned().unwrap_or_default();
+    let append = intent.parameters.contains_key("append");
+    
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .map_err(|e| format!("Cannot open '{}' for redirect: {}", path, e))?;
+    
+    printer.begin_tee(Box::new(io::BufWriter::new(file)));
+    let mut outputs = Vec::new();
+    let result = execute_intent_block(&body, &mut outputs, env, filesystem, library, history, history_manager, engine_manager, printer);
+    for output in &outputs {
+        printer.emit(output);
+    }
+    if let Err(e) = &result {
+        printer.tee(&format!("[-] {}", e));
+    }
+    printer.end_tee()?;
+    result?;
+    
+    Ok(format!("[+] Redirected {} output(s) to {}{}", outputs.len(), path, if append { " (appended)" } else { "" }))
+}
+
+fn execute_trace_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    _printer: &Printer,
+) -> Result<String, String> {
+    match &intent.target {
+        Some(Target::Expression(mode)) if mode == "on" => env.set_trace(true),
+        Some(Target::Expression(mode)) if mode == "off" => env.set_trace(false),
+        _ => {}
+    }
+    
+    let state = if env.trace_enabled() { "on" } else { "off" };
+    Ok(format!("[+] Propagation trace is {}", state))
+}
+
+// Lines of intent output kept under the watch panel
+const WATCH_OUTPUT_LINES: usize = 10;
+
+// Live panel for `watch x y z`. Intents typed at the watch> prompt run as usual
+// and the panel is redrawn afterwards from a cleared screen, marking the variables
+// that propagation touched. The intent's result is shown under the panel.
+// `q`, Ctrl+C or end of input leaves watch mode
+#[allow(clippy::too_many_arguments)]
+fn execute_watch_intent(
+    intent: &crate::core::intent::Intent,
+    env: &mut Env,
+    filesystem: &FileSystem,
+    library: &mut Library,
+    history: &mut Vec<crate::core::intent::Intent>,
+    history_manager: &mut HistoryManager,
+    engine_manager: &mut ChangeEngineManager,
+    printer: &Printer,
+) -> Result<String, String> {
+    use std::io::IsTerminal;
+    
+    let names: Vec<String> = intent.parameters.get("variables")
+        .ok_or("watch requires at least one variable")?
+        .split(',')
+        .map(|name| name.to_string())
+        .collect();
+    // A line editor when a person is typing (so Ctrl+C leaves at once), plain stdin otherwise
+    let mut editor = match io::stdin().is_terminal() {
+        true => Some(rustyline::DefaultEditor::new()
+            .map_err(|e| format!("Failed to initialize line editor: {}", e))?),
+        false => None,
+    };
+    
+    // Redrawing in place needs a terminal; otherwise each panel is printed below the last
+    let in_place = io::stdout().is_terminal();
+    
+    env.set_watched(names.clone());
+    printer.info(&format!("Watching {} (q or Ctrl+C to exit)", names.join(", ")));
+    
+    let mut status: Vec<String> = Vec::new();
+    let mut steps = 0;
+    loop {
+        let changed = env.take_watch_changes();
+        if in_place {
+            // Clear and draw from the top, whatever the last intent left on screen
+            print!("\x1b[2J\x1b[H");
+            println!("Watching {} (q or Ctrl+C to exit)", names.join(", "));
+        }
+        for name in &names {
+            let value = match env.get_variable(name) {
+                Some(var) if var.lazy_expr.is_some() => "<lazy, not evaluated>".to_string(),
+                Some(var) => var.value.display(),
+                None => "<unset>".to_string(),
+            };
+            let marker = if changed.contains(name) { "  ← changed" } else { "" };
+            println!("  {} = {}{}", name, value, marker);
+        }
+        println!();
+        if status.len() > WATCH_OUTPUT_LINES {
+            println!("  ... {} earlier line(s)", status.len() - WATCH_OUTPUT_LINES);
+        }
+        for line in status.iter().skip(status.len().saturating_sub(WATCH_OUTPUT_LINES)) {
+            println!("  {}", line);
+        }
+        
+        let line = match editor.as_mut() {
+            Some(editor) => match editor.readline("watch> ") {
+                Ok(line) => line,
+                Err(_) => break,
+            },
+            None => {
+                print!("watch> ");
+                let _ = io::stdout().flush();
+                let mut line = String::new();
+                if !matches!(io::stdin().read_line(&mut line), Ok(n) if n > 0) {
+                    println!();
+                    break;
+                }
+                // Piped input is not echoed, so show it to keep the panel below it
+                println!("{}", line.trim_end());
+                line
+            }
+        };
+        let line = line.trim();
+        if line == "q" || line == "quit" {
+            break;
+        }
+        if line.is_empty() {
+            status.clear();
+            continue;
+        }
+        
+        steps += 1;
+        // What the intent printed is captured and shown under the panel, so a redraw
+        // never lands on stray output
+        let outer = printer.begin_capture();
+        let result = parse_to_intent(line).and_then(|watched_intent| {
+            if watched_intent.verb == Verb::Watch {
+                return Err("Already watching; press q to leave first".to_string());
+            }
+            execute_intent(&watched_intent, env, filesystem, library, history, history_manager, engine_manager, printer)
+        });
+        let captured = printer.end_capture(outer);
+        status = captured.lines().map(str::to_string).collect();
+        match result {
+            Ok(output) => status.extend(output.lines().map(str::to_string)),
+            Err(e) => status.push(format!("[-] {}", e)),
+        }
+    }
+    
+    env.set_watched(Vec::new());
+    Ok(format!("[+] Left watch mode after {} intent(s)", steps))
+}
+
+fn execute_alias_intent(
+    intent: &crate::core::intent::Intent,
+    _printer: &Printer,
+) -> Result<String, String> {
+    let mut aliases = crate::core::alias::AliasManager::new();
+    let name = match &intent.target {
+        Some(Target::Expression(name)) => name.as_str(),
+        _ => "",
+    };
+    
+    match intent.verb {
+        Verb::Alias => {
+            let expansion = intent.parameters.get("expansion")
+                .ok_or("alias requires an expansion")?;
+            aliases.define(name, expansion)?;
+            Ok(format!("[+] Alias '{}' → {}", name, expansion))
+        }
+        Verb::Unalias => {
+            aliases.remove(name)?;
+            Ok(format!("[+] Removed alias '{}'", name))
+        }
+        _ => {
+            if aliases.aliases().is_empty() {
+                return Ok(format!("[+] No aliases defined ({})", aliases.path().display()));
+            }
+            let mut output = format!("[+] Aliases ({}):", aliases.aliases().len());
+            for (name, expansion) in aliases.aliases() {
+                output.push_str(&format!("\n  {} = \"{}\"", name, expansion));
+            }
+            Ok(output)
+        }
+    }
+}
+
+fn show_morris_logo(printer: &Printer) {
+    // Clear screen for clean startup
+    print!("\x1B[2J\x1B[1;1H");
+    
+    if printer.use_color {
+        println!("\x1b[1;38;5;39m"); // Bright professional blue (same as original)
+    }
+    
+    println!(r"
+        ╔══════════════════════════════════════════╗
+        ║                                          ║
+        ║             M O R R I S                  ║
+        ║                                          ║
+        ║    Memory Organization as a Reactive     ║
+        ║    & Recursive Intent-driven System      ║
+        ║                                          ║
+        ║    ────────────────────────────────      ║
+        ║                                          ║
+        ║    We shape our tools,                   ║
+        ║    and our tools shape us...             ║
+        ║                                          ║
+        ║    ────────────────────────────────      ║
+        ║                                          ║
+        ║    v3.0 • Meta-programmability           ║
+        ║                                          ║
+        ╚══════════════════════════════════════════╝
+    ");
+    
+    if printer.use_color {
+        println!("\x1b[0m"); // Reset
+    }
+    
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Keeps forge records and other auto-saved engine state out of ~/.morris
+    fn test_engine_manager() -> ChangeEngineManager {
+        let mut engine_manager = ChangeEngineManager::new();
+        engine_manager.file_path = std::env::temp_dir().join(format!("morris_test_engine_{}.json", std::process::id()));
+        engine_manager
+    }
+
+    // Runs each line as one intent, stopping at the first error; returns the last output
+    fn run_in(env: &mut Env, lines: &[&str]) -> Result<String, String> {
+        let filesystem = FileSystem::new();
+        let mut library = Library::new();
+        let mut history = Vec::new();
+        let mut history_manager = HistoryManager::new();
+        let mut engine_manager = test_engine_manager();
+        let printer = Printer::new();
+        let mut output = String::new();
+        for line in lines {
+            let intent = parse_to_intent(line)?;
+            output = execute_intent(&intent, env, &filesystem, &mut library, &mut history, &mut history_manager, &mut engine_manager, &printer)?;
+        }
+        Ok(output)
+    }
+
+    fn run(lines: &[&str]) -> (Env, Result<String, String>) {
+        let mut env = Env::new();
+        let result = run_in(&mut env, lines);
+        (env, result)
+    }
+
+    // Runs `content` as a script file would, returning (successes, errors)
+    fn run_script(content: &str, options: &ScriptOptions) -> (Env, (usize, usize)) {
+        let mut env = Env::new();
+        let counts = process_script_content(content, "test.msh", options, &mut env, &mut Vec::new(), &mut HistoryManager::new(), &mut test_engine_manager(), &mut Library::new(), &Printer::new()).unwrap();
+        (env, counts)
+    }
+
+    #[test]
+    fn test_switch_runs_matching_branch() {
+        let script = "switch mode { case \"fast\" then set speed = 10; case 2 then set speed = 2; default set speed = 1 }";
+        let (env, result) = run(&["set mode = \"fast\"", script]);
+        assert!(result.unwrap().contains("case \"fast\""));
+        assert_eq!(env.get_value("speed"), Some(&Value::Int(10)));
+
+        let (env, _) = run(&["set mode = 2", script]);
+        assert_eq!(env.get_value("speed"), Some(&Value::Int(2)));
+
+        let (env, result) = run(&["set mode = \"slow\"", script]);
+        assert!(result.unwrap().contains("default"));
+        assert_eq!(env.get_value("speed"), Some(&Value::Int(1)));
+    }
+
+    #[test]
+    fn test_switch_without_match_or_variable() {
+        let (_, result) = run(&["set mode = 3", "switch mode { case 1 then set x = 1 }"]);
+        assert_eq!(result.unwrap(), "[-] switch mode: no case matched 3");
+        let (_, result) = run(&["switch missing { case 1 then set x = 1 }"]);
+        assert_eq!(result.unwrap_err(), "Variable 'missing' not found");
+    }
+
+    #[test]
+    fn test_try_catch_binds_error() {
+        let (env, result) = run(&["try { set a = 1; frobnicate } catch { set seen = _error } finally { set done = true }"]);
+        let output = result.unwrap();
+        assert!(output.contains("Caught error"));
+        assert_eq!(env.get_value("a"), Some(&Value::Int(1)));
+        assert_eq!(env.get_value("seen"), Some(&Value::Str("Unknown intent: 'frobnicate'".to_string())));
+        assert_eq!(env.get_value("done"), Some(&Value::Bool(true)));
+    }
+
+    #[test]
+    fn test_try_finally_runs_before_error_propagates() {
+        let (env, result) = run(&["try { frobnicate } finally { set done = true }"]);
+        assert!(result.is_err());
+        assert_eq!(env.get_value("done"), Some(&Value::Bool(true)));
+
+        let (env, result) = run(&["try { set a = 1 } catch { set seen = _error }"]);
+        assert!(result.is_ok());
+        assert_eq!(env.get_value("seen"), None);
+    }
+
+    #[test]
+    fn test_raise_renders_message_and_is_catchable() {
+        let (_, result) = run(&["set user = \"ana\"", "raise \"quota exceeded for {user}\""]);
+        assert_eq!(result.unwrap_err(), "quota exceeded for ana");
+
+        let (env, result) = run(&["try { raise \"boom\" } catch { set seen = _error }"]);
+        assert!(result.is_ok());
+        assert_eq!(env.get_value("seen"), Some(&Value::Str("boom".to_string())));
+    }
+
+    #[test]
+    fn test_catch_clauses_match_in_order() {
+        let script = "try { raise \"disk full\" } catch /not found/ { set hit = 1 } catch /disk/ { set hit = 2 } catch { set hit = 3 }";
+        let (env, result) = run(&[script]);
+        assert!(result.is_ok());
+        assert_eq!(env.get_value("hit"), Some(&Value::Int(2)));
+
+        let (env, result) = run(&["try { raise \"disk full\" } catch /not found/ { set hit = 1 } finally { set done = true }"]);
+        assert_eq!(result.unwrap_err(), "disk full");
+        assert_eq!(env.get_value("hit"), None);
+        assert_eq!(env.get_value("done"), Some(&Value::Bool(true)));
+    }
+
+    #[test]
+    fn test_config_log_file_redirects_log() {
+        let path = std::env::temp_dir().join(format!("morris_config_log_{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let set_path = format!("config log_file \"{}\"", path.display());
+        let (_, result) = run(&[&set_path, "set version = 2", "log info \"deployed {version}\" fields { attempt=version }"]);
+        result.unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(content.contains("\"message\":\"deployed 2\""));
+        assert!(content.contains("\"attempt\":2"));
+
+        let (_, result) = run(&["config colour \"red\""]);
+        assert!(result.unwrap_err().contains("Unknown config setting"));
+    }
+
+    #[test]
+    fn test_on_error_notify_is_deferred() {
+        let (env, result) = run(&["set user = \"ana\"", "on-error notify \"failed for {user}\" title \"Deploy\""]);
+        assert!(result.unwrap().contains("Will notify"));
+        assert_eq!(env.on_error_notification(), Some(&("Deploy".to_string(), "failed for ana".to_string())));
+    }
+
+    #[test]
+    fn test_eval_intents_share_env_and_name_the_failing_one() {
+        let options = ScriptOptions::default();
+        assert!(execute_eval_intents(&["set x = 2".to_string(), "set y = x * 3".to_string()], &options).is_ok());
+        let err = execute_eval_intents(&["set x = 2".to_string(), "frobnicate".to_string()], &options).unwrap_err();
+        assert_eq!(err, "frobnicate: Unknown intent: 'frobnicate'");
+    }
+
+    #[test]
+    fn test_strict_stops_at_first_error() {
+        let script = "set a = 1\nfrobnicate\nset b = 2\n";
+        let (env, counts) = run_script(script, &ScriptOptions::default());
+        assert_eq!(counts, (2, 1));
+        assert_eq!(env.get_value("b"), Some(&Value::Int(2)));
+
+        let (env, counts) = run_script(script, &ScriptOptions { strict: true, ..Default::default() });
+        assert_eq!(counts, (1, 1));
+        assert_eq!(env.get_value("b"), None);
+    }
+
+    #[test]
+    fn test_vars_are_set_before_the_script() {
+        let options = ScriptOptions { vars: vec![("env".to_string(), "prod".to_string()), ("retries".to_string(), "3".to_string())], ..Default::default() };
+        let mut env = Env::new();
+        options.apply_vars(&mut env).unwrap();
+        assert_eq!(env.get_value("retries"), Some(&Value::Int(3)));
+        assert!(env.get_value("env").is_some());
+    }
+
+    #[test]
+    fn test_env_var_reads_process_environment() {
+        std::env::set_var("MORRIS_TEST_REGION", "42");
+        let (env, result) = run(&["env-var MORRIS_TEST_REGION into region"]);
+        assert!(result.is_ok());
+        assert_eq!(env.get_value("region"), Some(&Value::Int(42)));
+
+        let (_, result) = run(&["env-var MORRIS_TEST_UNSET_VARIABLE into x"]);
+        assert_eq!(result.unwrap_err(), "Environment variable 'MORRIS_TEST_UNSET_VARIABLE' is not set");
+    }
+
+    #[test]
+    fn test_dry_run_parses_without_executing() {
+        let path = std::env::temp_dir().join(format!("morris_dry_run_{}.msh", std::process::id()));
+        std::fs::write(&path, "set a = 1\nfrobnicate\nraise \"never runs\"\n").unwrap();
+        let result = dry_run_msh_file(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result, Ok((3, 1)));
+    }
+
+    #[test]
+    fn test_profile_sorts_slowest_first() {
+        let ms = std::time::Duration::from_millis;
+        let mut timings = vec![
+            (1, "Set".to_string(), "a".to_string(), ms(2)),
+            (2, "Set".to_string(), "b".to_string(), ms(9)),
+            (3, "Set".to_string(), "c".to_string(), ms(5)),
+        ];
+        show_profile(&mut timings, &Printer::new());
+        let lines: Vec<usize> = timings.iter().map(|t| t.0).collect();
+        assert_eq!(lines, [2, 3, 1]);
+
+        let (env, counts) = run_script("set a = 1\nset b = a + 1\n", &ScriptOptions { profile: true, ..Default::default() });
+        assert_eq!(counts, (2, 0));
+        assert_eq!(env.get_value("b"), Some(&Value::Int(2)));
+    }
+
+    #[test]
+    fn test_init_project_renders_template() {
+        let root = std::env::temp_dir().join(format!("morris_init_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let created = init_project(root.to_str().unwrap()).unwrap();
+        assert_eq!(created.len(), 3 + PROJECT_TEMPLATE.len());
+
+        let config = fs::read_to_string(root.join(".morris_config.toml")).unwrap();
+        let table = config.parse::<toml::Table>().unwrap();
+        let name = root.file_name().unwrap().to_string_lossy().to_string();
+        assert_eq!(table["project"]["name"].as_str(), Some(name.as_str()));
+        assert_eq!(table["script"]["strict"].as_bool(), Some(false));
+        assert!(root.join("lib/helpers.msh").exists());
+
+        assert!(init_project(root.to_str().unwrap()).unwrap_err().contains("already exists"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_man_pages_cover_their_verb() {
+        for (verb, page) in MAN_PAGES {
+            assert!(page.starts_with(&verb.to_uppercase()), "man {} has the wrong title", verb);
+            assert!(page.contains(&format!("NAME\n    {} - ", verb)), "man {} names another verb", verb);
+            assert!(page.contains("SYNTAX") && page.contains("EXAMPLES"), "man {} is incomplete", verb);
+        }
+
+        let (_, result) = run(&["man"]);
+        assert!(result.unwrap().starts_with("[+] Manual pages: bookmark, craft"));
+        let (_, result) = run(&["man frobnicate"]);
+        assert!(result.unwrap_err().starts_with("No manual entry for 'frobnicate'"));
+    }
+
+    #[test]
+    fn test_examples_run_by_number() {
+        let (env, result) = run(&["examples run 1"]);
+        assert_eq!(result.unwrap(), "[+] Example #1 finished");
+        assert_eq!(env.get_value("total"), Some(&Value::Int(60)));
+
+        let (_, result) = run(&["examples run 999"]);
+        assert!(result.unwrap_err().starts_with("No example #999"));
+        let (_, result) = run(&["examples frobnicate"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_changelog_sections_and_lookup() {
+        let sections = changelog_sections("# Changelog\n\n## Unreleased\n- a\n\n## v2.0 - Reactive\n- b\n");
+        assert_eq!(sections, [("Unreleased", "- a\n"), ("v2.0 - Reactive", "- b\n")]);
+
+        assert_eq!(changelog_sections(CHANGELOG)[0].0, "Unreleased");
+        let (_, result) = run(&["changelog v9.9"]);
+        assert!(result.unwrap_err().contains("Versions: Unreleased, v3.0"));
+    }
+
+    #[test]
+    fn test_polish_drops_redundant_changes() {
+        let (_, result) = run(&["set a = 1", "set b = 1", "craft \"tidy\"", "set a = 5", "set a = 1", "set b = 2", "set b = 3", "polish"]);
+        let output = result.unwrap();
+        assert!(output.contains("Removed a: set back to its original value"), "{}", output);
+        assert!(output.contains("Eliminated 3 change(s), 1 remaining"), "{}", output);
+        assert!(output.contains("  b = 3"), "{}", output);
+    }
+
+    #[test]
+    fn test_alloy_merges_recorded_transactions() {
+        let (env, result) = run(&[
+            "craft \"one\"", "set a = 1", "forge",
+            "craft \"two\"", "set a = 2", "set b = 3", "forge",
+            "alloy one two",
+        ]);
+        let output = result.unwrap();
+        assert!(output.contains("2 change(s)"), "{}", output);
+        assert!(output.contains("a: 1 replaced by 2"), "{}", output);
+        assert!(env.has_active_transaction());
+
+        let (_, result) = run(&["alloy one"]);
+        assert!(result.is_err());
+        let (_, result) = run(&["alloy missing other"]);
+        assert!(result.unwrap_err().contains("no transaction 'missing'"));
+    }
+
+    #[test]
+    fn test_engrave_tags_the_active_transaction() {
+        let (mut env, result) = run(&["craft \"deploy\"", "set replicas = 3", "engrave ticket \"PROJ-123\""]);
+        assert_eq!(result.unwrap(), "[🛠] Engraved ticket = \"PROJ-123\" on the active transaction");
+        let result = run_in(&mut env, &["inspect"]);
+        assert!(result.unwrap().contains("ticket = \"PROJ-123\""));
+        assert!(env.has_active_transaction());
+
+        let (_, result) = run(&["engrave ticket \"PROJ-123\""]);
+        assert!(result.unwrap_err().starts_with("Engraving error"));
+        assert!(parse_to_intent("engrave ticket").is_err());
+    }
+
+    #[test]
+    fn test_gild_marks_variables_through_a_transaction() {
+        let (env, result) = run(&["set rate = 1", "set owner = \"ops\"", "gild rate, owner"]);
+        assert_eq!(result.unwrap(), "[★] Gilded rate, owner");
+        assert!(env.is_gilded("rate") && env.is_gilded("owner"));
+
+        let (env, result) = run(&["craft \"bump\"", "set budget = 10", "gild budget", "forge"]);
+        assert!(result.is_ok());
+        assert!(env.is_gilded("budget"));
+
+        let (_, result) = run(&["gild missing"]);
+        assert_eq!(result.unwrap_err(), "Cannot gild 'missing': variable not found");
+    }
+
+    #[test]
+    fn test_patina_lists_forges_newest_first() {
+        let (_, result) = run(&[
+            "craft \"first\"", "set rate = 1", "forge",
+            "craft \"second\"", "set rate = 2", "engrave ticket \"T-2\"", "forge",
+            "patina rate",
+        ]);
+        let output = result.unwrap();
+        assert!(output.starts_with("[+] Patina of rate (2 forges):"), "{}", output);
+        let second = output.find("rate = 2 (second) ticket=T-2").unwrap();
+        let first = output.find("rate = 1 (first)").unwrap();
+        assert!(second < first);
+        assert!(output.ends_with("Current: 2"));
+
+        let (_, result) = run(&["set other = 1", "patina other"]);
+        assert_eq!(result.unwrap(), "[+] No forged changes recorded for 'other'");
+    }
+
+    #[test]
+    fn test_evolve_keeps_earlier_definitions() {
+        let (env, result) = run(&[
+            "define intent scale with (x) { x * 2 }",
+            "evolve \"scale\" { x * 3 }",
+            "examine intent \"scale\"",
+        ]);
+        let output = result.unwrap();
+        assert!(output.contains("Definition: (x) { x * 3 }"), "{}", output);
+        assert!(output.contains("1. (x) { x * 2 } @ "), "{}", output);
+        assert_eq!(env.defined_intents()["scale"].revisions.len(), 1);
+
+        let (env, result) = run(&["define intent scale with (x) { x * 2 }", "evolve \"scale\" { x * }"]);
+        assert!(result.unwrap_err().contains("definition unchanged"));
+        assert!(env.defined_intents()["scale"].revisions.is_empty());
+
+        let (_, result) = run(&["evolve \"missing\" { 1 }"]);
+        assert_eq!(result.unwrap_err(), "No defined intent 'missing'");
+    }
+
+    #[test]
+    fn test_grow_appends_composition_steps() {
+        let (env, result) = run(&[
+            "define intent \"welcome\" composed_of [\"greet\"]",
+            "grow \"welcome\" with [\"greet_user\", \"greet\"]",
+        ]);
+        assert_eq!(result.unwrap(), "[+] Grew '\"welcome\"' with greet_user: now 2 step(s)");
+        assert_eq!(env.defined_intents()["\"welcome\""].composition_steps, ["greet", "greet_user"]);
+
+        let (_, result) = run(&["define intent scale with (x) { x * 2 }", "grow scale with [\"greet\"]"]);
+        assert!(result.unwrap_err().contains("is not a composition"));
+        assert!(parse_to_intent("grow \"welcome\" with []").is_err());
+    }
+
+    #[test]
+    fn test_set_comparisons_and_ensure_null() {
+        let (env, result) = run(&["set a = 1", "set b = 1", "set same = a == b", "set s = \"a == b\"", "set x = 5", "ensure x = null"]);
+        result.unwrap();
+        assert_eq!(env.get_value("same"), Some(&Value::Bool(true)));
+        assert_eq!(env.get_value("s"), Some(&Value::Str("a == b".to_string())));
+        assert_eq!(env.get_value("x"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_readonly_variables_cannot_change() {
+        let mut env = Env::new();
+        run_in(&mut env, &["set path = \"/etc\" as readonly"]).unwrap();
+        assert_eq!(run_in(&mut env, &["set path = \"/tmp\""]).unwrap_err(), "Cannot modify readonly variable 'path'");
+        assert_eq!(env.get_value("path"), Some(&Value::Str("/etc".to_string())));
+    }
+
+    #[test]
+    fn test_final_variables_take_one_value() {
+        let mut env = Env::new();
+        run_in(&mut env, &["set port = null as final", "set port = 8080"]).unwrap();
+        assert_eq!(run_in(&mut env, &["set port = 9090"]).unwrap_err(), "Cannot reassign final variable 'port'");
+        assert_eq!(env.get_value("port"), Some(&Value::Int(8080)));
+    }
+
+    #[test]
+    fn test_lazy_variables_cache_and_rearm() {
+        let mut env = Env::new();
+        let output = run_in(&mut env, &["set base = 2", "set big = base * 10 as lazy"]).unwrap();
+        assert!(output.contains("<lazy>"));
+        assert_eq!(env.get_value("big"), Some(&Value::Null));
+        run_in(&mut env, &["set copy = big + 1"]).unwrap();
+        assert_eq!(env.get_value("copy"), Some(&Value::Int(21)));
+        assert_eq!(env.get_value("big"), Some(&Value::Int(20)));
+        // A change upstream re-arms it; `copy` reads it again while propagating
+        run_in(&mut env, &["set base = 5"]).unwrap();
+        assert_eq!(env.get_value("copy"), Some(&Value::Int(51)));
+        assert_eq!(env.get_value("big"), Some(&Value::Int(50)));
+    }
+
+    #[test]
+    fn test_unset_reevaluates_dependents() {
+        let mut env = Env::new();
+        let output = run_in(&mut env, &["set a = 2", "set b = a * 2", "unset a"]).unwrap();
+        assert!(output.starts_with("[+] Unset 'a'"));
+        assert_eq!(env.get_value("a"), None);
+        assert_eq!(env.get_value("b"), Some(&Value::Null));
+        assert_eq!(run_in(&mut env, &["unset a"]).unwrap_err(), "Variable 'a' not found");
+    }
+
+    #[test]
+    fn test_rename_keeps_dependents_working() {
+        let mut env = Env::new();
+        let output = run_in(&mut env, &["set price = 2", "set total = price * 3", "rename price as cost"]).unwrap();
+        assert!(output.contains("Updated expressions: total"));
+        run_in(&mut env, &["set cost = 5"]).unwrap();
+        assert_eq!(env.get_value("total"), Some(&Value::Int(15)));
+        assert_eq!(env.get_variable("total").and_then(|var| var.expression.clone()), Some("(cost * 3)".to_string()));
+    }
+
+    #[test]
+    fn test_copy_snapshots_computed_values() {
+        let mut env = Env::new();
+        run_in(&mut env, &["set n = 2", "set total = n * 10", "copy total as saved", "set n = 3"]).unwrap();
+        assert_eq!(env.get_value("total"), Some(&Value::Int(30)));
+        assert_eq!(env.get_value("saved"), Some(&Value::Int(20)));
+        assert_eq!(run_in(&mut env, &["copy n as saved"]).unwrap_err(), "Variable 'saved' already exists");
+        assert_eq!(run_in(&mut env, &["copy nope as other"]).unwrap_err(), "Variable 'nope' not found");
+    }
+
+    #[test]
+    fn test_swap_propagates_once() {
+        let mut env = Env::new();
+        let output = run_in(&mut env, &["set a = 1", "set b = 10", "set diff = b - a", "swap a b"]).unwrap();
+        assert!(output.contains("Swapped a ⇄ b"));
+        assert!(output.contains("→ Updated: diff"));
+        assert_eq!(env.get_value("a"), Some(&Value::Int(10)));
+        assert_eq!(env.get_value("diff"), Some(&Value::Int(-9)));
+    }
+
+    #[test]
+    fn test_cast_converts_in_place() {
+        let mut env = Env::new();
+        let output = run_in(&mut env, &["set port = \"8080\"", "cast port as int"]).unwrap();
+        assert!(output.starts_with("[+] Cast port: string \"8080\" → int 8080"), "{}", output);
+        assert_eq!(env.get_value("port"), Some(&Value::Int(8080)));
+
+        run_in(&mut env, &["set word = \"abc\""]).unwrap();
+        assert!(run_in(&mut env, &["cast word as int"]).unwrap_err().starts_with("Cannot cast word:"));
+        assert_eq!(run_in(&mut env, &["cast port as float"]).unwrap_err(), "Cannot cast to 'float': expected int, bool or string");
+    }
+
+    #[test]
+    fn test_each_runs_block_per_item() {
+        let mut env = Env::new();
+        run_in(&mut env, &["set total = 0", "set n = \"kept\"", "each n in [1, 2, 3] { set total = total + n }"]).unwrap();
+        assert_eq!(env.get_value("total"), Some(&Value::Int(6)));
+        // The loop variable goes back to its earlier value
+        assert_eq!(env.get_value("n"), Some(&Value::Str("kept".to_string())));
+        assert_eq!(run_in(&mut env, &["each n in total { set x = n }"]).unwrap_err(), "each requires a list, got int");
+    }
+
+    #[test]
+    fn test_filter_keeps_matching_items() {
+        let mut env = Env::new();
+        run_in(&mut env, &["set scores = [3, 8, 6, 1]", "filter scores where { item > 5 } into high"]).unwrap();
+        assert_eq!(env.get_value("high"), Some(&Value::List(vec![Value::Int(8), Value::Int(6)])));
+        // Without `into` the source variable is replaced
+        run_in(&mut env, &["filter scores where { item < 5 }"]).unwrap();
+        assert_eq!(env.get_value("scores"), Some(&Value::List(vec![Value::Int(3), Value::Int(1)])));
+        assert_eq!(run_in(&mut env, &["filter [1, 2] where { item > 1 }"]).unwrap_err(), "filter on an expression needs 'into <dest>'");
+    }
+
+    #[test]
+    fn test_transform_lists_and_dicts() {
+        let mut env = Env::new();
+        run_in(&mut env, &["set prices = [1, 2]", "map prices with { item * 10 } into big"]).unwrap();
+        assert_eq!(env.get_value("big"), Some(&Value::List(vec![Value::Int(10), Value::Int(20)])));
+
+        run_in(&mut env, &["set stock = {\"a\": 1, \"b\": 2}", "transform stock with { item + 1 }"]).unwrap();
+        let expected: Value = Value::Dict([("a".to_string(), Value::Int(2)), ("b".to_string(), Value::Int(3))].into_iter().collect());
+        assert_eq!(env.get_value("stock"), Some(&expected));
+        assert_eq!(run_in(&mut env, &["set n = 1", "transform n with { item }"]).unwrap_err(), "transform requires a list or dict, got int");
+    }
+
+    #[test]
+    fn test_reduce_folds_a_list() {
+        let mut env = Env::new();
+        let output = run_in(&mut env, &["set nums = [1, 2, 3]", "reduce nums with { acc * item }"]).unwrap();
+        assert_eq!(output, "[+] reduce = 6");
+        run_in(&mut env, &["reduce nums with { acc + item } starting_from 10 into total"]).unwrap();
+        assert_eq!(env.get_value("total"), Some(&Value::Int(16)));
+        assert_eq!(env.get_value("nums"), Some(&Value::List(vec![Value::Int(1), Value::Int(2), Value::Int(3)])));
+        assert_eq!(run_in(&mut env, &["reduce [] with { acc + item }"]).unwrap_err(), "reduce of an empty list needs 'starting_from <value>'");
+    }
+
+    #[test]
+    fn test_sort_in_place() {
+        let mut env = Env::new();
+        run_in(&mut env, &["set nums = [3, 1, 2]", "sort nums descending"]).unwrap();
+        assert_eq!(env.get_value("nums"), Some(&Value::List(vec![Value::Int(3), Value::Int(2), Value::Int(1)])));
+
+        run_in(&mut env, &["set people = [{\"age\": 40}, {\"age\": 20}]", "sort people by \"age\"", "set youngest = first(people)"]).unwrap();
+        let youngest: Value = Value::Dict([("age".to_string(), Value::Int(20))].into_iter().collect());
+        assert_eq!(env.get_value("youngest"), Some(&youngest));
+    }
+
+    #[test]
+    fn test_assert_reports_values_and_halts_scripts() {
+        let mut env = Env::new();
+        assert_eq!(run_in(&mut env, &["set x = 2", "assert x > 0"]).unwrap(), "[+] Assertion passed: x > 0");
+        assert_eq!(
+            run_in(&mut env, &["assert x > 5 message \"too small\""]).unwrap_err(),
+            "Assertion failed: too small (x > 5)\n  where x = 2"
+        );
+
+        // A failed assert stops the script even when not in strict mode
+        let (env, (_, errors)) = run_script("set a = 1\nassert a == 2\nset b = 3", &ScriptOptions::default());
+        assert_eq!(errors, 1);
+        assert_eq!(env.get_value("b"), None);
+    }
+
+    #[test]
+    fn test_benchmark_times_the_block() {
+        let mut env = Env::new();
+        let output = run_in(&mut env, &["benchmark { set a = 1; set b = a + 1 }"]).unwrap();
+        assert!(output.starts_with("[+] Benchmark: "), "{}", output);
+        assert_eq!(env.get_value("b"), Some(&Value::Int(2)));
+        assert!(matches!(env.get_value("__last_benchmark_ms"), Some(Value::Int(ms)) if *ms >= 0));
+    }
+
+    #[test]
+    fn test_trace_toggles_propagation_output() {
+        let mut env = Env::new();
+        assert_eq!(run_in(&mut env, &["trace on"]).unwrap(), "[+] Propagation trace is on");
+        assert!(env.trace_enabled());
+        run_in(&mut env, &["set a = 1", "set b = a + 1", "set a = 2"]).unwrap();
+        assert_eq!(env.get_value("b"), Some(&Value::Int(3)));
+        assert_eq!(run_in(&mut env, &["trace"]).unwrap(), "[+] Propagation trace is on");
+        assert_eq!(run_in(&mut env, &["trace off"]).unwrap(), "[+] Propagation trace is off");
+        assert!(!env.trace_enabled());
+    }
+
+    #[test]
+    fn test_export_sets_child_environment() {
+        let mut env = Env::new();
+        let output = run_in(&mut env, &["set morris_test_export = 42", "export morris_test_export as MORRIS_TEST_CHILD_VAR"]);
+        assert_eq!(output.unwrap_err(), "Cannot export 'MORRIS_TEST_CHILD_VAR': MORRIS_* names are reserved for morris settings");
+
+        run_in(&mut env, &["export morris_test_export as EXPORT_TEST_CHILD_VAR"]).unwrap();
+        assert_eq!(std::env::var("EXPORT_TEST_CHILD_VAR").unwrap(), "42");
+        assert_eq!(run_in(&mut env, &["export missing_var"]).unwrap_err(), "Variable 'missing_var' not found");
+    }
+
+    #[test]
+    fn test_export_star_skips_reserved_names() {
+        let mut env = Env::new();
+        let output = run_in(&mut env, &["set MORRIS_SANDBOX = 0", "set export_star_test = \"x\"", "export *"]).unwrap();
+        assert!(output.contains("export_star_test=x"), "{}", output);
+        assert!(output.ends_with("Skipped (reserved): MORRIS_SANDBOX"), "{}", output);
+        assert_ne!(std::env::var("MORRIS_SANDBOX").as_deref(), Ok("0"));
+        assert_eq!(run_in(&mut env, &["export MORRIS_SANDBOX"]).unwrap_err(), "Cannot export 'MORRIS_SANDBOX': MORRIS_* names are reserved for morris settings");
+    }
+
+    #[test]
+    fn test_snapshot_restore_merges_or_replaces() {
+        let path = std::env::temp_dir().join(format!("morris_test_restore_{}.msnap", std::process::id()));
+        let path = path.to_string_lossy();
+        let mut env = Env::new();
+        run_in(&mut env, &["set a = 1", &format!("snapshot \"{}\"", path), "set a = 2", "set b = 3"]).unwrap();
+
+        let output = run_in(&mut env, &[&format!("restore \"{}\"", path)]).unwrap();
+        assert!(output.contains("Restored 1 variables") && output.contains("merged"), "{}", output);
+        assert_eq!(env.get_value("a"), Some(&Value::Int(1)));
+        assert_eq!(env.get_value("b"), Some(&Value::Int(3)));
+
+        run_in(&mut env, &[&format!("restore \"{}\" --replace", path)]).unwrap();
+        assert_eq!(env.get_value("b"), None);
+        std::fs::remove_file(path.as_ref()).ok();
+    }
+
+    #[test]
+    fn test_diff_env_compares_snapshot_files() {
+        let dir = std::env::temp_dir();
+        let before = dir.join(format!("morris_test_diff_a_{}.msnap", std::process::id()));
+        let after = dir.join(format!("morris_test_diff_b_{}.msnap", std::process::id()));
+        let (before, after) = (before.to_string_lossy(), after.to_string_lossy());
+        let mut env = Env::new();
+        let output = run_in(&mut env, &[
+            "set a = 1",
+            &format!("snapshot \"{}\"", before),
+            "set a = 2",
+            &format!("snapshot \"{}\"", after),
+            &format!("diff-env \"{}\" \"{}\"", before, after),
+        ]).unwrap();
+        assert!(output.contains("~ a: 1 → 2"), "{}", output);
+
+        let same = run_in(&mut env, &[&format!("diff-env \"{}\" \"{}\"", before, before)]).unwrap();
+        assert!(same.starts_with("[+] No differences"));
+        std::fs::remove_file(before.as_ref()).ok();
+        std::fs::remove_file(after.as_ref()).ok();
+    }
+
+    #[test]
+    fn test_watch_changes_follow_propagation() {
+        let mut env = Env::new();
+        run_in(&mut env, &["set a = 1", "set b = a + 1", "set c = 5"]).unwrap();
+        env.set_watched(vec!["b".to_string(), "c".to_string()]);
+        run_in(&mut env, &["set a = 2"]).unwrap();
+        let changed = env.take_watch_changes();
+        assert!(changed.contains("b"));
+        assert!(!changed.contains("c"));
+        assert!(env.take_watch_changes().is_empty());
+
+        env.set_watched(Vec::new());
+        run_in(&mut env, &["set a = 3"]).unwrap();
+        assert!(env.take_watch_changes().is_empty());
+    }
+
+    #[test]
+    fn test_capture_stores_block_output() {
+        let (env, result) = run(&[
+            "set name = \"Ada\"",
+            "capture out { writeout(\"hello {name}\"); writeout(\"bye\") }",
+        ]);
+        result.unwrap();
+        assert_eq!(env.get_value("out"), Some(&Value::Str("hello Ada\nbye".to_string())));
+
+        let (env, result) = run(&["capture outer { writeout(\"a\"); capture inner { writeout(\"b\") } }"]);
+        result.unwrap();
+        assert_eq!(env.get_value("inner"), Some(&Value::Str("b".to_string())));
+        assert!(matches!(env.get_value("outer"), Some(Value::Str(text)) if text.starts_with("a\n")));
+    }
+
+    #[test]
+    fn test_capture_refuses_frozen_variable() {
+        let mut env = Env::new();
+        run_in(&mut env, &["set out = 1", "freeze out"]).unwrap();
+        assert!(run_in(&mut env, &["capture out { writeout(\"x\") }"]).is_err());
+        assert_eq!(env.get_value("out"), Some(&Value::Int(1)));
+    }
+
+    #[test]
+    fn test_redirect_writes_and_appends_block_output() {
+        let path = std::env::temp_dir().join(format!("morris_redirect_{}.log", std::process::id()));
+        let path_str = path.to_string_lossy().replace('\\', "/");
+        let mut env = Env::new();
+        run_in(&mut env, &[&format!("redirect \"{}\" {{ writeout(\"one\") }}", path_str)]).unwrap();
+        run_in(&mut env, &[&format!("redirect \"{}\" append {{ writeout(\"two\") }}", path_str)]).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert!(written.contains("one"));
+        assert!(written.contains("two"));
+        assert!(!written.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_capture_includes_intent_output() {
+        let (env, result) = run(&["capture out { set x = 5 }"]);
+        result.unwrap();
+        assert!(matches!(env.get_value("out"), Some(Value::Str(text)) if text.contains("x")));
+    }
+
+    #[test]
+    fn test_parse_yaml_and_to_yaml() {
+        let mut env = Env::new();
+        run_in(&mut env, &["parse-yaml \"name: web\\nports: [80, 443]\" into config"]).unwrap();
+        let config = env.get_value("config").cloned().unwrap();
+        assert!(matches!(&config, Value::Dict(map) if map.get("name") == Some(&Value::Str("web".to_string()))));
+
+        env.set_direct("raw", Value::Str("a: 1\n---\na: 2".to_string()));
+        run_in(&mut env, &["parse-yaml raw into docs"]).unwrap();
+        assert!(matches!(env.get_value("docs"), Some(Value::List(items)) if items.len() == 2));
+
+        let output = run_in(&mut env, &["to-yaml config"]).unwrap();
+        assert!(output.contains("name: web"), "{}", output);
+        assert!(run_in(&mut env, &["parse-yaml \"key: [unclosed\" into bad"]).unwrap_err().starts_with("Invalid YAML:"));
+        assert!(run_in(&mut env, &["set n = 1", "parse-yaml n"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_and_to_csv() {
+        let mut env = Env::new();
+        run_in(&mut env, &["parse-csv \"id,name\\n1,web\\n2,db\" into rows"]).unwrap();
+        match env.get_value("rows") {
+            Some(Value::List(rows)) => {
+                assert_eq!(rows.len(), 2);
+                assert!(matches!(&rows[1], Value::Dict(map) if map.get("name") == Some(&Value::Str("db".to_string()))));
+            }
+            other => panic!("rows is {:?}", other),
+        }
+        assert_eq!(run_in(&mut env, &["to-csv rows"]).unwrap(), "[+] CSV:\nid,name\n1,web\n2,db");
+
+        run_in(&mut env, &["parse-csv \"1,2\" into plain headers false"]).unwrap();
+        assert!(matches!(env.get_value("plain"), Some(Value::List(rows)) if matches!(&rows[0], Value::List(cells) if cells.len() == 2)));
+        assert!(run_in(&mut env, &["set n = 1", "to-csv n"]).is_err());
+    }
+}
@@ -1,11 +1,34 @@
 //use std::io;
+use std::cell::RefCell;
+use std::io::Write;
+use std::sync::OnceLock;
+
+// Set once from --color / --no-color before any Printer is created
+static COLOR_OVERRIDE: OnceLock<bool> = OnceLock::new();
+
+pub fn set_color_override(use_color: bool) {
+    let _ = COLOR_OVERRIDE.set(use_color);
+}
 
 pub struct Printer {
     pub use_color: bool,
+    // Set while a `capture` block runs; printed lines land here instead of the terminal
+    capture_buffer: RefCell<Option<String>>,
+    // `redirect` files; every printed line is also written to each, without color codes
+    tee_sinks: RefCell<Vec<Box<dyn Write>>>,
 }
 
 impl Printer {
     pub fn new() -> Self {
+        if let Some(&use_color) = COLOR_OVERRIDE.get() {
+            return Self::new_with_color(use_color);
+        }
+        
+        // https://no-color.org/ - any non-empty value disables color
+        if std::env::var("NO_COLOR").is_ok_and(|value| !value.is_empty()) {
+            return Self::new_with_color(false);
+        }
+        
         // Simple color detection
         #[cfg(windows)]
         let use_color = false; // Windows terminal color support is complex
@@ -15,10 +38,75 @@ impl Printer {
             .map(|term| term != "dumb")
             .unwrap_or(false);
         
-        Self { use_color }
+        Self::new_with_color(use_color)
         
     }
     
+    pub fn new_with_color(use_color: bool) -> Self {
+        Self { use_color, capture_buffer: RefCell::new(None), tee_sinks: RefCell::new(Vec::new()) }
+    }
+    
+    pub fn begin_tee(&self, sink: Box<dyn Write>) {
+        self.tee_sinks.borrow_mut().push(sink);
+    }
+    
+    // Stop the innermost redirect, flushing what was written to it
+    pub fn end_tee(&self) -> Result<(), String> {
+        match self.tee_sinks.borrow_mut().pop() {
+            Some(mut sink) => sink.flush().map_err(|e| format!("Failed to flush redirect output: {}", e)),
+            None => Ok(()),
+        }
+    }
+    
+    // Copy text to the redirect files only; the caller has already shown it
+    pub fn tee(&self, text: &str) {
+        let mut sinks = self.tee_sinks.borrow_mut();
+        if sinks.is_empty() {
+            return;
+        }
+        let plain = strip_ansi(text);
+        for sink in sinks.iter_mut() {
+            let _ = writeln!(sink, "{}", plain);
+        }
+    }
+    
+    // Every Printer method prints through here so `capture` and `redirect` see it
+    pub fn emit(&self, line: &str) {
+        if self.capture_buffer.borrow().is_some() {
+            // Spacer lines are cosmetic and would only pad the captured value
+            let plain = strip_ansi(line);
+            if !plain.trim().is_empty() {
+                self.capture(plain.trim_start_matches('\n'));
+            }
+            return;
+        }
+        println!("{}", line);
+        self.tee(line);
+    }
+    
+    // Start capturing; returns the enclosing capture's buffer so nested captures can restore it
+    pub fn begin_capture(&self) -> Option<String> {
+        self.capture_buffer.replace(Some(String::new()))
+    }
+    
+    pub fn end_capture(&self, outer: Option<String>) -> String {
+        self.capture_buffer.replace(outer).unwrap_or_default()
+    }
+    
+    // Append a line to the active capture; false when nothing is capturing
+    pub fn capture(&self, text: &str) -> bool {
+        match self.capture_buffer.borrow_mut().as_mut() {
+            Some(buffer) => {
+                if !buffer.is_empty() {
+                    buffer.push('\n');
+                }
+                buffer.push_str(text);
+                true
+            }
+            None => false,
+        }
+    }
+    
     pub fn success(&self, message: &str) {
         self.print_prefix("[+]", "green", message);
     }
@@ -34,6 +122,14 @@ impl Printer {
     pub fn info(&self, message: &str) {
         self.print_prefix("[?]", "cyan", message);
     }
+    // Gray, unprefixed: for diagnostic chatter such as propagation traces
+    pub fn dim(&self, message: &str) {
+        if self.use_color {
+            self.emit(&format!("\x1b[90m{}\x1b[0m", message));
+        } else {
+            self.emit(message);
+        }
+    }
     #[allow(dead_code)]
     pub fn neutral(&self, message: &str) {
         self.print_prefix("[•]", "blue", message);
@@ -41,25 +137,25 @@ impl Printer {
     
     pub fn header(&self, title: &str) {
         if self.use_color {
-            println!("\n\x1b[1;36m{}\x1b[0m", title);  // Bold cyan
-            println!("\x1b[90m{}\x1b[0m", "─".repeat(title.len()));  // Dark gray line
+            self.emit(&format!("\n\x1b[1;36m{}\x1b[0m", title));  // Bold cyan
+            self.emit(&format!("\x1b[90m{}\x1b[0m", "─".repeat(title.len())));  // Dark gray line
         } else {
-            println!("\n{}", title);
-            println!("{}", "─".repeat(title.len()));
+            self.emit(&format!("\n{}", title));
+            self.emit(&"─".repeat(title.len()));
         }
     }
     
     pub fn subheader(&self, title: &str) {
-        println!();
+        self.emit("");
         if self.use_color {
-            println!("\n\x1b[1m{}\x1b[0m", title);  // Bold
+            self.emit(&format!("\n\x1b[1m{}\x1b[0m", title));  // Bold
         } else {
-            println!("\n{}", title);
+            self.emit(&format!("\n{}", title));
         }
     }
     
     pub fn print_prefix(&self, prefix: &str, color: &str, message: &str) {
-        println!();
+        self.emit("");
         if self.use_color {
             let color_code = match color {
                 "green" => "\x1b[32m",
@@ -70,42 +166,127 @@ impl Printer {
                 "magenta" => "\x1b[35m",
                 _ => "\x1b[0m",
             };
-            println!("{}{}\x1b[0m {}", color_code, prefix, message);
+            self.emit(&format!("{}{}\x1b[0m {}", color_code, prefix, message));
         } else {
-            println!("{} {}", prefix, message);
+            self.emit(&format!("{} {}", prefix, message));
         }
     }
     
     pub fn print_key_value(&self, key: &str, value: &str, indent: usize) {
         let indent_str = " ".repeat(indent);
         if self.use_color {
-            println!("{}\x1b[1m{}:\x1b[0m {}", indent_str, key, value);  // Bold key
+            self.emit(&format!("{}\x1b[1m{}:\x1b[0m {}", indent_str, key, value));  // Bold key
         } else {
-            println!("{}{}: {}", indent_str, key, value);
+            self.emit(&format!("{}{}: {}", indent_str, key, value));
         }
     }
     #[allow(dead_code)]
     pub fn print_list_item(&self, item: &str, indent: usize) {
         let indent_str = " ".repeat(indent);
         if self.use_color {
-            println!("{}\x1b[36m•\x1b[0m {}", indent_str, item);  // Cyan bullet
+            self.emit(&format!("{}\x1b[36m•\x1b[0m {}", indent_str, item));  // Cyan bullet
         } else {
-            println!("{}• {}", indent_str, item);
+            self.emit(&format!("{}• {}", indent_str, item));
         }
     }
     #[allow(dead_code)]
     pub fn print_indented(&self, text: &str, indent: usize) {
         let indent_str = " ".repeat(indent);
         for line in text.lines() {
-            println!("{}{}", indent_str, line);
+            self.emit(&format!("{}{}", indent_str, line));
+        }
+    }
+    // Indented code block: leading verb in cyan, strings in green, numbers in yellow
+    pub fn print_code(&self, code: &str) {
+        for line in code.lines() {
+            if self.use_color {
+                self.emit(&format!("    {}", highlight_line(line)));
+            } else {
+                self.emit(&format!("    {}", line));
+            }
         }
     }
     #[allow(dead_code)]
     pub fn separator(&self) {
         if self.use_color {
-            println!("\x1b[90m{}\x1b[0m", "─".repeat(60));
+            self.emit(&format!("\x1b[90m{}\x1b[0m", "─".repeat(60)));
+        } else {
+            self.emit(&"─".repeat(60));
+        }
+    }
+}
+fn strip_ansi(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip the CSI sequence up to its final letter, e.g. \x1b[1;36m
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+fn highlight_line(line: &str) -> String {
+    let mut out = String::new();
+    let mut chars = line.char_indices().peekable();
+    let verb_end = line.find(|c: char| c.is_whitespace() || c == '(').unwrap_or(line.len());
+    if verb_end > 0 {
+        out.push_str(&format!("\x1b[36m{}\x1b[0m", &line[..verb_end]));
+        while chars.peek().is_some_and(|(i, _)| *i < verb_end) {
+            chars.next();
+        }
+    }
+
+    while let Some((_, c)) = chars.next() {
+        if c == '"' {
+            let mut literal = String::from('"');
+            for (_, next) in chars.by_ref() {
+                literal.push(next);
+                if next == '"' {
+                    break;
+                }
+            }
+            out.push_str(&format!("\x1b[32m{}\x1b[0m", literal));
+        } else if c.is_ascii_digit() && !out.ends_with(|p: char| p.is_alphanumeric() || p == '_') {
+            let mut number = String::from(c);
+            while let Some((_, next)) = chars.next_if(|(_, n)| n.is_ascii_digit() || *n == '.') {
+                number.push(next);
+            }
+            out.push_str(&format!("\x1b[33m{}\x1b[0m", number));
         } else {
-            println!("{}", "─".repeat(60));
+            out.push(c);
         }
     }
-}
\ No newline at end of file
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_override_wins() {
+        // The override is process-wide; tests only ever turn color off
+        set_color_override(false);
+        std::env::set_var("TERM", "xterm-256color");
+        assert!(!Printer::new().use_color);
+        assert!(Printer::new_with_color(true).use_color);
+    }
+
+    #[test]
+    fn test_highlight_line() {
+        assert_eq!(
+            highlight_line("set name = \"a1\" + 42"),
+            "\x1b[36mset\x1b[0m name = \x1b[32m\"a1\"\x1b[0m + \x1b[33m42\x1b[0m"
+        );
+        // Digits inside identifiers stay plain
+        assert_eq!(highlight_line("set x2 = 1.5"), "\x1b[36mset\x1b[0m x2 = \x1b[33m1.5\x1b[0m");
+    }
+}